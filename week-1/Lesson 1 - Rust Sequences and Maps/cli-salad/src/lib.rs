@@ -40,29 +40,32 @@
 //!
 //! # How is the Vec fruits shuffled in the create_fruit_salad function?
 //!
-//! The shuffling of the `Vec` containing fruits in the `create_fruit_salad` function is achieved
-//! through the use of the `shuffle` method provided by the `rand` crate, specifically through its
-//! `SliceRandom` trait. This method takes a mutable reference to a slice (which the `Vec` can be
-//! coerced into) and a mutable reference to a random number generator (RNG) and randomly
-//! reorders the elements within the slice in-place.
+//! The `create_fruit_salad` function leaves the shuffle to
+//! `fruit_catalogue::sample_without_replacement`, the sampler shared by the fruit salad lessons,
+//! passing it the `FRUITS` catalogue. Picking `num_fruits` distinct fruits at random is the same
+//! as shuffling the list with the `shuffle` method of `rand`'s `SliceRandom` trait, which takes a
+//! mutable reference to a slice and a mutable reference to a random number generator (RNG) and
+//! randomly reorders the elements in-place, and then taking the first `num_fruits`. The sampler
+//! draws the positions with `rand::seq::index::sample`, which only does as much of the shuffle as
+//! it needs.
 //!
 //! Here's a breakdown of the process:
 //!
-//! 1. **RNG Initialization**: First, a random number generator is initialized by calling
-//! `thread_rng()`, which provides a thread-local RNG seeded by the system.
+//! 1. **RNG Initialization**: The caller passes in the random number generator, e.g.
+//! `thread_rng()`, which provides a thread-local RNG seeded by the system. Any `R: Rng` works, so
+//! a test can pass a seeded RNG and get the same salad every time.
 //!
-//! 2. **Shuffling**: The `shuffle` method is then called on the `fruits` vector with the
-//! `&mut rng` passed as an argument. This method mutates the vector, randomly permuting the
-//! elements it contains.
+//! 2. **Shuffling**: `sample_without_replacement` is called with the `&mut rng` and picks
+//! `num_fruits` distinct random positions of `FRUITS`, in random order, as if the list had been
+//! shuffled.
 //!
-//! 3. **Selecting a Subset**: After shuffling, the function selects a subset of the shuffled
-//! fruits using `into_iter().take(num_fruits).collect()`. This takes the first `num_fruits`
-//! elements from the shuffled list and collects them into a new `Vec<String>`, which is then
-//! returned.
+//! 3. **Selecting a Subset**: The fruits at those positions are collected into a new `Vec`, which
+//! the function turns into a `Vec<String>` and returns. Asking for more fruits than the catalogue
+//! holds is an error rather than a shorter salad.
 //!
 //! This approach allows for the creation of a random assortment of fruits from the predefined
 //! list, with the number of fruits in the final salad determined by the `num_fruits` parameter.
-//! The use of `SliceRandom::shuffle` ensures that the selection is varied and unpredictable,
+//! Sampling without replacement ensures that the selection is varied and unpredictable,
 //! making the function versatile for generating different combinations of fruit salads.
 //!
 //! # Why is there a need to convert the fruits Vec into an iterator and then take only a specific
@@ -70,8 +73,8 @@
 //!
 //! The conversion of the `fruits` Vec into an iterator followed by taking only a specific number
 //! of fruits is a method to efficiently create a subset of the original list based on the
-//! `num_fruits` parameter. This technique serves several purposes in the context of the
-//! `create_fruit_salad` function:
+//! `num_fruits` parameter. `sample_without_replacement` now does this for `create_fruit_salad`,
+//! but the shuffle-then-take it replaces served several purposes:
 //!
 //! 1. **Dynamic Subset Selection**: By converting the `Vec` to an iterator, we can use the `take`
 //! method to easily specify how many elements (fruits in this case) we want to include in the
@@ -98,26 +101,16 @@
 //! dynamic subset of the original collection, perfectly suited for the `create_fruit_salad`
 //! function's requirements.
 
-use rand::seq::SliceRandom;
-use rand::thread_rng;
+// The answers above keep their original list layout.
+#![allow(clippy::doc_lazy_continuation)]
 
-pub fn create_fruit_salad(num_fruits: usize) -> Vec<String> {
-    let fruits = vec![
-        "Arbutus".to_string(),
-        "Loquat".to_string(),
-        "Strawberry Tree Berry".to_string(),
-        "Pomegranate".to_string(),
-        "Fig".to_string(),
-        "Cherry".to_string(),
-        "Orange".to_string(),
-        "Pear".to_string(),
-        "Peach".to_string(),
-        "Apple".to_string(),
-    ];
+use fruit_catalogue::{sample_without_replacement, NotEnoughItems, FRUITS};
+use rand::Rng;
 
-    let mut rng = thread_rng();
-    let mut fruits = fruits;
-    fruits.shuffle(&mut rng);
-
-    fruits.into_iter().take(num_fruits).collect()
-}
\ No newline at end of file
+pub fn create_fruit_salad<R: Rng + ?Sized>(
+    num_fruits: usize,
+    rng: &mut R,
+) -> Result<Vec<String>, NotEnoughItems> {
+    let fruits = sample_without_replacement(&FRUITS, num_fruits, rng)?;
+    Ok(fruits.into_iter().map(String::from).collect())
+}
@@ -1,5 +1,7 @@
 use clap::Parser;
 use cli_salad::create_fruit_salad;
+use fruit_catalogue::FRUITS;
+use rand::thread_rng;
 
 #[derive(Parser)]
 #[clap(
@@ -22,15 +24,16 @@ fn main() {
     let select = opts.select;
 
 
-    // Create the fruit salad
-    let mut fruits = create_fruit_salad(num_fruits);
-
-    // Challenge(3): Handle invalid number of fruits 
-    if num_fruits > fruits.len() {
-        println!("Error: Cannot generate {} fruits. There are only {} fruits in the salad.", num_fruits, fruits.len());
+    // Challenge(3): Handle invalid number of fruits
+    if num_fruits > FRUITS.len() {
+        println!("Error: Cannot generate {} fruits. There are only {} fruits in the catalogue.", num_fruits, FRUITS.len());
         std::process::exit(1);
     }
 
+    // Create the fruit salad
+    let mut fruits = create_fruit_salad(num_fruits, &mut thread_rng())
+        .expect("num_fruits is at most FRUITS.len()");
+
     // Challenge(1): Select the fruits the user wants
     if select {
         let mut selection = Vec::new();
@@ -0,0 +1,166 @@
+//! The fruit catalogue shared by the fruit salad lessons, and helpers to sample from it.
+//!
+//! Every sampler takes the random number generator as a generic `R: Rng`, so callers can pass
+//! `thread_rng()` in a program and a seeded `StdRng` in a test that needs repeatable results.
+//!
+//! ```
+//! use fruit_catalogue::{sample_without_replacement, FRUITS};
+//!
+//! let salad = sample_without_replacement(&FRUITS, 4, &mut rand::thread_rng()).unwrap();
+//! assert_eq!(salad.len(), 4);
+//! ```
+
+use std::error::Error;
+use std::fmt;
+
+use rand::seq::{index, SliceRandom};
+use rand::Rng;
+
+/// Every fruit used by the lessons, in alphabetical order.
+pub const FRUITS: [&str; 18] = [
+    "Apple",
+    "Arbutus",
+    "Banana",
+    "Cherry",
+    "Date",
+    "Elderberry",
+    "Fig",
+    "Grape",
+    "Honeydew",
+    "Loquat",
+    "Orange",
+    "Peach",
+    "Pear",
+    "Plum",
+    "Pomegranate",
+    "Strawberry",
+    "Strawberry Tree Berry",
+    "Watermelon",
+];
+
+/// Returned when more distinct items are requested than there are to choose from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotEnoughItems {
+    pub requested: usize,
+    pub available: usize,
+}
+
+impl fmt::Display for NotEnoughItems {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "cannot pick {} distinct items, there are only {}",
+            self.requested, self.available
+        )
+    }
+}
+
+impl Error for NotEnoughItems {}
+
+/// One item picked uniformly at random, or `None` if `items` is empty.
+pub fn choose<'a, T, R: Rng + ?Sized>(items: &'a [T], rng: &mut R) -> Option<&'a T> {
+    items.choose(rng)
+}
+
+/// `amount` items picked independently, so the same item can come up more than once.
+/// Returns an empty vector if `items` is empty.
+pub fn sample_with_replacement<T: Clone, R: Rng + ?Sized>(
+    items: &[T],
+    amount: usize,
+    rng: &mut R,
+) -> Vec<T> {
+    if items.is_empty() {
+        return Vec::new();
+    }
+    (0..amount)
+        .map(|_| items[rng.gen_range(0..items.len())].clone())
+        .collect()
+}
+
+/// `amount` distinct positions of `items`, in random order. Equal values at different positions
+/// can both be picked.
+pub fn sample_without_replacement<T: Clone, R: Rng + ?Sized>(
+    items: &[T],
+    amount: usize,
+    rng: &mut R,
+) -> Result<Vec<T>, NotEnoughItems> {
+    if amount > items.len() {
+        return Err(NotEnoughItems {
+            requested: amount,
+            available: items.len(),
+        });
+    }
+    Ok(index::sample(rng, items.len(), amount)
+        .into_iter()
+        .map(|i| items[i].clone())
+        .collect())
+}
+
+/// A salad of `amount` different fruits from [`FRUITS`], in random order.
+pub fn random_salad<R: Rng + ?Sized>(
+    amount: usize,
+    rng: &mut R,
+) -> Result<Vec<&'static str>, NotEnoughItems> {
+    sample_without_replacement(&FRUITS, amount, rng)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::collections::HashSet;
+
+    fn rng() -> StdRng {
+        StdRng::seed_from_u64(42)
+    }
+
+    #[test]
+    fn catalogue_is_sorted_and_unique() {
+        assert!(FRUITS.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn without_replacement_never_repeats() {
+        let mut rng = rng();
+        for amount in 0..=FRUITS.len() {
+            let salad = random_salad(amount, &mut rng).unwrap();
+            let distinct: HashSet<_> = salad.iter().collect();
+            assert_eq!(salad.len(), amount);
+            assert_eq!(distinct.len(), amount);
+        }
+    }
+
+    #[test]
+    fn without_replacement_rejects_too_many() {
+        assert_eq!(
+            sample_without_replacement(&["a", "b"], 3, &mut rng()),
+            Err(NotEnoughItems {
+                requested: 3,
+                available: 2
+            })
+        );
+    }
+
+    #[test]
+    fn with_replacement_can_repeat() {
+        let picks = sample_with_replacement(&["only"], 3, &mut rng());
+        assert_eq!(picks, vec!["only"; 3]);
+        assert!(sample_with_replacement::<&str, _>(&[], 3, &mut rng()).is_empty());
+    }
+
+    #[test]
+    fn seeded_rngs_repeat_their_picks() {
+        let first = random_salad(5, &mut rng()).unwrap();
+        let second = random_salad(5, &mut rng()).unwrap();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn accepts_unsized_rngs() {
+        let mut rng = rng();
+        let rng: &mut dyn rand::RngCore = &mut rng;
+        assert!(choose(&FRUITS, rng).is_some());
+        assert_eq!(choose::<&str, _>(&[], rng), None);
+    }
+}
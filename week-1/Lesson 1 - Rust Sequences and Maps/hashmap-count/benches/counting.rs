@@ -0,0 +1,49 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use hashmap_count::parallel::{count_dashmap, count_mutex, count_sequential, count_sharded};
+
+/// 100M integers drawn from 100k distinct keys.
+const INPUT_LEN: usize = 100_000_000;
+const DISTINCT_KEYS: u64 = 100_000;
+
+fn input() -> Vec<i32> {
+    // Cheap LCG so generating the input does not dominate the benchmark setup.
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    (0..INPUT_LEN)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            ((state >> 33) % DISTINCT_KEYS) as i32
+        })
+        .collect()
+}
+
+fn counting_benchmarks(c: &mut Criterion) {
+    let numbers = input();
+    let mut group = c.benchmark_group("counting");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(numbers.len() as u64));
+
+    group.bench_with_input(
+        BenchmarkId::new("single_threaded", INPUT_LEN),
+        &numbers,
+        |b, n| b.iter(|| count_sequential(n)),
+    );
+    group.bench_with_input(
+        BenchmarkId::new("mutex_hashmap", INPUT_LEN),
+        &numbers,
+        |b, n| b.iter(|| count_mutex(n)),
+    );
+    group.bench_with_input(BenchmarkId::new("dashmap", INPUT_LEN), &numbers, |b, n| {
+        b.iter(|| count_dashmap(n))
+    });
+    group.bench_with_input(
+        BenchmarkId::new("shard_and_merge", INPUT_LEN),
+        &numbers,
+        |b, n| b.iter(|| count_sharded(n)),
+    );
+    group.finish();
+}
+
+criterion_group!(benches, counting_benchmarks);
+criterion_main!(benches);
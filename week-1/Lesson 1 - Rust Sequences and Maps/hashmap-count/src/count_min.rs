@@ -0,0 +1,156 @@
+//! Count-Min Sketch for approximate frequencies.
+//!
+//! The sketch is a `depth x width` table of counters. Each row uses its own hash function, and an
+//! item increments one counter per row. The estimate is the minimum over the rows, so it can only
+//! over-count. With `width = ⌈e / ε⌉` and `depth = ⌈ln(1 / δ)⌉` the estimate exceeds the true count
+//! by more than `ε * total` with probability at most `δ`.
+
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+use crate::counter::{seeded_hash, FrequencyEstimator, StreamCounter};
+
+/// Counts items of type `T`. The sketch only stores hashes of them, never the items themselves.
+#[derive(Debug, Clone)]
+pub struct CountMinSketch<T> {
+    width: usize,
+    depth: usize,
+    table: Vec<u64>,
+    total: u64,
+    items: PhantomData<fn(&T)>,
+}
+
+impl<T: Hash> CountMinSketch<T> {
+    /// Creates a sketch with an explicit table size.
+    pub fn new(width: usize, depth: usize) -> Self {
+        assert!(width > 0, "width must be greater than zero");
+        assert!(depth > 0, "depth must be greater than zero");
+        Self {
+            width,
+            depth,
+            table: vec![0; width * depth],
+            total: 0,
+            items: PhantomData,
+        }
+    }
+
+    /// Creates a sketch whose estimates are within `epsilon * total` of the true count with
+    /// probability `1 - delta`.
+    pub fn with_error(epsilon: f64, delta: f64) -> Self {
+        assert!(
+            epsilon > 0.0 && epsilon < 1.0,
+            "epsilon must be in the range (0, 1)"
+        );
+        assert!(
+            delta > 0.0 && delta < 1.0,
+            "delta must be in the range (0, 1)"
+        );
+        let width = (std::f64::consts::E / epsilon).ceil() as usize;
+        let depth = (1.0 / delta).ln().ceil() as usize;
+        Self::new(width, depth.max(1))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Adds the counters of `other` into this sketch. Both sketches must have the same shape.
+    pub fn merge(&mut self, other: &Self) {
+        assert_eq!(
+            (self.width, self.depth),
+            (other.width, other.depth),
+            "cannot merge sketches of different shapes"
+        );
+        for (mine, theirs) in self.table.iter_mut().zip(&other.table) {
+            *mine += theirs;
+        }
+        self.total += other.total;
+    }
+
+    fn cell(&self, item: &T, row: usize) -> usize {
+        row * self.width + (seeded_hash(item, row as u64) % self.width as u64) as usize
+    }
+}
+
+impl<T: Hash> StreamCounter<T> for CountMinSketch<T> {
+    fn observe(&mut self, item: &T) {
+        for row in 0..self.depth {
+            let cell = self.cell(item, row);
+            self.table[cell] += 1;
+        }
+        self.total += 1;
+    }
+
+    fn total(&self) -> u64 {
+        self.total
+    }
+}
+
+impl<T: Hash> FrequencyEstimator<T> for CountMinSketch<T> {
+    fn frequency(&self, item: &T) -> u64 {
+        (0..self.depth)
+            .map(|row| self.table[self.cell(item, row)])
+            .min()
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::counter::ExactCounter;
+
+    #[test]
+    fn with_error_sizes_the_table() {
+        let sketch: CountMinSketch<i32> = CountMinSketch::with_error(0.01, 0.01);
+        assert_eq!(sketch.width(), 272);
+        assert_eq!(sketch.depth(), 5);
+    }
+
+    #[test]
+    fn estimates_never_under_count_and_stay_within_bounds() {
+        let epsilon = 0.001;
+        let mut sketch = CountMinSketch::with_error(epsilon, 0.01);
+        let mut exact = ExactCounter::new();
+
+        // Skewed stream: small numbers are much more frequent than large ones.
+        let numbers: Vec<i32> = (0..100_000i64)
+            .map(|i| ((i * i) % 5_000 / 7) as i32)
+            .collect();
+        sketch.observe_all(&numbers);
+        exact.observe_all(&numbers);
+
+        let bound = (epsilon * numbers.len() as f64).ceil() as u64;
+        let mut within_bound = 0;
+        for (number, count) in exact.counts() {
+            let estimate = sketch.frequency(number);
+            assert!(estimate >= *count);
+            if estimate - count <= bound {
+                within_bound += 1;
+            }
+        }
+        assert!(within_bound as f64 >= 0.99 * exact.counts().len() as f64);
+    }
+
+    #[test]
+    fn merge_equals_single_sketch() {
+        let mut left = CountMinSketch::new(64, 4);
+        let mut right = CountMinSketch::new(64, 4);
+        let mut whole = CountMinSketch::new(64, 4);
+        for number in 0..1_000 {
+            if number % 2 == 0 {
+                left.observe(&number);
+            } else {
+                right.observe(&number);
+            }
+            whole.observe(&number);
+        }
+        left.merge(&right);
+        assert_eq!(left.table, whole.table);
+        assert_eq!(left.total(), 1_000);
+    }
+}
@@ -0,0 +1,125 @@
+//! Common counting traits and the exact `HashMap` based implementation.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+/// A counter that consumes a stream of items one at a time.
+pub trait StreamCounter<T> {
+    /// Records one occurrence of `item`.
+    fn observe(&mut self, item: &T);
+
+    /// Records every item yielded by `items`.
+    fn observe_all<'a, I>(&mut self, items: I)
+    where
+        I: IntoIterator<Item = &'a T>,
+        T: 'a,
+    {
+        for item in items {
+            self.observe(item);
+        }
+    }
+
+    /// Total number of observed items, duplicates included.
+    fn total(&self) -> u64;
+}
+
+/// A counter that can answer "how many times was `item` seen?".
+pub trait FrequencyEstimator<T>: StreamCounter<T> {
+    fn frequency(&self, item: &T) -> u64;
+}
+
+/// A counter that can answer "how many distinct items were seen?".
+pub trait CardinalityEstimator<T>: StreamCounter<T> {
+    fn distinct(&self) -> u64;
+}
+
+/// A counter that can report the most frequent items.
+pub trait HeavyHitters<T>: StreamCounter<T> {
+    /// Returns up to `k` items with their counts, most frequent first.
+    fn top_k(&self, k: usize) -> Vec<(T, u64)>;
+}
+
+/// Hashes `item` together with `seed`, giving a family of independent hash functions.
+pub(crate) fn seeded_hash<T: Hash + ?Sized>(item: &T, seed: u64) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    seed.hash(&mut hasher);
+    item.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Exact counting with one `HashMap` entry per distinct item.
+#[derive(Debug, Clone, Default)]
+pub struct ExactCounter<T: Eq + Hash> {
+    counts: HashMap<T, u64>,
+    total: u64,
+}
+
+impl<T: Eq + Hash> ExactCounter<T> {
+    pub fn new() -> Self {
+        Self {
+            counts: HashMap::new(),
+            total: 0,
+        }
+    }
+
+    pub fn counts(&self) -> &HashMap<T, u64> {
+        &self.counts
+    }
+}
+
+impl<T: Eq + Hash + Clone> StreamCounter<T> for ExactCounter<T> {
+    fn observe(&mut self, item: &T) {
+        *self.counts.entry(item.clone()).or_insert(0) += 1;
+        self.total += 1;
+    }
+
+    fn total(&self) -> u64 {
+        self.total
+    }
+}
+
+impl<T: Eq + Hash + Clone> FrequencyEstimator<T> for ExactCounter<T> {
+    fn frequency(&self, item: &T) -> u64 {
+        self.counts.get(item).copied().unwrap_or(0)
+    }
+}
+
+impl<T: Eq + Hash + Clone> CardinalityEstimator<T> for ExactCounter<T> {
+    fn distinct(&self) -> u64 {
+        self.counts.len() as u64
+    }
+}
+
+impl<T: Eq + Hash + Clone + Ord> HeavyHitters<T> for ExactCounter<T> {
+    fn top_k(&self, k: usize) -> Vec<(T, u64)> {
+        let mut items: Vec<(T, u64)> = self
+            .counts
+            .iter()
+            .map(|(item, count)| (item.clone(), *count))
+            .collect();
+        items.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        items.truncate(k);
+        items
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exact_counter_matches_logic() {
+        let numbers = vec![1, 2, 3, 4, 7, 7, 5, 6, 1, 7, 1, 8, 2, 2, 2, 2, 9, 10];
+        let mut counter = ExactCounter::new();
+        counter.observe_all(&numbers);
+
+        let expected = crate::logic(numbers.clone());
+        for (number, count) in expected {
+            assert_eq!(counter.frequency(&number), count as u64);
+        }
+        assert_eq!(counter.total(), numbers.len() as u64);
+        assert_eq!(counter.distinct(), 10);
+        assert_eq!(counter.top_k(2), vec![(2, 5), (1, 3)]);
+    }
+}
@@ -0,0 +1,151 @@
+//! HyperLogLog for approximate distinct counts.
+//!
+//! The first `precision` bits of an item's hash pick one of `2^precision` registers, and the
+//! register keeps the longest run of leading zeros seen in the remaining bits. The harmonic mean of
+//! the registers estimates the cardinality with a standard error of about `1.04 / sqrt(2^precision)`.
+
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+use crate::counter::{seeded_hash, CardinalityEstimator, StreamCounter};
+
+const HASH_SEED: u64 = 0x5eed;
+
+/// Counts distinct items of type `T`. Only their hashes are kept, never the items themselves.
+#[derive(Debug, Clone)]
+pub struct HyperLogLog<T> {
+    precision: u8,
+    registers: Vec<u8>,
+    total: u64,
+    items: PhantomData<fn(&T)>,
+}
+
+impl<T: Hash> HyperLogLog<T> {
+    /// Creates an estimator with `2^precision` registers. `precision` must be in `4..=16`.
+    pub fn new(precision: u8) -> Self {
+        assert!(
+            (4..=16).contains(&precision),
+            "precision must be between 4 and 16"
+        );
+        Self {
+            precision,
+            registers: vec![0; 1 << precision],
+            total: 0,
+            items: PhantomData,
+        }
+    }
+
+    /// Expected relative standard error of the estimate.
+    pub fn standard_error(&self) -> f64 {
+        1.04 / (self.registers.len() as f64).sqrt()
+    }
+
+    /// Combines `other` into this estimator, as if it had seen both streams.
+    pub fn merge(&mut self, other: &Self) {
+        assert_eq!(
+            self.precision, other.precision,
+            "cannot merge estimators of different precision"
+        );
+        for (mine, theirs) in self.registers.iter_mut().zip(&other.registers) {
+            *mine = (*mine).max(*theirs);
+        }
+        self.total += other.total;
+    }
+
+    fn alpha(&self) -> f64 {
+        let m = self.registers.len() as f64;
+        match self.registers.len() {
+            16 => 0.673,
+            32 => 0.697,
+            64 => 0.709,
+            _ => 0.7213 / (1.0 + 1.079 / m),
+        }
+    }
+}
+
+impl<T: Hash> StreamCounter<T> for HyperLogLog<T> {
+    fn observe(&mut self, item: &T) {
+        let hash = seeded_hash(item, HASH_SEED);
+        let index = (hash >> (64 - self.precision)) as usize;
+        // Set a sentinel bit so an all-zero remainder still yields a bounded rank.
+        let remainder = (hash << self.precision) | (1 << (self.precision - 1));
+        let rank = remainder.leading_zeros() as u8 + 1;
+        self.registers[index] = self.registers[index].max(rank);
+        self.total += 1;
+    }
+
+    fn total(&self) -> u64 {
+        self.total
+    }
+}
+
+impl<T: Hash> CardinalityEstimator<T> for HyperLogLog<T> {
+    fn distinct(&self) -> u64 {
+        let m = self.registers.len() as f64;
+        let sum: f64 = self
+            .registers
+            .iter()
+            .map(|&register| 2f64.powi(-i32::from(register)))
+            .sum();
+        let estimate = self.alpha() * m * m / sum;
+
+        // Small range correction: fall back to linear counting while registers are still empty.
+        let zeros = self
+            .registers
+            .iter()
+            .filter(|&&register| register == 0)
+            .count();
+        if estimate <= 2.5 * m && zeros > 0 {
+            (m * (m / zeros as f64).ln()).round() as u64
+        } else {
+            estimate.round() as u64
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::counter::ExactCounter;
+
+    #[test]
+    fn empty_estimator_counts_zero() {
+        let hll: HyperLogLog<i32> = HyperLogLog::new(10);
+        assert_eq!(hll.distinct(), 0);
+    }
+
+    #[test]
+    fn estimate_is_close_to_exact_distinct_count() {
+        let mut hll = HyperLogLog::new(12);
+        let mut exact = ExactCounter::new();
+
+        let numbers: Vec<i32> = (0..200_000).map(|i| i % 50_000).collect();
+        hll.observe_all(&numbers);
+        exact.observe_all(&numbers);
+
+        let truth = exact.distinct() as f64;
+        let estimate = hll.distinct() as f64;
+        let error = (estimate - truth).abs() / truth;
+        assert!(error < 4.0 * hll.standard_error(), "relative error {error}");
+    }
+
+    #[test]
+    fn small_cardinalities_are_nearly_exact() {
+        let mut hll = HyperLogLog::new(14);
+        hll.observe_all(&(0..100).collect::<Vec<i32>>());
+        let estimate = hll.distinct();
+        assert!((98..=102).contains(&estimate), "estimate {estimate}");
+    }
+
+    #[test]
+    fn merge_is_union() {
+        let mut left = HyperLogLog::new(12);
+        let mut right = HyperLogLog::new(12);
+        left.observe_all(&(0..10_000).collect::<Vec<i32>>());
+        right.observe_all(&(5_000..15_000).collect::<Vec<i32>>());
+        left.merge(&right);
+
+        let estimate = left.distinct() as f64;
+        assert!((estimate - 15_000.0).abs() / 15_000.0 < 4.0 * left.standard_error());
+    }
+}
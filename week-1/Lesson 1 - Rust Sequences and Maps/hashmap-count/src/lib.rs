@@ -0,0 +1,40 @@
+//! Frequency counting over integer streams.
+//!
+//! The exact `HashMap` counter in [`logic`] needs one entry per distinct key, which does not fit
+//! streams with billions of rows. The sketches in this crate trade exactness for bounded memory:
+//!
+//! - [`CountMinSketch`]: approximate frequencies, never under-counts.
+//! - [`HyperLogLog`]: approximate number of distinct items.
+//! - [`SpaceSaving`]: heavy hitters (most frequent items) with a fixed number of counters.
+//!
+//! All of them implement [`StreamCounter`], so exact and approximate counting can be swapped.
+//! For exact counting on many cores, [`parallel::count_sharded`] counts chunks independently on
+//! rayon workers and merges the partial maps.
+
+use std::collections::HashMap;
+
+pub mod count_min;
+pub mod counter;
+pub mod hyperloglog;
+pub mod parallel;
+pub mod space_saving;
+
+pub use count_min::CountMinSketch;
+pub use counter::{
+    CardinalityEstimator, ExactCounter, FrequencyEstimator, HeavyHitters, StreamCounter,
+};
+pub use hyperloglog::HyperLogLog;
+pub use parallel::{count_dashmap, count_mutex, count_sharded, merge_counts};
+pub use space_saving::SpaceSaving;
+
+/// Counts how many times each number appears in `numbers`.
+pub fn logic(numbers: Vec<i32>) -> HashMap<i32, u32> {
+    let mut frequencies = HashMap::new();
+
+    for number in numbers {
+        let frecuency = frequencies.entry(number).or_insert(0);
+        *frecuency += 1;
+    }
+
+    frequencies
+}
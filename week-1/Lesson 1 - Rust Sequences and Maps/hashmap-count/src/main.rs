@@ -26,25 +26,36 @@
 //! they are correctly paired in the final result.
 //!
 
-use std::collections::{BTreeMap, HashMap};
+use std::collections::BTreeMap;
 
-fn logic(numbers: Vec<i32>) -> HashMap<i32, u32> {
-    let mut frequencies = HashMap::new();
-
-    for number in numbers {
-        let frecuency = frequencies.entry(number).or_insert(0);
-        *frecuency += 1;
-    }
-
-    frequencies
-}
+use hashmap_count::{
+    count_sharded, logic, CardinalityEstimator, CountMinSketch, FrequencyEstimator, HeavyHitters, HyperLogLog,
+    SpaceSaving, StreamCounter,
+};
 
 fn main() {
     let numbers = vec![1, 2, 3, 4, 7, 7, 5, 6, 1, 7, 1, 8, 2, 2,2,2,9, 10];
-    let result = logic(numbers);
+    let result = logic(numbers.clone());
 
     // Challenge (3): How would you modify the program to sort the final result by frequency?
     let result: BTreeMap<&i32, &u32> = result.iter().collect();
 
     println!("The frequency of each number in the vector is: {:?}", result);
+
+    // Parallel counting: per-worker maps merged in a tree reduction
+    let parallel = count_sharded(&numbers);
+    let parallel: BTreeMap<&i32, &u32> = parallel.iter().collect();
+    assert_eq!(parallel, result);
+
+    // Approximate counting for streams that do not fit in an exact HashMap
+    let mut count_min = CountMinSketch::with_error(0.01, 0.01);
+    let mut hyperloglog = HyperLogLog::new(12);
+    let mut space_saving = SpaceSaving::new(4);
+    count_min.observe_all(&numbers);
+    hyperloglog.observe_all(&numbers);
+    space_saving.observe_all(&numbers);
+
+    println!("Count-Min estimate for 2: {}", count_min.frequency(&2));
+    println!("HyperLogLog distinct estimate: {}", hyperloglog.distinct());
+    println!("Space-Saving heavy hitters: {:?}", space_saving.top_k(2));
 }
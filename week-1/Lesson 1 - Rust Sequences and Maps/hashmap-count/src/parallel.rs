@@ -0,0 +1,104 @@
+//! Multi-threaded counting strategies.
+//!
+//! [`count_sharded`] is the preferred parallel path: every rayon worker counts its own chunk into
+//! a private `HashMap`, and the partial maps are merged pairwise in a tree reduction. There is no
+//! shared state while counting, so workers never contend. [`count_mutex`] and [`count_dashmap`]
+//! share a single map between all workers and are kept as baselines for the benchmarks.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use dashmap::DashMap;
+use rayon::prelude::*;
+
+/// Number of integers each rayon task counts before its partial map is merged.
+pub const DEFAULT_CHUNK_SIZE: usize = 1 << 16;
+
+/// Merges two partial counts, folding the smaller map into the larger one.
+pub fn merge_counts(
+    mut left: HashMap<i32, u32>,
+    mut right: HashMap<i32, u32>,
+) -> HashMap<i32, u32> {
+    if left.len() < right.len() {
+        std::mem::swap(&mut left, &mut right);
+    }
+    for (number, count) in right {
+        *left.entry(number).or_insert(0) += count;
+    }
+    left
+}
+
+/// Counts `numbers` on a single thread, without taking ownership of the input.
+pub fn count_sequential(numbers: &[i32]) -> HashMap<i32, u32> {
+    let mut frequencies = HashMap::new();
+    for &number in numbers {
+        *frequencies.entry(number).or_insert(0) += 1;
+    }
+    frequencies
+}
+
+/// Counts `numbers` in parallel with one local map per chunk and a tree reduction of the partials.
+pub fn count_sharded(numbers: &[i32]) -> HashMap<i32, u32> {
+    count_sharded_with_chunk_size(numbers, DEFAULT_CHUNK_SIZE)
+}
+
+/// Same as [`count_sharded`] with an explicit chunk size.
+pub fn count_sharded_with_chunk_size(numbers: &[i32], chunk_size: usize) -> HashMap<i32, u32> {
+    assert!(chunk_size > 0, "chunk size must be greater than zero");
+    numbers
+        .par_chunks(chunk_size)
+        .map(count_sequential)
+        .reduce(HashMap::new, merge_counts)
+}
+
+/// Counts `numbers` in parallel into a single `Mutex<HashMap>`.
+pub fn count_mutex(numbers: &[i32]) -> HashMap<i32, u32> {
+    let frequencies = Mutex::new(HashMap::new());
+    numbers.par_iter().for_each(|&number| {
+        *frequencies.lock().unwrap().entry(number).or_insert(0) += 1;
+    });
+    frequencies.into_inner().unwrap()
+}
+
+/// Counts `numbers` in parallel into a concurrent `DashMap`.
+pub fn count_dashmap(numbers: &[i32]) -> HashMap<i32, u32> {
+    let frequencies = DashMap::new();
+    numbers.par_iter().for_each(|&number| {
+        *frequencies.entry(number).or_insert(0) += 1;
+    });
+    frequencies.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Vec<i32> {
+        (0..200_000).map(|i| (i * 7919) % 1_009).collect()
+    }
+
+    #[test]
+    fn merge_adds_counts_of_shared_keys() {
+        let left = HashMap::from([(1, 2), (2, 1)]);
+        let right = HashMap::from([(2, 3), (3, 1), (4, 1)]);
+        let merged = merge_counts(left, right);
+        assert_eq!(merged, HashMap::from([(1, 2), (2, 4), (3, 1), (4, 1)]));
+    }
+
+    #[test]
+    fn all_strategies_agree_with_logic() {
+        let numbers = sample();
+        let expected = crate::logic(numbers.clone());
+
+        assert_eq!(count_sequential(&numbers), expected);
+        assert_eq!(count_sharded(&numbers), expected);
+        assert_eq!(count_sharded_with_chunk_size(&numbers, 3), expected);
+        assert_eq!(count_mutex(&numbers), expected);
+        assert_eq!(count_dashmap(&numbers), expected);
+    }
+
+    #[test]
+    fn empty_input_gives_empty_map() {
+        assert!(count_sharded(&[]).is_empty());
+    }
+}
@@ -0,0 +1,225 @@
+//! Space-Saving algorithm for heavy hitters.
+//!
+//! Keeps at most `capacity` monitored items. When a new item arrives and the table is full, the
+//! item with the smallest count is replaced and the newcomer inherits that count as its error.
+//! Any item whose true frequency exceeds `total / capacity` is guaranteed to be monitored.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::counter::{FrequencyEstimator, HeavyHitters, StreamCounter};
+
+#[derive(Debug, Clone)]
+struct Monitored<T> {
+    item: T,
+    count: u64,
+    error: u64,
+}
+
+/// The monitored items sit in a binary min-heap on their counts, with a map from each item to its
+/// position in the heap. Incrementing a count and evicting the minimum both take O(log capacity).
+#[derive(Debug, Clone)]
+pub struct SpaceSaving<T: Eq + Hash> {
+    capacity: usize,
+    heap: Vec<Monitored<T>>,
+    positions: HashMap<T, usize>,
+    total: u64,
+}
+
+impl<T: Eq + Hash + Clone> SpaceSaving<T> {
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "capacity must be greater than zero");
+        Self {
+            capacity,
+            heap: Vec::with_capacity(capacity),
+            positions: HashMap::with_capacity(capacity),
+            total: 0,
+        }
+    }
+
+    /// Maximum amount by which the reported count of `item` may exceed its true count.
+    pub fn error(&self, item: &T) -> Option<u64> {
+        self.monitored(item).map(|monitored| monitored.error)
+    }
+
+    /// Lower bound on the true count of `item`.
+    pub fn guaranteed(&self, item: &T) -> u64 {
+        self.monitored(item)
+            .map(|monitored| monitored.count - monitored.error)
+            .unwrap_or(0)
+    }
+
+    fn monitored(&self, item: &T) -> Option<&Monitored<T>> {
+        self.positions
+            .get(item)
+            .map(|&position| &self.heap[position])
+    }
+
+    /// Replaces the item with the smallest count by `item`, which inherits that count as its error.
+    fn replace_min(&mut self, item: &T) {
+        let error = self.heap[0].count;
+        let evicted = std::mem::replace(
+            &mut self.heap[0],
+            Monitored {
+                item: item.clone(),
+                count: error + 1,
+                error,
+            },
+        );
+        self.positions.remove(&evicted.item);
+        self.positions.insert(item.clone(), 0);
+        self.sift_down(0);
+    }
+
+    fn sift_up(&mut self, mut position: usize) {
+        while position > 0 {
+            let parent = (position - 1) / 2;
+            if self.heap[parent].count <= self.heap[position].count {
+                break;
+            }
+            self.swap(parent, position);
+            position = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut position: usize) {
+        loop {
+            let mut smallest = position;
+            for child in [2 * position + 1, 2 * position + 2] {
+                if child < self.heap.len() && self.heap[child].count < self.heap[smallest].count {
+                    smallest = child;
+                }
+            }
+            if smallest == position {
+                break;
+            }
+            self.swap(smallest, position);
+            position = smallest;
+        }
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        self.heap.swap(a, b);
+        for position in [a, b] {
+            *self
+                .positions
+                .get_mut(&self.heap[position].item)
+                .expect("every monitored item has a position") = position;
+        }
+    }
+}
+
+impl<T: Eq + Hash + Clone> StreamCounter<T> for SpaceSaving<T> {
+    fn observe(&mut self, item: &T) {
+        self.total += 1;
+        if let Some(&position) = self.positions.get(item) {
+            self.heap[position].count += 1;
+            self.sift_down(position);
+        } else if self.heap.len() == self.capacity {
+            self.replace_min(item);
+        } else {
+            self.positions.insert(item.clone(), self.heap.len());
+            self.heap.push(Monitored {
+                item: item.clone(),
+                count: 1,
+                error: 0,
+            });
+            self.sift_up(self.heap.len() - 1);
+        }
+    }
+
+    fn total(&self) -> u64 {
+        self.total
+    }
+}
+
+impl<T: Eq + Hash + Clone> FrequencyEstimator<T> for SpaceSaving<T> {
+    /// Upper bound on the true count; zero for items that are not monitored.
+    fn frequency(&self, item: &T) -> u64 {
+        self.monitored(item)
+            .map(|monitored| monitored.count)
+            .unwrap_or(0)
+    }
+}
+
+impl<T: Eq + Hash + Clone + Ord> HeavyHitters<T> for SpaceSaving<T> {
+    fn top_k(&self, k: usize) -> Vec<(T, u64)> {
+        let mut items: Vec<(T, u64)> = self
+            .heap
+            .iter()
+            .map(|monitored| (monitored.item.clone(), monitored.count))
+            .collect();
+        items.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        items.truncate(k);
+        items
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::counter::ExactCounter;
+
+    #[test]
+    fn exact_while_under_capacity() {
+        let numbers = vec![1, 2, 3, 4, 7, 7, 5, 6, 1, 7, 1, 8, 2, 2, 2, 2, 9, 10];
+        let mut space_saving = SpaceSaving::new(16);
+        let mut exact = ExactCounter::new();
+        space_saving.observe_all(&numbers);
+        exact.observe_all(&numbers);
+
+        assert_eq!(space_saving.top_k(3), exact.top_k(3));
+        assert_eq!(space_saving.error(&2), Some(0));
+    }
+
+    #[test]
+    fn keeps_the_smallest_count_at_the_root() {
+        let mut space_saving = SpaceSaving::new(8);
+        for i in 0..5_000u64 {
+            space_saving.observe(&(i * i % 37));
+            let heap = &space_saving.heap;
+            assert!((1..heap.len()).all(|child| heap[(child - 1) / 2].count <= heap[child].count));
+            assert!(heap
+                .iter()
+                .enumerate()
+                .all(|(position, monitored)| space_saving.positions[&monitored.item] == position));
+        }
+        // Every observation adds exactly one to the monitored counts, evictions included
+        let counted: u64 = space_saving
+            .heap
+            .iter()
+            .map(|monitored| monitored.count)
+            .sum();
+        assert_eq!(counted, space_saving.total());
+    }
+
+    #[test]
+    fn finds_heavy_hitters_in_a_long_tail() {
+        let capacity = 50;
+        let mut space_saving = SpaceSaving::new(capacity);
+        let mut exact = ExactCounter::new();
+
+        // Three heavy items interleaved with a long tail of unique items.
+        let numbers: Vec<i32> = (0..30_000)
+            .map(|i| match i % 10 {
+                0..=2 => -1,
+                3 | 4 => -2,
+                5 => -3,
+                _ => i,
+            })
+            .collect();
+        space_saving.observe_all(&numbers);
+        exact.observe_all(&numbers);
+
+        let top: Vec<i32> = space_saving.top_k(3).into_iter().map(|(n, _)| n).collect();
+        assert_eq!(top, vec![-1, -2, -3]);
+
+        let threshold = space_saving.total() / capacity as u64;
+        for (number, count) in exact.counts() {
+            if *count > threshold {
+                assert!(space_saving.frequency(number) >= *count);
+                assert!(space_saving.guaranteed(number) <= *count);
+            }
+        }
+    }
+}
@@ -0,0 +1,131 @@
+//! Language records and the loaders that read them from CSV or JSON datasets.
+
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Typing {
+    Static,
+    Dynamic,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Language {
+    pub name: String,
+    pub year: u32,
+    pub paradigm: String,
+    pub typing: Typing,
+}
+
+impl Language {
+    pub fn new(name: &str, year: u32, paradigm: &str, typing: Typing) -> Self {
+        Self {
+            name: name.to_string(),
+            year,
+            paradigm: paradigm.to_string(),
+            typing,
+        }
+    }
+}
+
+/// The 15 languages the lesson started with, used when no dataset is given.
+pub fn builtin() -> Vec<Language> {
+    vec![
+        Language::new("Rust", 2010, "multi-paradigm", Typing::Static),
+        Language::new("Python", 1991, "multi-paradigm", Typing::Dynamic),
+        Language::new("Java", 1995, "object-oriented", Typing::Static),
+        Language::new("C", 1972, "procedural", Typing::Static),
+        Language::new("C++", 1983, "multi-paradigm", Typing::Static),
+        Language::new("C#", 2000, "object-oriented", Typing::Static),
+        Language::new("PHP", 1995, "multi-paradigm", Typing::Dynamic),
+        Language::new("JavaScript", 1995, "multi-paradigm", Typing::Dynamic),
+        Language::new("Go", 2009, "procedural", Typing::Static),
+        Language::new("Swift", 2014, "multi-paradigm", Typing::Static),
+        Language::new("Kotlin", 2011, "multi-paradigm", Typing::Static),
+        Language::new("R", 1993, "functional", Typing::Dynamic),
+        Language::new("Ruby", 1995, "object-oriented", Typing::Dynamic),
+        Language::new("Scala", 2003, "multi-paradigm", Typing::Static),
+        Language::new("Dart", 2011, "object-oriented", Typing::Static),
+    ]
+}
+
+/// Reads language records from CSV with a `Name,Year,Paradigm,Typing` header.
+pub fn from_csv<R: Read>(reader: R) -> Result<Vec<Language>, Box<dyn Error>> {
+    let mut rdr = csv::Reader::from_reader(reader);
+    let mut languages = Vec::new();
+    for result in rdr.deserialize() {
+        languages.push(result?);
+    }
+    Ok(languages)
+}
+
+/// Reads language records from a JSON array of objects.
+pub fn from_json<R: Read>(reader: R) -> Result<Vec<Language>, Box<dyn Error>> {
+    Ok(serde_json::from_reader(reader)?)
+}
+
+/// Loads a dataset, picking the format from the file extension (`.csv` or `.json`).
+pub fn load(path: &Path) -> Result<Vec<Language>, Box<dyn Error>> {
+    let ext = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_ascii_lowercase);
+    match ext.as_deref() {
+        Some("csv") => from_csv(BufReader::new(File::open(path)?)),
+        Some("json") => from_json(BufReader::new(File::open(path)?)),
+        _ => Err(format!("unsupported dataset format: {}", path.display()).into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_csv_records() {
+        let data = "Name,Year,Paradigm,Typing\nRust,2010,multi-paradigm,static\nR,1993,functional,dynamic\n";
+        let languages = from_csv(data.as_bytes()).unwrap();
+        assert_eq!(
+            languages,
+            vec![
+                Language::new("Rust", 2010, "multi-paradigm", Typing::Static),
+                Language::new("R", 1993, "functional", Typing::Dynamic),
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_json_records() {
+        let data =
+            r#"[{"Name": "Go", "Year": 2009, "Paradigm": "procedural", "Typing": "static"}]"#;
+        let languages = from_json(data.as_bytes()).unwrap();
+        assert_eq!(
+            languages,
+            vec![Language::new("Go", 2009, "procedural", Typing::Static)]
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_typing() {
+        let data = "Name,Year,Paradigm,Typing\nRust,2010,multi-paradigm,gradual\n";
+        assert!(from_csv(data.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn bundled_datasets_match_builtin() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("data");
+        assert_eq!(load(&dir.join("languages.csv")).unwrap(), builtin());
+        assert_eq!(load(&dir.join("languages.json")).unwrap(), builtin());
+    }
+
+    #[test]
+    fn rejects_unknown_extension() {
+        assert!(load(Path::new("languages.xml")).is_err());
+    }
+}
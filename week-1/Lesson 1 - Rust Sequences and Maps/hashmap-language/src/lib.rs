@@ -0,0 +1,21 @@
+//! Language metadata and the weighting schemes computed from it.
+//!
+//! Records are loaded with [`language::load`] (CSV or JSON) or taken from [`language::builtin`],
+//! and any [`weighting::WeightingScheme`] turns them into weights between 1 and 100. The weights
+//! drive [`sampler::LanguagePicker`] and the ranking in [`report`].
+
+use std::collections::HashMap;
+
+pub mod language;
+pub mod report;
+pub mod sampler;
+pub mod weighting;
+
+pub use language::{Language, Typing};
+pub use sampler::{AliasTable, LanguagePicker};
+pub use weighting::{current_year, Log, MinMax, Rank, Scheme, WeightingScheme, ZScore};
+
+/// Min-max weights relative to `reference_year`. The input is left untouched.
+pub fn normalize(languages: &[Language], reference_year: u32) -> HashMap<String, u32> {
+    MinMax.weights(languages, reference_year)
+}
@@ -1,88 +1,101 @@
-//! # How does the calculate_weights function determine the weight of each language?
-//! 
-//! The `calculate_weights` function determines the weight of each language by normalizing the years of
-//! creation, calculating a weight based on the normalized year, and then inserting the language and its
-//! corresponding weight into a new HashMap.
+//! # How does the code determine the weight of each language?
 //!
-//! The normalization is performed by first calculating the range of years, then transforming each year into
-//! a normalized value between 0 and 1. The weight is then calculated based on the normalized year and
-//! inserted into the HashMap along with the language name.
+//! The weights come from the `weighting` module. Each language's age is its distance from a reference
+//! year (the current year unless `--reference-year` is given), so older languages weigh more. A
+//! `WeightingScheme` maps the ages onto scores between 0 and 1, and `WeightingScheme::weights` turns the
+//! scores into weights and collects them into a new HashMap keyed by language name.
 //!
-//! The specific steps are:
-//! 1. Calculate normalized year for each language based on the range of years.
-//! 2. Calculate weight based on the normalized year.
-//! 3. Insert the language and its corresponding weight into the new HashMap.
-//! 
-//! # How does the code ensure that the weights are normalized between 1 and 100?
+//! The schemes, picked with `--scheme`, differ in how they spread the ages:
+//! 1. `min-max` rescales the ages linearly, so the oldest language scores 1 and the newest 0.
+//! 2. `z-score` uses standard scores clamped to three standard deviations, so one outlier does not squash
+//!    every other language into a corner of the range.
+//! 3. `log` rescales `ln(1 + age)`, which compresses the differences between old languages.
+//! 4. `rank` only looks at the order of the ages, with ties sharing their average rank.
 //!
-//! The `normalize` function ensures that the weights are normalized between 1 and 100 by performing the
-//! following steps:
-//! 1. Calculating the range of years by finding the minimum and maximum year values in the `languages`
-//! map.
-//! 2. Calculating the normalized year for each language based on the range of years.
-//! 3. Calculating the weight based on the normalized year, ensuring it falls between 1 and 100.
-//! 4. Inserting the language and its corresponding weight into the `weights` HashMap.
+//! # How does the code ensure that the weights are normalized between 1 and 100?
 //!
-//! The code uses a formula to calculate the weight: `weight = (normalized_year * 99.0) as u32 + 1`, which
-//! ensures that the weights are normalized between 1 and 100.
+//! Every scheme returns scores in `[0, 1]`; when all ages are equal there is no range to spread them over,
+//! so every language scores 1. `to_weight` clamps each score to `[0, 1]` and maps it onto
+//! `MIN_WEIGHT..=MAX_WEIGHT` with `(score * 99.0) as u32 + 1`, so the weights always fall between 1 and
+//! 100 whichever scheme produced them.
 //!
-//! # Why does the code use values_mut when updating the years in the calculate_weights function?
+//! # Why does the code not use values_mut to update the years in place?
 //!
-//! The code uses `values_mut` when updating the years in the `normalize` function because it needs mutable
-//! references to the values in the hashmap in order to update them. This is necessary because the years are
-//! being modified in place, and `values_mut` provides a way to get mutable references to the values in the
-//! hashmap. This allows the code to directly modify the values in the original hashmap rather than creating
-//! a new hashmap with the updated values.
+//! The schemes are pure: they borrow the language records, compute the ages into a new vector and return a
+//! new map of weights. Overwriting the years in place with `values_mut` would destroy the input, so the
+//! same records could not be weighted again with another scheme or reference year.
 
-use std::collections::HashMap;
+use std::error::Error;
+use std::path::PathBuf;
 
-/// Return a hashmap with the name of 15 the programming language and its year of creation.
-fn languages() -> HashMap<String, u32> {
-    let mut languages = HashMap::new();
+use clap::{Parser, Subcommand};
+use hashmap_language::{current_year, language, report, LanguagePicker, Scheme};
 
-    languages.insert("Rust".to_string(), 2010);
-    languages.insert("Python".to_string(), 1991);
-    languages.insert("Java".to_string(), 1995);
-    languages.insert("C".to_string(), 1972);
-    languages.insert("C++".to_string(), 1983);
-    languages.insert("C#".to_string(), 2000);
-    languages.insert("PHP".to_string(), 1995);
-    languages.insert("JavaScript".to_string(), 1995);
-    languages.insert("Go".to_string(), 2009);
-    languages.insert("Swift".to_string(), 2014);
-    languages.insert("Kotlin".to_string(), 2011);
-    languages.insert("R".to_string(), 1993);
-    languages.insert("Ruby".to_string(), 1995);
-    languages.insert("Scala".to_string(), 2003);
-    languages.insert("Dart".to_string(), 2011);
-
-    languages
+#[derive(Parser)]
+#[clap(
+    version = "1.0",
+    about = "Weights programming languages by their year of creation"
+)]
+struct Opts {
+    /// Dataset with Name, Year, Paradigm and Typing columns (.csv or .json)
+    #[clap(short, long, global = true)]
+    data: Option<PathBuf>,
+    /// Weighting scheme: min-max, z-score, log or rank
+    #[clap(short, long, global = true, default_value = "min-max")]
+    scheme: Scheme,
+    /// Year the ages are measured from (defaults to the current year)
+    #[clap(short, long, global = true)]
+    reference_year: Option<u32>,
+    #[clap(subcommand)]
+    command: Option<Command>,
 }
 
-/// Normalize the years in the given languages map and calculate weights.
-fn normalize(languages: &mut HashMap<String, u32>) -> HashMap<String, u32> {
-    for year in languages.values_mut() {
-       *year = 2024 - *year; 
-    }
+#[derive(Subcommand)]
+enum Command {
+    /// Print the weight of every language (default)
+    Weights,
+    /// Pick random languages proportionally to their weights
+    Pick {
+        /// Number of languages to pick
+        #[clap(short, long, default_value_t = 1)]
+        count: usize,
+    },
+    /// Print the languages ranked by weight with a histogram bar per row
+    Report {
+        /// Width of the longest bar in characters
+        #[clap(short, long, default_value_t = 40)]
+        width: usize,
+    },
+}
 
-    let min_year = languages.values().min().unwrap_or(&0);
-    let max_year = languages.values().max().unwrap_or(&0);
+fn main() -> Result<(), Box<dyn Error>> {
+    let opts: Opts = Opts::parse();
 
-    let mut weights = HashMap::new();
+    let languages = match &opts.data {
+        Some(path) => language::load(path)?,
+        None => language::builtin(),
+    };
+    let reference_year = opts.reference_year.unwrap_or_else(current_year);
+    let weights = opts.scheme.weights(&languages, reference_year);
 
-    for (language, year) in languages.iter() {
-        let normalized_year = (year - min_year) as f64 / (max_year - min_year) as f64;
-        let weight = (normalized_year * 99.0) as u32 + 1;
-        weights.insert(language.to_string(), weight);
+    match opts.command.unwrap_or(Command::Weights) {
+        Command::Weights => {
+            // Print the normalized weights.
+            for (name, weight) in weights {
+                println!("{}: {}", name, weight);
+            }
+        }
+        Command::Pick { count } => {
+            let picker = LanguagePicker::new(&weights).ok_or("no languages to pick from")?;
+            let mut rng = rand::thread_rng();
+            for _ in 0..count {
+                println!("{}", picker.pick(&mut rng));
+            }
+        }
+        Command::Report { width } => {
+            print!("{}", report::render(&weights, width));
+        }
     }
 
-    weights
+    Ok(())
 }
-
-fn main() {
-    // Print the normalized weights.
-    let weights = normalize(&mut languages());
-    for (name, weight) in weights {
-        println!("{}: {}", name, weight);
-    }
-}
\ No newline at end of file
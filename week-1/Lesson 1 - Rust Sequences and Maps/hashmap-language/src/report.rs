@@ -0,0 +1,70 @@
+//! Text report of languages ranked by weight.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Languages sorted by descending weight, ties broken by name.
+pub fn ranked(weights: &HashMap<String, u32>) -> Vec<(&str, u32)> {
+    let mut rows: Vec<(&str, u32)> = weights
+        .iter()
+        .map(|(name, &weight)| (name.as_str(), weight))
+        .collect();
+    rows.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    rows
+}
+
+/// Renders one row per language with a histogram bar scaled so the heaviest language spans
+/// `bar_width` characters.
+pub fn render(weights: &HashMap<String, u32>, bar_width: usize) -> String {
+    let rows = ranked(weights);
+    let name_width = rows.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+    let max_weight = rows.first().map(|(_, weight)| *weight).unwrap_or(0);
+
+    let mut report = String::new();
+    for (rank, (name, weight)) in rows.iter().enumerate() {
+        let bar = if max_weight == 0 {
+            0
+        } else {
+            (*weight as usize * bar_width).div_ceil(max_weight as usize)
+        };
+        writeln!(
+            report,
+            "{:>2}. {:<name_width$} {:>3} {}",
+            rank + 1,
+            name,
+            weight,
+            "#".repeat(bar),
+        )
+        .unwrap();
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sorts_by_weight_then_name() {
+        let weights = HashMap::from([
+            ("Go".to_string(), 10),
+            ("C".to_string(), 100),
+            ("Ada".to_string(), 10),
+        ]);
+        assert_eq!(ranked(&weights), vec![("C", 100), ("Ada", 10), ("Go", 10)]);
+    }
+
+    #[test]
+    fn renders_bars_relative_to_heaviest() {
+        let weights = HashMap::from([("C".to_string(), 100), ("Rust".to_string(), 50)]);
+        assert_eq!(
+            render(&weights, 10),
+            " 1. C    100 ##########\n 2. Rust  50 #####\n"
+        );
+    }
+
+    #[test]
+    fn empty_weights_render_nothing() {
+        assert_eq!(render(&HashMap::new(), 10), "");
+    }
+}
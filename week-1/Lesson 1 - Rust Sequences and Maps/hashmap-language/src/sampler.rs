@@ -0,0 +1,155 @@
+//! Weighted random picking with Vose's alias method.
+//!
+//! Building the table is O(n). Every draw afterwards is O(1): pick a column uniformly, then flip a
+//! biased coin to return either the column's own item or its alias.
+
+use std::collections::HashMap;
+
+use rand::Rng;
+
+#[derive(Debug, Clone)]
+pub struct AliasTable<T> {
+    items: Vec<T>,
+    probability: Vec<f64>,
+    alias: Vec<usize>,
+}
+
+impl<T> AliasTable<T> {
+    /// Builds a table from `(item, weight)` pairs. Returns `None` if there are no items or the
+    /// weights do not add up to a positive number.
+    pub fn new(weighted: Vec<(T, f64)>) -> Option<Self> {
+        let total: f64 = weighted.iter().map(|(_, weight)| weight.max(0.0)).sum();
+        if weighted.is_empty() || total <= 0.0 || !total.is_finite() {
+            return None;
+        }
+
+        let n = weighted.len();
+        let (items, weights): (Vec<T>, Vec<f64>) = weighted.into_iter().unzip();
+        let mut scaled: Vec<f64> = weights
+            .iter()
+            .map(|weight| weight.max(0.0) * n as f64 / total)
+            .collect();
+
+        let mut small: Vec<usize> = Vec::new();
+        let mut large: Vec<usize> = Vec::new();
+        for (index, &p) in scaled.iter().enumerate() {
+            if p < 1.0 {
+                small.push(index);
+            } else {
+                large.push(index);
+            }
+        }
+
+        let mut probability = vec![1.0; n];
+        let mut alias: Vec<usize> = (0..n).collect();
+        while let (Some(&less), Some(&more)) = (small.last(), large.last()) {
+            small.pop();
+            probability[less] = scaled[less];
+            alias[less] = more;
+            scaled[more] -= 1.0 - scaled[less];
+            if scaled[more] < 1.0 {
+                large.pop();
+                small.push(more);
+            }
+        }
+        // Whatever is left is 1 up to rounding error and always returns itself.
+        for index in small.into_iter().chain(large) {
+            probability[index] = 1.0;
+        }
+
+        Some(Self {
+            items,
+            probability,
+            alias,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Draws one item with probability proportional to its weight.
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> &T {
+        let column = rng.gen_range(0..self.items.len());
+        if rng.gen::<f64>() < self.probability[column] {
+            &self.items[column]
+        } else {
+            &self.items[self.alias[column]]
+        }
+    }
+}
+
+/// Picks language names proportionally to their weights.
+#[derive(Debug, Clone)]
+pub struct LanguagePicker {
+    table: AliasTable<String>,
+}
+
+impl LanguagePicker {
+    pub fn new(weights: &HashMap<String, u32>) -> Option<Self> {
+        // Sort so the table layout, and therefore seeded draws, do not depend on hash order.
+        let mut weighted: Vec<(String, f64)> = weights
+            .iter()
+            .map(|(name, &weight)| (name.clone(), f64::from(weight)))
+            .collect();
+        weighted.sort_by(|a, b| a.0.cmp(&b.0));
+        AliasTable::new(weighted).map(|table| Self { table })
+    }
+
+    pub fn pick<R: Rng + ?Sized>(&self, rng: &mut R) -> &str {
+        self.table.sample(rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::builtin;
+    use crate::weighting::{MinMax, WeightingScheme};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn rejects_empty_and_zero_weights() {
+        assert!(AliasTable::<u8>::new(vec![]).is_none());
+        assert!(AliasTable::new(vec![('a', 0.0), ('b', 0.0)]).is_none());
+    }
+
+    #[test]
+    fn never_samples_zero_weight_items() {
+        let table = AliasTable::new(vec![('a', 0.0), ('b', 3.0), ('c', 0.0)]).unwrap();
+        let mut rng = StdRng::seed_from_u64(7);
+        assert!((0..1_000).all(|_| *table.sample(&mut rng) == 'b'));
+    }
+
+    #[test]
+    fn sampled_distribution_matches_weights() {
+        let weights = MinMax.weights(&builtin(), 2024);
+        let picker = LanguagePicker::new(&weights).unwrap();
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let draws = 200_000;
+        let mut observed: HashMap<&str, u64> = HashMap::new();
+        for _ in 0..draws {
+            *observed.entry(picker.pick(&mut rng)).or_insert(0) += 1;
+        }
+
+        // Pearson's chi-squared goodness-of-fit test against the weights.
+        let total: f64 = weights.values().map(|&w| f64::from(w)).sum();
+        let chi_squared: f64 = weights
+            .iter()
+            .map(|(name, &weight)| {
+                let expected = draws as f64 * f64::from(weight) / total;
+                let seen = *observed.get(name.as_str()).unwrap_or(&0) as f64;
+                (seen - expected).powi(2) / expected
+            })
+            .sum();
+
+        // Critical value for 14 degrees of freedom at a significance level of 0.001.
+        assert!(chi_squared < 36.123, "chi-squared statistic {chi_squared}");
+    }
+}
@@ -0,0 +1,290 @@
+//! Weighting schemes that turn language ages into weights between 1 and 100.
+//!
+//! A language's age is `reference_year - year`; older languages get higher weights. Every scheme
+//! only has to map the ages onto scores in `[0, 1]`, and [`WeightingScheme::weights`] scales the
+//! scores to `1..=100`. Schemes are pure: they borrow the records and return a new map.
+//!
+//! When all ages are equal (including the single-language case) there is no range to spread the
+//! scores over, so every scheme gives every language a score of 1, i.e. the same weight of 100.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::language::Language;
+
+pub const MIN_WEIGHT: u32 = 1;
+pub const MAX_WEIGHT: u32 = 100;
+
+pub trait WeightingScheme {
+    /// Maps each age onto a score in `[0, 1]`, preserving the order of the input.
+    fn scores(&self, ages: &[f64]) -> Vec<f64>;
+
+    /// Weights between 1 and 100 for every language, keyed by name.
+    fn weights(&self, languages: &[Language], reference_year: u32) -> HashMap<String, u32> {
+        let ages: Vec<f64> = languages
+            .iter()
+            .map(|language| reference_year.saturating_sub(language.year) as f64)
+            .collect();
+        let scores = self.scores(&ages);
+        languages
+            .iter()
+            .zip(scores)
+            .map(|(language, score)| (language.name.clone(), to_weight(score)))
+            .collect()
+    }
+}
+
+fn to_weight(score: f64) -> u32 {
+    let score = score.clamp(0.0, 1.0);
+    (score * (MAX_WEIGHT - MIN_WEIGHT) as f64) as u32 + MIN_WEIGHT
+}
+
+/// Rescales `values` linearly onto `[0, 1]`, or all ones when the range is empty.
+fn rescale(values: &[f64]) -> Vec<f64> {
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let range = max - min;
+    if range.is_nan() || range <= 0.0 {
+        return vec![1.0; values.len()];
+    }
+    values.iter().map(|value| (value - min) / range).collect()
+}
+
+/// Linear min-max scaling of the ages. This is what the lesson's `normalize` computed.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MinMax;
+
+impl WeightingScheme for MinMax {
+    fn scores(&self, ages: &[f64]) -> Vec<f64> {
+        rescale(ages)
+    }
+}
+
+/// Standard scores clamped to `±clamp` standard deviations and mapped linearly onto `[0, 1]`.
+/// Outliers no longer squash everything else into a corner of the range.
+#[derive(Debug, Clone, Copy)]
+pub struct ZScore {
+    pub clamp: f64,
+}
+
+impl Default for ZScore {
+    fn default() -> Self {
+        Self { clamp: 3.0 }
+    }
+}
+
+impl WeightingScheme for ZScore {
+    fn scores(&self, ages: &[f64]) -> Vec<f64> {
+        let n = ages.len() as f64;
+        let mean = ages.iter().sum::<f64>() / n;
+        let variance = ages.iter().map(|age| (age - mean).powi(2)).sum::<f64>() / n;
+        let std_dev = variance.sqrt();
+        if std_dev.is_nan() || std_dev <= 0.0 {
+            return vec![1.0; ages.len()];
+        }
+        ages.iter()
+            .map(|age| {
+                let z = ((age - mean) / std_dev).clamp(-self.clamp, self.clamp);
+                (z + self.clamp) / (2.0 * self.clamp)
+            })
+            .collect()
+    }
+}
+
+/// Min-max scaling of `ln(1 + age)`, which compresses the differences between old languages.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Log;
+
+impl WeightingScheme for Log {
+    fn scores(&self, ages: &[f64]) -> Vec<f64> {
+        let logs: Vec<f64> = ages.iter().map(|age| age.ln_1p()).collect();
+        rescale(&logs)
+    }
+}
+
+/// Scores by rank of age, so the spacing between years does not matter. Ties share their
+/// average rank.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Rank;
+
+impl WeightingScheme for Rank {
+    fn scores(&self, ages: &[f64]) -> Vec<f64> {
+        let mut order: Vec<usize> = (0..ages.len()).collect();
+        order.sort_by(|&a, &b| ages[a].total_cmp(&ages[b]));
+
+        let mut ranks = vec![0.0; ages.len()];
+        let mut start = 0;
+        while start < order.len() {
+            let mut end = start + 1;
+            while end < order.len() && ages[order[end]] == ages[order[start]] {
+                end += 1;
+            }
+            let average = (start + end - 1) as f64 / 2.0;
+            for &index in &order[start..end] {
+                ranks[index] = average;
+            }
+            start = end;
+        }
+        rescale(&ranks)
+    }
+}
+
+/// The schemes selectable by name, e.g. from a command-line flag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scheme {
+    MinMax,
+    ZScore,
+    Log,
+    Rank,
+}
+
+impl Scheme {
+    pub fn weights(&self, languages: &[Language], reference_year: u32) -> HashMap<String, u32> {
+        match self {
+            Scheme::MinMax => MinMax.weights(languages, reference_year),
+            Scheme::ZScore => ZScore::default().weights(languages, reference_year),
+            Scheme::Log => Log.weights(languages, reference_year),
+            Scheme::Rank => Rank.weights(languages, reference_year),
+        }
+    }
+}
+
+impl FromStr for Scheme {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "min-max" => Ok(Scheme::MinMax),
+            "z-score" => Ok(Scheme::ZScore),
+            "log" => Ok(Scheme::Log),
+            "rank" => Ok(Scheme::Rank),
+            _ => Err(format!(
+                "unknown scheme '{}', expected one of: min-max, z-score, log, rank",
+                s
+            )),
+        }
+    }
+}
+
+/// The current calendar year (UTC) according to the system clock.
+pub fn current_year() -> u32 {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0);
+    year_from_days((seconds / 86_400) as i64)
+}
+
+/// Converts days since 1970-01-01 into a proleptic Gregorian year.
+fn year_from_days(days: i64) -> u32 {
+    // Howard Hinnant's `civil_from_days`, keeping only the year.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    year as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::{builtin, Typing};
+
+    fn language(name: &str, year: u32) -> Language {
+        Language::new(name, year, "procedural", Typing::Static)
+    }
+
+    #[test]
+    fn min_max_matches_original_normalize() {
+        let weights = MinMax.weights(&builtin(), 2024);
+        assert_eq!(weights["C"], 100);
+        assert_eq!(weights["Swift"], 1);
+        assert_eq!(weights["Python"], 55);
+    }
+
+    #[test]
+    fn weights_do_not_modify_input() {
+        let languages = builtin();
+        let _ = MinMax.weights(&languages, 2024);
+        assert_eq!(languages, builtin());
+    }
+
+    #[test]
+    fn every_scheme_handles_equal_years() {
+        let languages = vec![language("A", 2000), language("B", 2000)];
+        for scheme in [Scheme::MinMax, Scheme::ZScore, Scheme::Log, Scheme::Rank] {
+            let weights = scheme.weights(&languages, 2024);
+            assert_eq!(weights["A"], MAX_WEIGHT, "{:?}", scheme);
+            assert_eq!(weights["B"], MAX_WEIGHT, "{:?}", scheme);
+        }
+    }
+
+    #[test]
+    fn every_scheme_handles_a_single_language() {
+        let languages = vec![language("A", 2000)];
+        for scheme in [Scheme::MinMax, Scheme::ZScore, Scheme::Log, Scheme::Rank] {
+            assert_eq!(scheme.weights(&languages, 2024)["A"], MAX_WEIGHT);
+        }
+    }
+
+    #[test]
+    fn every_scheme_keeps_weights_in_range_and_older_is_heavier() {
+        let languages = builtin();
+        for scheme in [Scheme::MinMax, Scheme::ZScore, Scheme::Log, Scheme::Rank] {
+            let weights = scheme.weights(&languages, 2024);
+            assert!(weights
+                .values()
+                .all(|w| (MIN_WEIGHT..=MAX_WEIGHT).contains(w)));
+            assert!(weights["C"] > weights["Rust"], "{:?}", scheme);
+            assert!(weights["Rust"] >= weights["Swift"], "{:?}", scheme);
+        }
+    }
+
+    #[test]
+    fn future_years_count_as_age_zero() {
+        let languages = vec![language("Old", 1990), language("New", 2030)];
+        let weights = MinMax.weights(&languages, 2024);
+        assert_eq!(weights["New"], MIN_WEIGHT);
+        assert_eq!(weights["Old"], MAX_WEIGHT);
+    }
+
+    #[test]
+    fn rank_averages_ties() {
+        assert_eq!(Rank.scores(&[5.0, 1.0, 5.0, 3.0]), vec![1.0, 0.0, 1.0, 0.4]);
+    }
+
+    #[test]
+    fn log_compresses_old_ages() {
+        let scores = Log.scores(&[0.0, 10.0, 50.0]);
+        assert!(scores[1] > 10.0 / 50.0);
+    }
+
+    #[test]
+    fn z_score_is_centered() {
+        let scores = ZScore::default().scores(&[1.0, 2.0, 3.0]);
+        assert!((scores[1] - 0.5).abs() < 1e-12);
+        assert!(scores[0] < 0.5 && scores[2] > 0.5);
+    }
+
+    #[test]
+    fn parses_scheme_names() {
+        assert_eq!("z-score".parse(), Ok(Scheme::ZScore));
+        assert!("median".parse::<Scheme>().is_err());
+    }
+
+    #[test]
+    fn converts_days_to_years() {
+        assert_eq!(year_from_days(0), 1970);
+        assert_eq!(year_from_days(364), 1970);
+        assert_eq!(year_from_days(365), 1971);
+        // 2024-02-29 and 2024-12-31
+        assert_eq!(year_from_days(19_782), 2024);
+        assert_eq!(year_from_days(20_088), 2024);
+        assert_eq!(year_from_days(20_089), 2025);
+    }
+}
@@ -0,0 +1,115 @@
+//! Middle-of-list edits on a `Playlist` compared with the same edits on a `Vec`.
+//!
+//! Every benchmark undoes its own edit, so the collection keeps its size across iterations and no
+//! setup has to be repeated. `iterate` is included as the counterweight: walking linked nodes is
+//! slower than walking contiguous memory.
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use linked_list_fruit_salad::Playlist;
+
+const SIZES: [usize; 3] = [1_000, 100_000, 1_000_000];
+/// Items moved into the middle and back in the `splice` benchmarks.
+const SPLICE_LEN: usize = 1_000;
+
+fn inputs(size: usize) -> (Playlist<u64>, Vec<u64>) {
+    let values: Vec<u64> = (0..size as u64).collect();
+    (values.iter().copied().collect(), values)
+}
+
+fn insert_remove_middle(c: &mut Criterion) {
+    let mut group = c.benchmark_group("insert_remove_middle");
+    for size in SIZES {
+        let (mut playlist, mut vec) = inputs(size);
+        let middle = playlist.handles().nth(size / 2).unwrap();
+
+        group.bench_function(BenchmarkId::new("Playlist", size), |b| {
+            b.iter(|| {
+                let inserted = playlist.insert_after(middle, black_box(7)).unwrap();
+                playlist.remove(inserted)
+            })
+        });
+        group.bench_function(BenchmarkId::new("Vec", size), |b| {
+            b.iter(|| {
+                vec.insert(size / 2 + 1, black_box(7));
+                vec.remove(size / 2 + 1)
+            })
+        });
+    }
+    group.finish();
+}
+
+fn move_to_front(c: &mut Criterion) {
+    let mut group = c.benchmark_group("move_to_front");
+    for size in SIZES {
+        let (mut playlist, mut vec) = inputs(size);
+        let handles: Vec<_> = playlist.handles().collect();
+        let (before, middle) = (handles[size / 2 - 1], handles[size / 2]);
+
+        group.bench_function(BenchmarkId::new("Playlist", size), |b| {
+            b.iter(|| {
+                playlist.move_to_front(black_box(middle));
+                playlist.move_after(middle, before)
+            })
+        });
+        group.bench_function(BenchmarkId::new("Vec", size), |b| {
+            b.iter(|| {
+                let item = vec.remove(black_box(size / 2));
+                vec.insert(0, item);
+                let item = vec.remove(0);
+                vec.insert(size / 2, item);
+            })
+        });
+    }
+    group.finish();
+}
+
+fn splice(c: &mut Criterion) {
+    let mut group = c.benchmark_group("splice");
+    for size in SIZES {
+        // The run to move sits at the back: it is spliced into the middle and back out again
+        let (mut playlist, mut vec) = inputs(size + SPLICE_LEN);
+        let handles: Vec<_> = playlist.handles().collect();
+        let (middle, end) = (handles[size / 2], handles[size - 1]);
+        let (first, last) = (handles[size], handles[size + SPLICE_LEN - 1]);
+
+        group.bench_function(BenchmarkId::new("Playlist", size), |b| {
+            b.iter(|| {
+                playlist.splice_after(black_box(middle), first, last);
+                playlist.splice_after(end, first, last)
+            })
+        });
+        group.bench_function(BenchmarkId::new("Vec", size), |b| {
+            b.iter(|| {
+                let at = black_box(size / 2 + 1);
+                vec[at..].rotate_right(SPLICE_LEN);
+                vec[at..].rotate_left(SPLICE_LEN);
+            })
+        });
+    }
+    group.finish();
+}
+
+fn iterate(c: &mut Criterion) {
+    let mut group = c.benchmark_group("iterate");
+    for size in SIZES {
+        let (playlist, vec) = inputs(size);
+        group.bench_function(BenchmarkId::new("Playlist", size), |b| {
+            b.iter(|| playlist.iter().sum::<u64>())
+        });
+        group.bench_function(BenchmarkId::new("Vec", size), |b| {
+            b.iter(|| vec.iter().sum::<u64>())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    insert_remove_middle,
+    move_to_front,
+    splice,
+    iterate
+);
+criterion_main!(benches);
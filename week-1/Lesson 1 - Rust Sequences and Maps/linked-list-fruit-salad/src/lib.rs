@@ -0,0 +1,6 @@
+//! Library side of the linked list fruit salad: a playlist-style list that can be edited in the
+//! middle, used by `main.rs` and the benchmarks.
+
+pub mod playlist;
+
+pub use playlist::{Cursor, Handle, Playlist};
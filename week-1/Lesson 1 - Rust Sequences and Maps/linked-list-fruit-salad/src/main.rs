@@ -93,13 +93,21 @@
 //! access) and `LinkedList` (efficient insertions/removals at any point), demonstrating a
 //! pragmatic approach to achieve a specific functionality (shuffling) that is not directly
 //! supported by the `LinkedList`.
+//!
+//! This program no longer needs the round trip: it samples its fruit from the catalogue already
+//! in random order and builds the salad straight into a `Playlist`, which it then edits in the
+//! middle through a cursor.
+
+// The answers above keep their original list layout.
+#![allow(clippy::doc_lazy_continuation)]
 
 
 /*
-As with the VecDeque example, this code starts by creating a LinkedList of fruits,
-converts it to a Vec for shuffling, and then converts it back to a LinkedList.
-After the shuffling, it adds "Pomegranate", "Fig", and "Cherry" to the end of the list.
-Finally, it prints out the final fruit salad.
+This code samples fruits from the shared catalogue, already shuffled, straight into a
+Playlist, a linked list whose items can be edited in the middle.
+It then adds one more fruit to the front of the list and two to the end, and prints out the
+fruit salad. Finally, it moves a cursor into the salad to insert, remove, splice and move fruits
+there, and prints the edited salad.
 
 This example shows how to use a LinkedList, but remember that LinkedList
 has a higher memory overhead and worse cache locality than Vec or VecDeque,
@@ -112,35 +120,22 @@ A great example of when to use a LinkedList is when you need to insert or remove
 from the middle of the list.
 */
 
-use rand::seq::SliceRandom; // rand is a random number generation library in Rust
-use rand::thread_rng;
-use std::collections::LinkedList;
+use fruit_catalogue::{sample_without_replacement, FRUITS};
+use linked_list_fruit_salad::Playlist;
+use rand::thread_rng; // rand is a random number generation library in Rust
 
 fn main() {
-    let mut fruit: LinkedList<&str> = LinkedList::new();
-    fruit.push_back("Arbutus");
-    fruit.push_back("Loquat");
-    fruit.push_back("Strawberry Tree Berry");
-
-    /*
-    Please note that converting a LinkedList to a Vec and back to a LinkedList
-    isn't a common operation in practice. I included
-    it in this example to keep the code as similar as possible
-    to the original VecDeque example.
-     */
-
-    // Scramble (shuffle) the fruit
+    // Pick nine different fruits from the catalogue, already in random order: three start the
+    // salad, three are added to its ends and the rest are used to edit its middle
     let mut rng = thread_rng();
-    let mut fruit: Vec<_> = fruit.into_iter().collect();
-    fruit.shuffle(&mut rng);
+    let picks = sample_without_replacement(&FRUITS, 9, &mut rng)
+        .expect("the catalogue has at least nine fruits");
+    let mut fruit: Playlist<&str> = picks[..3].iter().copied().collect();
 
-    // Convert it back to LinkedList
-    let mut fruit: LinkedList<_> = fruit.into_iter().collect();
-
-    // Add fruits to the both ends of the list after shuffling
-    fruit.push_front("Pomegranate");
-    fruit.push_back("Fig");
-    fruit.push_back("Cherry");
+    // Add fruits to the both ends of the list
+    fruit.push_front(picks[3]);
+    fruit.push_back(picks[4]);
+    fruit.push_back(picks[5]);
 
     // Print out the fruit salad
     println!("Fruit Salad:");
@@ -151,4 +146,22 @@ fn main() {
             println!("{}", item);
         }
     }
+
+    // Edit the salad in the middle: a Playlist keeps a handle to every fruit, so inserting,
+    // removing and moving next to one only relinks its neighbours. The fruits to splice in are
+    // pushed to the back first, then the cursor moves the whole run after its item at once.
+    let first = fruit.push_back(picks[7]);
+    let last = fruit.push_back(picks[8]);
+    let mut cursor = fruit.cursor_front();
+    cursor.move_next();
+    cursor.move_next();
+    if let Some(current) = cursor.current() {
+        println!("Cursor on: {}", current);
+    }
+    cursor.insert_after(picks[6]);
+    let removed = cursor.remove_current();
+    println!("Removed: {:?}", removed);
+    cursor.splice_after(first, last);
+    cursor.move_current_to_front();
+    println!("Edited Fruit Salad: {:?}", fruit);
 }
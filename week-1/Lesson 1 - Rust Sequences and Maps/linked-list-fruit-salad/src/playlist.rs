@@ -0,0 +1,749 @@
+//! A doubly linked list with stable node handles and a cursor for editing it in place.
+//!
+//! `std::collections::LinkedList` can only insert and remove in the middle through its cursor API,
+//! which is not available on stable Rust. [`Playlist`] keeps its nodes in a `Vec` and links them by
+//! index instead, so every node can be addressed by the [`Handle`] returned when it was inserted.
+//! Given a handle, inserting next to a node, removing it and moving it elsewhere in the list only
+//! rewrite a few links and take O(1) time, where a `Vec` has to shift every element behind it. The
+//! same goes for moving a whole run of nodes: only its two ends are relinked.
+//!
+//! Removed nodes are reused by later insertions. Each reuse bumps the node's generation, so a
+//! handle to a removed item is rejected instead of silently pointing at the new one.
+
+use std::fmt;
+
+/// Refers to an item in a [`Playlist`]. It stays valid while the item is in the list, wherever the
+/// item is moved to, and goes stale once the item is removed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Handle {
+    slot: usize,
+    generation: u64,
+}
+
+#[derive(Debug, Clone)]
+struct Node<T> {
+    value: T,
+    prev: Option<usize>,
+    next: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+struct Slot<T> {
+    generation: u64,
+    node: Option<Node<T>>,
+}
+
+#[derive(Clone)]
+pub struct Playlist<T> {
+    slots: Vec<Slot<T>>,
+    free: Vec<usize>,
+    head: Option<usize>,
+    tail: Option<usize>,
+    len: usize,
+}
+
+impl<T> Default for Playlist<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Playlist<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T> FromIterator<T> for Playlist<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut playlist = Playlist::new();
+        playlist.extend(iter);
+        playlist
+    }
+}
+
+impl<T> Extend<T> for Playlist<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push_back(value);
+        }
+    }
+}
+
+impl<T> Playlist<T> {
+    pub fn new() -> Self {
+        Self {
+            slots: Vec::new(),
+            free: Vec::new(),
+            head: None,
+            tail: None,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn front(&self) -> Option<Handle> {
+        self.head.map(|slot| self.handle(slot))
+    }
+
+    pub fn back(&self) -> Option<Handle> {
+        self.tail.map(|slot| self.handle(slot))
+    }
+
+    /// Whether `handle` still refers to an item in the list.
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.live_slot(handle).is_some()
+    }
+
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        let slot = self.live_slot(handle)?;
+        Some(&self.node(slot).value)
+    }
+
+    pub fn get_mut(&mut self, handle: Handle) -> Option<&mut T> {
+        let slot = self.live_slot(handle)?;
+        Some(&mut self.node_mut(slot).value)
+    }
+
+    /// The item after `handle`, or `None` at the end of the list or for a stale handle.
+    pub fn next(&self, handle: Handle) -> Option<Handle> {
+        let slot = self.live_slot(handle)?;
+        self.node(slot).next.map(|next| self.handle(next))
+    }
+
+    /// The item before `handle`, or `None` at the start of the list or for a stale handle.
+    pub fn prev(&self, handle: Handle) -> Option<Handle> {
+        let slot = self.live_slot(handle)?;
+        self.node(slot).prev.map(|prev| self.handle(prev))
+    }
+
+    pub fn push_front(&mut self, value: T) -> Handle {
+        let slot = self.allocate(value);
+        self.link(slot, None, self.head);
+        self.handle(slot)
+    }
+
+    pub fn push_back(&mut self, value: T) -> Handle {
+        let slot = self.allocate(value);
+        self.link(slot, self.tail, None);
+        self.handle(slot)
+    }
+
+    /// Inserts `value` right after `handle`. Returns `None` (dropping nothing) for stale handles.
+    pub fn insert_after(&mut self, handle: Handle, value: T) -> Option<Handle> {
+        let at = self.live_slot(handle)?;
+        let next = self.node(at).next;
+        let slot = self.allocate(value);
+        self.link(slot, Some(at), next);
+        Some(self.handle(slot))
+    }
+
+    /// Inserts `value` right before `handle`. Returns `None` for stale handles.
+    pub fn insert_before(&mut self, handle: Handle, value: T) -> Option<Handle> {
+        let at = self.live_slot(handle)?;
+        let prev = self.node(at).prev;
+        let slot = self.allocate(value);
+        self.link(slot, prev, Some(at));
+        Some(self.handle(slot))
+    }
+
+    /// Removes the item behind `handle`. Returns `None` for stale handles.
+    pub fn remove(&mut self, handle: Handle) -> Option<T> {
+        let slot = self.live_slot(handle)?;
+        self.unlink(slot);
+        let removed = &mut self.slots[slot];
+        let node = removed.node.take().expect("live slot has a node");
+        removed.generation += 1;
+        self.free.push(slot);
+        Some(node.value)
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.front().and_then(|handle| self.remove(handle))
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.back().and_then(|handle| self.remove(handle))
+    }
+
+    /// Moves the item behind `handle` to the start of the list. Returns `false` for stale handles.
+    pub fn move_to_front(&mut self, handle: Handle) -> bool {
+        let Some(slot) = self.live_slot(handle) else {
+            return false;
+        };
+        if self.head != Some(slot) {
+            self.unlink(slot);
+            self.link(slot, None, self.head);
+        }
+        true
+    }
+
+    /// Moves the item behind `handle` to the end of the list. Returns `false` for stale handles.
+    pub fn move_to_back(&mut self, handle: Handle) -> bool {
+        let Some(slot) = self.live_slot(handle) else {
+            return false;
+        };
+        if self.tail != Some(slot) {
+            self.unlink(slot);
+            self.link(slot, self.tail, None);
+        }
+        true
+    }
+
+    /// Moves the item behind `handle` right after `target`. Returns `false` if either handle is
+    /// stale; moving an item after itself leaves the list unchanged.
+    pub fn move_after(&mut self, handle: Handle, target: Handle) -> bool {
+        let (Some(slot), Some(at)) = (self.live_slot(handle), self.live_slot(target)) else {
+            return false;
+        };
+        if slot != at {
+            self.unlink(slot);
+            let next = self.node(at).next;
+            self.link(slot, Some(at), next);
+        }
+        true
+    }
+
+    /// Moves the run of items from `first` through `last` right after `target`. Only the ends of
+    /// the run are relinked, so this takes O(1) time however long the run is, where moving m items
+    /// within a `Vec` shifts every item in between. To splice new items into the middle, push them
+    /// to the back first and then move them into place.
+    ///
+    /// `last` must be `first` or come after it, and `target` must not be inside the run. Checking
+    /// that means walking the run, so it is only done in debug builds. Returns `false` if any
+    /// handle is stale.
+    pub fn splice_after(&mut self, target: Handle, first: Handle, last: Handle) -> bool {
+        let (Some(at), Some(first), Some(last)) = (
+            self.live_slot(target),
+            self.live_slot(first),
+            self.live_slot(last),
+        ) else {
+            return false;
+        };
+        self.move_run(first, last, Some(at));
+        true
+    }
+
+    /// The items from front to back.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            playlist: self,
+            next: self.head,
+            remaining: self.len,
+        }
+    }
+
+    /// The handles of the items from front to back.
+    pub fn handles(&self) -> impl Iterator<Item = Handle> + '_ {
+        std::iter::successors(self.front(), move |&handle| self.next(handle))
+    }
+
+    /// A cursor on the first item, or on nothing if the list is empty.
+    pub fn cursor_front(&mut self) -> Cursor<'_, T> {
+        let current = self.head;
+        Cursor {
+            playlist: self,
+            current,
+        }
+    }
+
+    /// A cursor on the item behind `handle`, or `None` for stale handles.
+    pub fn cursor_at(&mut self, handle: Handle) -> Option<Cursor<'_, T>> {
+        let current = Some(self.live_slot(handle)?);
+        Some(Cursor {
+            playlist: self,
+            current,
+        })
+    }
+
+    fn handle(&self, slot: usize) -> Handle {
+        Handle {
+            slot,
+            generation: self.slots[slot].generation,
+        }
+    }
+
+    fn live_slot(&self, handle: Handle) -> Option<usize> {
+        let slot = self.slots.get(handle.slot)?;
+        (slot.generation == handle.generation && slot.node.is_some()).then_some(handle.slot)
+    }
+
+    fn node(&self, slot: usize) -> &Node<T> {
+        self.slots[slot]
+            .node
+            .as_ref()
+            .expect("linked slot has a node")
+    }
+
+    fn node_mut(&mut self, slot: usize) -> &mut Node<T> {
+        self.slots[slot]
+            .node
+            .as_mut()
+            .expect("linked slot has a node")
+    }
+
+    /// Stores `value` in a free slot, not yet linked into the list.
+    fn allocate(&mut self, value: T) -> usize {
+        let node = Node {
+            value,
+            prev: None,
+            next: None,
+        };
+        match self.free.pop() {
+            Some(slot) => {
+                self.slots[slot].node = Some(node);
+                slot
+            }
+            None => {
+                self.slots.push(Slot {
+                    generation: 0,
+                    node: Some(node),
+                });
+                self.slots.len() - 1
+            }
+        }
+    }
+
+    /// Links an unlinked `slot` between the neighbours `prev` and `next`.
+    fn link(&mut self, slot: usize, prev: Option<usize>, next: Option<usize>) {
+        let node = self.node_mut(slot);
+        node.prev = prev;
+        node.next = next;
+        match prev {
+            Some(prev) => self.node_mut(prev).next = Some(slot),
+            None => self.head = Some(slot),
+        }
+        match next {
+            Some(next) => self.node_mut(next).prev = Some(slot),
+            None => self.tail = Some(slot),
+        }
+        self.len += 1;
+    }
+
+    /// Takes `slot` out of the chain, joining its neighbours.
+    fn unlink(&mut self, slot: usize) {
+        let node = self.node_mut(slot);
+        let (prev, next) = (node.prev.take(), node.next.take());
+        match prev {
+            Some(prev) => self.node_mut(prev).next = next,
+            None => self.head = next,
+        }
+        match next {
+            Some(next) => self.node_mut(next).prev = prev,
+            None => self.tail = prev,
+        }
+        self.len -= 1;
+    }
+
+    /// Relinks the run from `first` through `last` right after `prev`, or at the front for `None`.
+    /// The items stay in the list, so its length does not change.
+    fn move_run(&mut self, first: usize, last: usize, prev: Option<usize>) {
+        debug_assert!(
+            self.is_run_without(first, last, prev),
+            "the run is out of order or contains the splice target"
+        );
+        let (before, after) = (self.node(first).prev, self.node(last).next);
+        match before {
+            Some(before) => self.node_mut(before).next = after,
+            None => self.head = after,
+        }
+        match after {
+            Some(after) => self.node_mut(after).prev = before,
+            None => self.tail = before,
+        }
+
+        let next = match prev {
+            Some(prev) => self.node(prev).next,
+            None => self.head,
+        };
+        self.node_mut(first).prev = prev;
+        self.node_mut(last).next = next;
+        match prev {
+            Some(prev) => self.node_mut(prev).next = Some(first),
+            None => self.head = Some(first),
+        }
+        match next {
+            Some(next) => self.node_mut(next).prev = Some(last),
+            None => self.tail = Some(last),
+        }
+    }
+
+    /// Whether `last` is reached by walking forward from `first` without passing `outside`.
+    fn is_run_without(&self, first: usize, last: usize, outside: Option<usize>) -> bool {
+        let mut slot = first;
+        loop {
+            if Some(slot) == outside {
+                return false;
+            }
+            if slot == last {
+                return true;
+            }
+            match self.node(slot).next {
+                Some(next) => slot = next,
+                None => return false,
+            }
+        }
+    }
+}
+
+pub struct Iter<'a, T> {
+    playlist: &'a Playlist<T>,
+    next: Option<usize>,
+    remaining: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.playlist.node(self.next?);
+        self.next = node.next;
+        self.remaining -= 1;
+        Some(&node.value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Playlist<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+pub struct IntoIter<T>(Playlist<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.0.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len(), Some(self.0.len()))
+    }
+}
+
+impl<T> IntoIterator for Playlist<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
+/// An editing position in a [`Playlist`], like the cursor of a text editor. It points at one
+/// item, or at nothing once it has moved past either end or the list is empty.
+pub struct Cursor<'a, T> {
+    playlist: &'a mut Playlist<T>,
+    current: Option<usize>,
+}
+
+impl<'a, T> Cursor<'a, T> {
+    pub fn current(&self) -> Option<&T> {
+        self.current.map(|slot| &self.playlist.node(slot).value)
+    }
+
+    pub fn current_mut(&mut self) -> Option<&mut T> {
+        let slot = self.current?;
+        Some(&mut self.playlist.node_mut(slot).value)
+    }
+
+    /// The handle of the current item, to come back to it later.
+    pub fn handle(&self) -> Option<Handle> {
+        self.current.map(|slot| self.playlist.handle(slot))
+    }
+
+    /// Moves to the next item. From past either end this wraps around to the first item.
+    pub fn move_next(&mut self) {
+        self.current = match self.current {
+            Some(slot) => self.playlist.node(slot).next,
+            None => self.playlist.head,
+        };
+    }
+
+    /// Moves to the previous item. From past either end this wraps around to the last item.
+    pub fn move_prev(&mut self) {
+        self.current = match self.current {
+            Some(slot) => self.playlist.node(slot).prev,
+            None => self.playlist.tail,
+        };
+    }
+
+    /// Inserts `value` after the current item, or at the front when the cursor points at nothing.
+    /// The cursor stays where it is.
+    pub fn insert_after(&mut self, value: T) -> Handle {
+        match self.handle() {
+            Some(handle) => self
+                .playlist
+                .insert_after(handle, value)
+                .expect("cursor points at a live item"),
+            None => self.playlist.push_front(value),
+        }
+    }
+
+    /// Inserts `value` before the current item, or at the back when the cursor points at
+    /// nothing. The cursor stays where it is.
+    pub fn insert_before(&mut self, value: T) -> Handle {
+        match self.handle() {
+            Some(handle) => self
+                .playlist
+                .insert_before(handle, value)
+                .expect("cursor points at a live item"),
+            None => self.playlist.push_back(value),
+        }
+    }
+
+    /// Removes the current item and moves the cursor to the item after it.
+    pub fn remove_current(&mut self) -> Option<T> {
+        let handle = self.handle()?;
+        self.current = self.playlist.node(handle.slot).next;
+        self.playlist.remove(handle)
+    }
+
+    /// Moves the current item to the front of the list. The cursor follows the item.
+    pub fn move_current_to_front(&mut self) -> bool {
+        self.handle()
+            .is_some_and(|handle| self.playlist.move_to_front(handle))
+    }
+
+    /// Moves the run of items from `first` through `last` after the current item, or to the front
+    /// when the cursor points at nothing, in O(1) time. The cursor stays where it is and must not
+    /// be inside the run, see [`Playlist::splice_after`]. Returns `false` if either handle is
+    /// stale.
+    pub fn splice_after(&mut self, first: Handle, last: Handle) -> bool {
+        let (Some(first), Some(last)) = (
+            self.playlist.live_slot(first),
+            self.playlist.live_slot(last),
+        ) else {
+            return false;
+        };
+        self.playlist.move_run(first, last, self.current);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn items<T: Clone>(playlist: &Playlist<T>) -> Vec<T> {
+        playlist.iter().cloned().collect()
+    }
+
+    #[test]
+    fn push_and_iterate_both_ends() {
+        let mut playlist = Playlist::new();
+        playlist.push_back("Loquat");
+        playlist.push_front("Arbutus");
+        playlist.push_back("Fig");
+        assert_eq!(items(&playlist), vec!["Arbutus", "Loquat", "Fig"]);
+        assert_eq!(playlist.len(), 3);
+        let backwards: Vec<&str> =
+            std::iter::successors(playlist.back(), |&handle| playlist.prev(handle))
+                .map(|handle| *playlist.get(handle).unwrap())
+                .collect();
+        assert_eq!(backwards, vec!["Fig", "Loquat", "Arbutus"]);
+    }
+
+    #[test]
+    fn insert_and_remove_in_the_middle() {
+        let mut playlist: Playlist<i32> = [1, 2, 4].into_iter().collect();
+        let two = playlist.handles().nth(1).unwrap();
+        let three = playlist.insert_after(two, 3).unwrap();
+        playlist.insert_before(two, 0);
+        assert_eq!(items(&playlist), vec![1, 0, 2, 3, 4]);
+
+        assert_eq!(playlist.remove(two), Some(2));
+        assert_eq!(playlist.get(three), Some(&3));
+        assert_eq!(items(&playlist), vec![1, 0, 3, 4]);
+        assert_eq!(playlist.pop_front(), Some(1));
+        assert_eq!(playlist.pop_back(), Some(4));
+        assert_eq!(items(&playlist), vec![0, 3]);
+    }
+
+    #[test]
+    fn stale_handles_are_rejected() {
+        let mut playlist = Playlist::new();
+        let old = playlist.push_back("old");
+        assert_eq!(playlist.remove(old), Some("old"));
+
+        // The new item reuses the slot of the old one.
+        let new = playlist.push_back("new");
+        assert!(!playlist.contains(old));
+        assert_eq!(playlist.get(old), None);
+        assert_eq!(playlist.insert_after(old, "x"), None);
+        assert!(!playlist.move_to_front(old));
+        assert!(playlist.cursor_at(old).is_none());
+        assert_eq!(playlist.get(new), Some(&"new"));
+        assert_eq!(playlist.len(), 1);
+    }
+
+    #[test]
+    fn moves_keep_handles_valid() {
+        let mut playlist: Playlist<char> = "abcde".chars().collect();
+        let handles: Vec<Handle> = playlist.handles().collect();
+
+        assert!(playlist.move_to_front(handles[3]));
+        assert_eq!(items(&playlist), "dabce".chars().collect::<Vec<_>>());
+        assert!(playlist.move_to_back(handles[0]));
+        assert_eq!(items(&playlist), "dbcea".chars().collect::<Vec<_>>());
+        assert!(playlist.move_after(handles[1], handles[4]));
+        assert_eq!(items(&playlist), "dceba".chars().collect::<Vec<_>>());
+        assert!(playlist.move_after(handles[2], handles[2]));
+        assert_eq!(items(&playlist), "dceba".chars().collect::<Vec<_>>());
+
+        assert_eq!(playlist.front(), Some(handles[3]));
+        assert_eq!(playlist.back(), Some(handles[0]));
+        assert_eq!(playlist.next(handles[4]), Some(handles[1]));
+        assert_eq!(playlist.prev(handles[4]), Some(handles[2]));
+    }
+
+    #[test]
+    fn splice_moves_a_run_of_items() {
+        let mut playlist: Playlist<i32> = [1, 2, 6, 3, 4, 5].into_iter().collect();
+        let handles: Vec<Handle> = playlist.handles().collect();
+        assert!(playlist.splice_after(handles[1], handles[3], handles[5]));
+        assert_eq!(items(&playlist), vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(playlist.back(), Some(handles[2]));
+        assert_eq!(playlist.prev(handles[2]), Some(handles[5]));
+
+        // A run can move back to where it already is, or be a single item at either end
+        assert!(playlist.splice_after(handles[1], handles[3], handles[5]));
+        assert!(playlist.splice_after(handles[2], handles[0], handles[0]));
+        assert_eq!(items(&playlist), vec![2, 3, 4, 5, 6, 1]);
+        assert_eq!(playlist.front(), Some(handles[1]));
+        assert_eq!(playlist.len(), 6);
+
+        playlist.extend([7]);
+        let seven = playlist.back().unwrap();
+        assert!(playlist.remove(handles[0]).is_some());
+        assert!(!playlist.splice_after(seven, handles[0], handles[0]));
+        assert_eq!(items(&playlist), vec![2, 3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "splice target")]
+    fn splice_rejects_a_target_inside_the_run() {
+        let mut playlist: Playlist<i32> = (1..=4).collect();
+        let handles: Vec<Handle> = playlist.handles().collect();
+        playlist.splice_after(handles[2], handles[1], handles[3]);
+    }
+
+    #[test]
+    fn cursor_edits_around_the_current_item() {
+        let mut playlist: Playlist<&str> = ["Arbutus", "Loquat", "Fig"].into_iter().collect();
+        let mut cursor = playlist.cursor_front();
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&"Loquat"));
+
+        cursor.insert_after("Cherry");
+        cursor.insert_before("Pomegranate");
+        assert_eq!(cursor.remove_current(), Some("Loquat"));
+        assert_eq!(cursor.current(), Some(&"Cherry"));
+        assert!(cursor.move_current_to_front());
+        let kiwi = cursor.playlist.push_back("Kiwi");
+        let date = cursor.playlist.push_back("Date");
+        assert!(cursor.splice_after(kiwi, date));
+        assert_eq!(
+            items(&playlist),
+            vec!["Cherry", "Kiwi", "Date", "Arbutus", "Pomegranate", "Fig"]
+        );
+    }
+
+    #[test]
+    fn cursor_wraps_around_the_ends() {
+        let mut playlist: Playlist<i32> = [1, 2].into_iter().collect();
+        let mut cursor = playlist.cursor_front();
+        cursor.move_prev();
+        assert_eq!(cursor.current(), None);
+        cursor.move_prev();
+        assert_eq!(cursor.current(), Some(&2));
+        cursor.move_next();
+        assert_eq!(cursor.current(), None);
+        let first = cursor.insert_before(-1);
+        let last = cursor.insert_before(0);
+        assert!(cursor.splice_after(first, last));
+        cursor.insert_before(3);
+        assert_eq!(items(&playlist), vec![-1, 0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn removing_the_last_item_moves_the_cursor_past_the_end() {
+        let mut playlist: Playlist<i32> = [1].into_iter().collect();
+        let mut cursor = playlist.cursor_front();
+        assert_eq!(cursor.remove_current(), Some(1));
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.remove_current(), None);
+        assert!(playlist.is_empty());
+        assert_eq!(playlist.front(), None);
+    }
+
+    #[test]
+    fn matches_a_vec_under_random_edits() {
+        let mut rng = StdRng::seed_from_u64(99);
+
+        let mut playlist = Playlist::new();
+        let mut reference: Vec<(Handle, usize)> = Vec::new();
+        for value in 0..3_000 {
+            if reference.is_empty() {
+                reference.push((playlist.push_back(value), value));
+                continue;
+            }
+            let index = rng.gen_range(0..reference.len());
+            let handle = reference[index].0;
+            match rng.gen_range(0..5) {
+                0 => {
+                    let new = playlist.insert_after(handle, value).unwrap();
+                    reference.insert(index + 1, (new, value));
+                }
+                1 => {
+                    assert_eq!(playlist.remove(handle), Some(reference.remove(index).1));
+                }
+                2 => {
+                    playlist.move_to_front(handle);
+                    let item = reference.remove(index);
+                    reference.insert(0, item);
+                }
+                3 => {
+                    let new = playlist.insert_before(handle, value).unwrap();
+                    reference.insert(index, (new, value));
+                }
+                _ => {
+                    let end = rng.gen_range(index..reference.len());
+                    if end - index + 1 == reference.len() {
+                        continue;
+                    }
+                    let (first, last) = (handle, reference[end].0);
+                    let run: Vec<_> = reference.drain(index..=end).collect();
+                    let target = rng.gen_range(0..reference.len());
+                    assert!(playlist.splice_after(reference[target].0, first, last));
+                    reference.splice(target + 1..target + 1, run);
+                }
+            }
+        }
+
+        let expected: Vec<usize> = reference.iter().map(|(_, value)| *value).collect();
+        assert_eq!(items(&playlist), expected);
+        assert_eq!(playlist.len(), expected.len());
+        let handles: Vec<Handle> = reference.iter().map(|(handle, _)| *handle).collect();
+        assert_eq!(playlist.handles().collect::<Vec<_>>(), handles);
+    }
+}
@@ -1,101 +1,358 @@
-use std::collections::{LinkedList, VecDeque};
+//! Collection benchmarks across sizes and workloads.
+//!
+//! Every workload is one criterion group, and every collection is benchmarked at each size with a
+//! `BenchmarkId`, so the HTML report (`target/criterion/report/index.html`) plots the collections
+//! against each other per workload. Benchmarks that change a collection get a fresh copy of the
+//! input on every iteration through `iter_batched`, so results do not drift as the collection grows.
+//!
+//! Sizes run from 10 to 10M elements. Set `COLLECTIONS_BENCH_MAX_SIZE` to stop earlier, e.g.
+//! `COLLECTIONS_BENCH_MAX_SIZE=100000 cargo bench`.
 
-use criterion::{criterion_group, criterion_main, Criterion};
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque};
+use std::hint::black_box;
+use std::time::Duration;
 
-fn vector_insertion_bencmarks(c: &mut Criterion) {
-    let mut group = c.benchmark_group("vector_insertion_benchmarks");
-    let mut v = Vec::new();
-    group.bench_function("vector_insertion", |b| {
-        b.iter(|| {
-            for i in 0..1000 {
-                v.push(i);
-            }
+use criterion::measurement::WallTime;
+use criterion::{
+    criterion_group, criterion_main, BatchSize, BenchmarkGroup, BenchmarkId, Criterion, Throughput,
+};
+
+const SIZES: [usize; 7] = [10, 100, 1_000, 10_000, 100_000, 1_000_000, 10_000_000];
+
+/// Number of lookups and removals timed per iteration.
+const OPERATIONS: usize = 1_000;
+
+/// Walking a linked list to an index is O(n) per operation, so stop there before it takes minutes.
+const LINKED_LIST_WALK_MAX_SIZE: usize = 100_000;
+
+fn sizes() -> Vec<usize> {
+    let max = std::env::var("COLLECTIONS_BENCH_MAX_SIZE")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(usize::MAX);
+    SIZES.into_iter().filter(|&size| size <= max).collect()
+}
+
+/// Deterministic pseudo-random numbers below `bound`.
+fn random_indices(count: usize, bound: usize, seed: u64) -> Vec<usize> {
+    let mut state = seed;
+    (0..count)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            ((state >> 33) as usize) % bound
         })
+        .collect()
+}
+
+/// `0..size` in a shuffled order, used as keys for maps and sets.
+fn shuffled_keys(size: usize) -> Vec<u64> {
+    let mut keys: Vec<u64> = (0..size as u64).collect();
+    let swaps = random_indices(size, size, 0x5eed);
+    for (i, j) in swaps.into_iter().enumerate().rev() {
+        keys.swap(i, j % (i + 1));
+    }
+    keys
+}
+
+fn batch_size(size: usize) -> BatchSize {
+    if size >= 100_000 {
+        BatchSize::LargeInput
+    } else {
+        BatchSize::SmallInput
+    }
+}
+
+fn configure_for(group: &mut BenchmarkGroup<WallTime>, size: usize) {
+    group.sample_size(if size >= 1_000_000 { 10 } else { 50 });
+}
+
+/// Benchmarks `routine` on a fresh clone of `input` every iteration.
+fn bench_mutation<C: Clone, O>(
+    group: &mut BenchmarkGroup<WallTime>,
+    name: &str,
+    size: usize,
+    input: &C,
+    mut routine: impl FnMut(&mut C) -> O,
+) {
+    group.bench_with_input(BenchmarkId::new(name, size), input, |b, input| {
+        b.iter_batched_ref(
+            || input.clone(),
+            |collection| black_box(routine(collection)),
+            batch_size(size),
+        )
     });
-    group.finish();
 }
 
-fn vector_lookup_benchmarks(c: &mut Criterion) {
-    let mut group = c.benchmark_group("vector_lookup_benchmarks");
-    let mut v = Vec::new();
-    group.bench_function("vector_lookup", |b| {
+/// Benchmarks building a `C` by pushing or inserting `keys` one at a time.
+fn bench_push<C: Default>(
+    group: &mut BenchmarkGroup<WallTime>,
+    name: &str,
+    keys: &[u64],
+    mut push: impl FnMut(&mut C, u64),
+) {
+    group.bench_with_input(BenchmarkId::new(name, keys.len()), keys, |b, keys| {
         b.iter(|| {
-            for i in 0..1000 {
-                v.push(i);
-            }
-            for i in 0..1000 {
-                let _v = v[i];
+            let mut collection = C::default();
+            for &key in keys {
+                push(&mut collection, key);
             }
+            collection
         })
     });
+}
+
+/// Building each collection from `size` shuffled keys with the same per-element loop.
+fn push_benchmarks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("push");
+    for size in sizes() {
+        configure_for(&mut group, size);
+        group.throughput(Throughput::Elements(size as u64));
+        let keys = shuffled_keys(size);
+
+        bench_push(&mut group, "Vec", &keys, Vec::push);
+        bench_push(&mut group, "VecDeque", &keys, VecDeque::push_back);
+        bench_push(&mut group, "LinkedList", &keys, LinkedList::push_back);
+        bench_push(
+            &mut group,
+            "HashMap",
+            &keys,
+            |map: &mut HashMap<_, _>, k| {
+                map.insert(k, k);
+            },
+        );
+        bench_push(
+            &mut group,
+            "BTreeMap",
+            &keys,
+            |map: &mut BTreeMap<_, _>, k| {
+                map.insert(k, k);
+            },
+        );
+        bench_push(&mut group, "HashSet", &keys, |set: &mut HashSet<_>, k| {
+            set.insert(k);
+        });
+        bench_push(&mut group, "BTreeSet", &keys, |set: &mut BTreeSet<_>, k| {
+            set.insert(k);
+        });
+        bench_push(&mut group, "BinaryHeap", &keys, BinaryHeap::push);
+    }
     group.finish();
 }
 
-fn vecdeque_insertion_bencmarks(c: &mut Criterion) {
-    let mut group = c.benchmark_group("vecdeque_insertion_benchmarks");
-    let mut v = VecDeque::new();
-    group.bench_function("vecdeque_insertion", |b| {
-        b.iter(|| {
-            for i in 0..1000 {
-                v.push_back(i);
-            }
-        })
-    });
+/// Inserting one element at the front of a collection that already holds `size` elements.
+fn front_insert_benchmarks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("front_insert");
+    for size in sizes() {
+        configure_for(&mut group, size);
+        group.throughput(Throughput::Elements(1));
+
+        let vec: Vec<u64> = (0..size as u64).collect();
+        let deque: VecDeque<u64> = vec.iter().copied().collect();
+        let list: LinkedList<u64> = vec.iter().copied().collect();
+
+        bench_mutation(&mut group, "Vec", size, &vec, |v| v.insert(0, u64::MAX));
+        bench_mutation(&mut group, "VecDeque", size, &deque, |d| {
+            d.push_front(u64::MAX)
+        });
+        bench_mutation(&mut group, "LinkedList", size, &list, |l| {
+            l.push_front(u64::MAX)
+        });
+    }
     group.finish();
 }
 
-fn vecdeque_lookup_benchmarks(c: &mut Criterion) {
-    let mut group = c.benchmark_group("vecdeque_lookup_benchmarks");
-    let mut v = VecDeque::new();
-    group.bench_function("vecdeque_lookup", |b| {
-        b.iter(|| {
-            for i in 0..1000 {
-                v.push_back(i);
-            }
-            for i in 0..1000 {
-                let _ = v[i];
-            }
-        })
-    });
+/// Inserting one element in the middle of a collection that already holds `size` elements.
+fn middle_insert_benchmarks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("middle_insert");
+    for size in sizes() {
+        configure_for(&mut group, size);
+        group.throughput(Throughput::Elements(1));
+
+        let vec: Vec<u64> = (0..size as u64).collect();
+        let deque: VecDeque<u64> = vec.iter().copied().collect();
+        let list: LinkedList<u64> = vec.iter().copied().collect();
+        let middle = size / 2;
+
+        bench_mutation(&mut group, "Vec", size, &vec, |v| {
+            v.insert(middle, u64::MAX)
+        });
+        bench_mutation(&mut group, "VecDeque", size, &deque, |d| {
+            d.insert(middle, u64::MAX)
+        });
+        // Without a stable cursor API the list has to be walked to the middle and split.
+        bench_mutation(&mut group, "LinkedList", size, &list, |l| {
+            let mut tail = l.split_off(middle);
+            l.push_back(u64::MAX);
+            l.append(&mut tail);
+        });
+    }
     group.finish();
 }
 
-fn linked_list_insertion_benchmarks(c: &mut Criterion) {
-    let mut group = c.benchmark_group("linked_list_insertion_benchmarks");
-    let mut v = LinkedList::new();
-    group.bench_function("linked_list_insertion", |b| {
-        b.iter(|| {
-            for i in 0..1000 {
-                v.push_back(i);
-            }
-        })
-    });
+/// `OPERATIONS` lookups of random positions (sequences) or random keys (maps and sets).
+fn random_lookup_benchmarks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("random_lookup");
+    group.throughput(Throughput::Elements(OPERATIONS as u64));
+    for size in sizes() {
+        configure_for(&mut group, size);
+        let indices = random_indices(OPERATIONS, size, size as u64);
+        let keys = shuffled_keys(size);
+
+        let vec: Vec<u64> = (0..size as u64).collect();
+        group.bench_with_input(BenchmarkId::new("Vec", size), &vec, |b, vec| {
+            b.iter(|| indices.iter().map(|&i| vec[i]).sum::<u64>())
+        });
+        let deque: VecDeque<u64> = (0..size as u64).collect();
+        group.bench_with_input(BenchmarkId::new("VecDeque", size), &deque, |b, deque| {
+            b.iter(|| indices.iter().map(|&i| deque[i]).sum::<u64>())
+        });
+        if size <= LINKED_LIST_WALK_MAX_SIZE {
+            let list: LinkedList<u64> = (0..size as u64).collect();
+            group.bench_with_input(BenchmarkId::new("LinkedList", size), &list, |b, list| {
+                b.iter(|| {
+                    indices
+                        .iter()
+                        .map(|&i| *list.iter().nth(i).unwrap())
+                        .sum::<u64>()
+                })
+            });
+        }
+
+        let lookups: Vec<u64> = indices.iter().map(|&i| i as u64).collect();
+        let hash_map: HashMap<u64, u64> = keys.iter().map(|&k| (k, k)).collect();
+        group.bench_with_input(BenchmarkId::new("HashMap", size), &hash_map, |b, map| {
+            b.iter(|| lookups.iter().map(|k| map[k]).sum::<u64>())
+        });
+        let btree_map: BTreeMap<u64, u64> = keys.iter().map(|&k| (k, k)).collect();
+        group.bench_with_input(BenchmarkId::new("BTreeMap", size), &btree_map, |b, map| {
+            b.iter(|| lookups.iter().map(|k| map[k]).sum::<u64>())
+        });
+        let hash_set: HashSet<u64> = keys.iter().copied().collect();
+        group.bench_with_input(BenchmarkId::new("HashSet", size), &hash_set, |b, set| {
+            b.iter(|| lookups.iter().filter(|k| set.contains(k)).count())
+        });
+        let btree_set: BTreeSet<u64> = keys.iter().copied().collect();
+        group.bench_with_input(BenchmarkId::new("BTreeSet", size), &btree_set, |b, set| {
+            b.iter(|| lookups.iter().filter(|k| set.contains(k)).count())
+        });
+    }
     group.finish();
 }
 
-fn linked_list_lookup_benchmarks(c: &mut Criterion) {
-    let mut group = c.benchmark_group("linked_list_lookup_benchmarks");
-    let mut v = LinkedList::new();
-    group.bench_function("linked_list_lookup", |b| {
-        b.iter(|| {
-            for i in 0..1000 {
-                v.push_back(i);
-            }
-            for i in 0..1000 {
-                v.iter().any(|&x| x == i);
-            }
-        })
-    });
+/// Visiting every element once.
+fn iteration_benchmarks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("iteration");
+    for size in sizes() {
+        configure_for(&mut group, size);
+        group.throughput(Throughput::Elements(size as u64));
+        let keys = shuffled_keys(size);
+
+        let vec: Vec<u64> = (0..size as u64).collect();
+        group.bench_with_input(BenchmarkId::new("Vec", size), &vec, |b, c| {
+            b.iter(|| c.iter().sum::<u64>())
+        });
+        let deque: VecDeque<u64> = (0..size as u64).collect();
+        group.bench_with_input(BenchmarkId::new("VecDeque", size), &deque, |b, c| {
+            b.iter(|| c.iter().sum::<u64>())
+        });
+        let list: LinkedList<u64> = (0..size as u64).collect();
+        group.bench_with_input(BenchmarkId::new("LinkedList", size), &list, |b, c| {
+            b.iter(|| c.iter().sum::<u64>())
+        });
+        let hash_map: HashMap<u64, u64> = keys.iter().map(|&k| (k, k)).collect();
+        group.bench_with_input(BenchmarkId::new("HashMap", size), &hash_map, |b, c| {
+            b.iter(|| c.values().sum::<u64>())
+        });
+        let btree_map: BTreeMap<u64, u64> = keys.iter().map(|&k| (k, k)).collect();
+        group.bench_with_input(BenchmarkId::new("BTreeMap", size), &btree_map, |b, c| {
+            b.iter(|| c.values().sum::<u64>())
+        });
+        let hash_set: HashSet<u64> = keys.iter().copied().collect();
+        group.bench_with_input(BenchmarkId::new("HashSet", size), &hash_set, |b, c| {
+            b.iter(|| c.iter().sum::<u64>())
+        });
+        let btree_set: BTreeSet<u64> = keys.iter().copied().collect();
+        group.bench_with_input(BenchmarkId::new("BTreeSet", size), &btree_set, |b, c| {
+            b.iter(|| c.iter().sum::<u64>())
+        });
+        let heap: BinaryHeap<u64> = keys.iter().copied().collect();
+        group.bench_with_input(BenchmarkId::new("BinaryHeap", size), &heap, |b, c| {
+            b.iter(|| c.iter().sum::<u64>())
+        });
+    }
     group.finish();
 }
 
+/// Removing up to `OPERATIONS` elements: from the middle of sequences, random keys from maps and
+/// sets, and the maximum from the heap.
+fn removal_benchmarks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("removal");
+    for size in sizes() {
+        configure_for(&mut group, size);
+        let operations = OPERATIONS.min(size);
+        group.throughput(Throughput::Elements(operations as u64));
+        let keys = shuffled_keys(size);
+        let victims: Vec<u64> = keys[..operations].to_vec();
+
+        let vec: Vec<u64> = (0..size as u64).collect();
+        bench_mutation(&mut group, "Vec", size, &vec, |v| {
+            (0..operations).map(|_| v.remove(v.len() / 2)).sum::<u64>()
+        });
+        let deque: VecDeque<u64> = (0..size as u64).collect();
+        bench_mutation(&mut group, "VecDeque", size, &deque, |d| {
+            (0..operations)
+                .filter_map(|_| d.remove(d.len() / 2))
+                .sum::<u64>()
+        });
+        if size <= LINKED_LIST_WALK_MAX_SIZE {
+            let list: LinkedList<u64> = (0..size as u64).collect();
+            bench_mutation(&mut group, "LinkedList", size, &list, |l| {
+                let mut tail = l.split_off(l.len() / 2);
+                let removed: u64 = (0..operations).filter_map(|_| tail.pop_front()).sum();
+                l.append(&mut tail);
+                removed
+            });
+        }
+        let hash_map: HashMap<u64, u64> = keys.iter().map(|&k| (k, k)).collect();
+        bench_mutation(&mut group, "HashMap", size, &hash_map, |m| {
+            victims.iter().filter_map(|k| m.remove(k)).sum::<u64>()
+        });
+        let btree_map: BTreeMap<u64, u64> = keys.iter().map(|&k| (k, k)).collect();
+        bench_mutation(&mut group, "BTreeMap", size, &btree_map, |m| {
+            victims.iter().filter_map(|k| m.remove(k)).sum::<u64>()
+        });
+        let hash_set: HashSet<u64> = keys.iter().copied().collect();
+        bench_mutation(&mut group, "HashSet", size, &hash_set, |s| {
+            victims.iter().filter(|k| s.remove(k)).count()
+        });
+        let btree_set: BTreeSet<u64> = keys.iter().copied().collect();
+        bench_mutation(&mut group, "BTreeSet", size, &btree_set, |s| {
+            victims.iter().filter(|k| s.remove(k)).count()
+        });
+        let heap: BinaryHeap<u64> = keys.iter().copied().collect();
+        bench_mutation(&mut group, "BinaryHeap", size, &heap, |h| {
+            (0..operations).filter_map(|_| h.pop()).sum::<u64>()
+        });
+    }
+    group.finish();
+}
+
+fn config() -> Criterion {
+    Criterion::default()
+        .warm_up_time(Duration::from_secs(1))
+        .measurement_time(Duration::from_secs(3))
+}
+
 criterion_group!(
-    benches,
-    vector_insertion_bencmarks,
-    vector_lookup_benchmarks,
-    vecdeque_insertion_bencmarks,
-    vecdeque_lookup_benchmarks,
-    linked_list_insertion_benchmarks,
-    linked_list_lookup_benchmarks
+    name = benches;
+    config = config();
+    targets = push_benchmarks,
+    front_insert_benchmarks,
+    middle_insert_benchmarks,
+    random_lookup_benchmarks,
+    iteration_benchmarks,
+    removal_benchmarks
 );
 criterion_main!(benches);
@@ -0,0 +1,1064 @@
+//! Recommends a std collection for a workload by measuring the candidates on it.
+//!
+//! A [`Workload`] describes how many elements are held, how many operations run, the mix of
+//! operations, whether iteration has to happen in key order and the key type. [`advise`] generates
+//! a deterministic sequence of operations shaped like that workload, replays it against every
+//! [`Candidate`] collection and ranks them by the measured time per operation.
+//!
+//! Unordered collections are not excluded when `ordered` is set: they pay for sorting on every
+//! ordered iteration instead, which is exactly the trade-off the recommendation should show.
+
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque};
+use std::fmt;
+use std::hash::Hash;
+use std::hint::black_box;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+
+/// Relative weights of each operation in a workload. Weights do not have to add up to 100.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OperationMix {
+    /// Add a new element at the back (or anywhere, for maps, sets and heaps).
+    pub insert: u32,
+    /// Add a new element at the front.
+    pub insert_front: u32,
+    /// Add a new element in the middle.
+    pub insert_middle: u32,
+    /// Check whether an existing key is present.
+    pub lookup: u32,
+    /// Remove an existing key.
+    pub remove: u32,
+    /// Remove the smallest key, as a priority queue would.
+    pub pop_min: u32,
+    /// Visit every element.
+    pub iterate: u32,
+}
+
+impl OperationMix {
+    fn weights(&self) -> [(OperationKind, u32); 7] {
+        [
+            (OperationKind::Insert, self.insert),
+            (OperationKind::InsertFront, self.insert_front),
+            (OperationKind::InsertMiddle, self.insert_middle),
+            (OperationKind::Lookup, self.lookup),
+            (OperationKind::Remove, self.remove),
+            (OperationKind::PopMin, self.pop_min),
+            (OperationKind::Iterate, self.iterate),
+        ]
+    }
+
+    pub fn total(&self) -> u32 {
+        self.weights().iter().map(|(_, weight)| weight).sum()
+    }
+}
+
+impl FromStr for OperationMix {
+    type Err = String;
+
+    /// Parses `insert=40,lookup=50,remove=10`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut mix = OperationMix::default();
+        for part in s.split(',').map(str::trim).filter(|part| !part.is_empty()) {
+            let (name, weight) = part
+                .split_once('=')
+                .ok_or_else(|| format!("expected operation=weight, got '{}'", part))?;
+            let weight: u32 = weight
+                .trim()
+                .parse()
+                .map_err(|_| format!("invalid weight in '{}'", part))?;
+            let slot = match name.trim().replace('-', "_").as_str() {
+                "insert" => &mut mix.insert,
+                "insert_front" => &mut mix.insert_front,
+                "insert_middle" => &mut mix.insert_middle,
+                "lookup" => &mut mix.lookup,
+                "remove" => &mut mix.remove,
+                "pop_min" => &mut mix.pop_min,
+                "iterate" => &mut mix.iterate,
+                other => return Err(format!("unknown operation '{}'", other)),
+            };
+            *slot = weight;
+        }
+        Ok(mix)
+    }
+}
+
+impl fmt::Display for OperationMix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let total = self.total().max(1) as f64;
+        let parts: Vec<String> = self
+            .weights()
+            .iter()
+            .filter(|(_, weight)| *weight > 0)
+            .map(|(kind, weight)| format!("{} {:.0}%", kind, *weight as f64 * 100.0 / total))
+            .collect();
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyType {
+    Integer,
+    String,
+}
+
+impl FromStr for KeyType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "integer" => Ok(KeyType::Integer),
+            "string" => Ok(KeyType::String),
+            _ => Err(format!(
+                "unknown key type '{}', expected integer or string",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for KeyType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyType::Integer => write!(f, "integer"),
+            KeyType::String => write!(f, "string"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Workload {
+    /// Number of elements in the collection before the operations start.
+    pub size: usize,
+    /// Number of operations to replay.
+    pub operations: usize,
+    pub mix: OperationMix,
+    /// Whether `iterate` has to visit elements in key order.
+    pub ordered: bool,
+    pub key: KeyType,
+}
+
+impl Default for Workload {
+    fn default() -> Self {
+        Self {
+            size: 10_000,
+            operations: 10_000,
+            mix: OperationMix {
+                insert: 50,
+                lookup: 50,
+                ..OperationMix::default()
+            },
+            ordered: false,
+            key: KeyType::Integer,
+        }
+    }
+}
+
+impl Workload {
+    /// Reads a workload from TOML. Missing fields keep their default values.
+    pub fn from_toml(text: &str) -> Result<Self, String> {
+        toml::from_str(text).map_err(|err| err.to_string())
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.mix.total() == 0 {
+            return Err("the operation mix needs at least one non-zero weight".to_string());
+        }
+        let needs_elements = self.mix.lookup + self.mix.remove + self.mix.pop_min > 0;
+        let adds_elements = self.mix.insert + self.mix.insert_front + self.mix.insert_middle > 0;
+        if self.size == 0 && needs_elements && !adds_elements {
+            return Err("lookups and removals need a non-zero size or some inserts".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OperationKind {
+    Insert,
+    InsertFront,
+    InsertMiddle,
+    Lookup,
+    Remove,
+    PopMin,
+    Iterate,
+}
+
+impl fmt::Display for OperationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            OperationKind::Insert => "insert",
+            OperationKind::InsertFront => "insert_front",
+            OperationKind::InsertMiddle => "insert_middle",
+            OperationKind::Lookup => "lookup",
+            OperationKind::Remove => "remove",
+            OperationKind::PopMin => "pop_min",
+            OperationKind::Iterate => "iterate",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Operation<K> {
+    Insert(K),
+    InsertFront(K),
+    InsertMiddle(K),
+    Lookup(K),
+    Remove(K),
+    PopMin,
+    Iterate,
+}
+
+/// Keys the synthetic data can be generated for.
+pub trait Key: Ord + Hash + Clone {
+    fn from_id(id: u64) -> Self;
+}
+
+impl Key for u64 {
+    fn from_id(id: u64) -> Self {
+        id
+    }
+}
+
+impl Key for String {
+    fn from_id(id: u64) -> Self {
+        format!("key-{:016}", id)
+    }
+}
+
+/// Deterministic pseudo-random numbers, so every candidate replays the same operations.
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self) -> u64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        self.0 >> 33
+    }
+
+    fn below(&mut self, bound: u64) -> u64 {
+        self.next() % bound
+    }
+}
+
+/// Keys present before the first operation, and the operations to replay.
+#[derive(Debug, Clone)]
+pub struct Script<K> {
+    pub initial: Vec<K>,
+    pub operations: Vec<Operation<K>>,
+}
+
+/// Builds the replay script for `workload`. Lookups and removals always target keys that are
+/// present at that point, and inserts always add new keys.
+pub fn script<K: Key>(workload: &Workload, seed: u64) -> Script<K> {
+    let mut rng = Lcg(seed);
+
+    // Ids are spread out so new keys land all over the key space, not only at the end.
+    let mut next_id = 0u64;
+    let mut fresh_id = |rng: &mut Lcg| {
+        next_id += 1;
+        (rng.below(1 << 20) << 32) | next_id
+    };
+
+    let mut live: Vec<u64> = (0..workload.size).map(|_| fresh_id(&mut rng)).collect();
+    let initial = live.iter().map(|&id| K::from_id(id)).collect();
+    let mut ordered: BTreeSet<u64> = live.iter().copied().collect();
+    let mut positions: HashMap<u64, usize> =
+        live.iter().enumerate().map(|(i, &id)| (id, i)).collect();
+
+    let forget = |id: u64, live: &mut Vec<u64>, positions: &mut HashMap<u64, usize>| {
+        let index = positions.remove(&id).expect("live id has a position");
+        live.swap_remove(index);
+        if let Some(&moved) = live.get(index) {
+            positions.insert(moved, index);
+        }
+    };
+
+    let weights = workload.mix.weights();
+    let total = u64::from(workload.mix.total());
+    let mut operations = Vec::with_capacity(workload.operations);
+    while operations.len() < workload.operations {
+        let mut roll = rng.below(total);
+        let kind = weights
+            .iter()
+            .find(|(_, weight)| {
+                let hit = roll < u64::from(*weight);
+                roll = roll.saturating_sub(u64::from(*weight));
+                hit
+            })
+            .map(|(kind, _)| *kind)
+            .expect("roll is below the total weight");
+
+        let needs_element = matches!(
+            kind,
+            OperationKind::Lookup | OperationKind::Remove | OperationKind::PopMin
+        );
+        if needs_element && live.is_empty() {
+            continue;
+        }
+
+        let operation = match kind {
+            OperationKind::Insert | OperationKind::InsertFront | OperationKind::InsertMiddle => {
+                let id = fresh_id(&mut rng);
+                positions.insert(id, live.len());
+                live.push(id);
+                ordered.insert(id);
+                let key = K::from_id(id);
+                match kind {
+                    OperationKind::Insert => Operation::Insert(key),
+                    OperationKind::InsertFront => Operation::InsertFront(key),
+                    _ => Operation::InsertMiddle(key),
+                }
+            }
+            OperationKind::Lookup => {
+                let id = live[rng.below(live.len() as u64) as usize];
+                Operation::Lookup(K::from_id(id))
+            }
+            OperationKind::Remove => {
+                let id = live[rng.below(live.len() as u64) as usize];
+                forget(id, &mut live, &mut positions);
+                ordered.remove(&id);
+                Operation::Remove(K::from_id(id))
+            }
+            OperationKind::PopMin => {
+                let id = ordered.pop_first().expect("live is not empty");
+                forget(id, &mut live, &mut positions);
+                Operation::PopMin
+            }
+            OperationKind::Iterate => Operation::Iterate,
+        };
+        operations.push(operation);
+    }
+
+    Script {
+        initial,
+        operations,
+    }
+}
+
+/// A collection the advisor can measure. Sequences keep insertion order; maps, sets and heaps
+/// ignore where an element is inserted.
+pub trait Candidate<K> {
+    fn name(&self) -> &'static str;
+    fn len(&self) -> usize;
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    fn insert(&mut self, key: K);
+    fn insert_front(&mut self, key: K);
+    fn insert_middle(&mut self, key: K);
+    fn lookup(&self, key: &K) -> bool;
+    fn remove(&mut self, key: &K) -> bool;
+    fn pop_min(&mut self) -> Option<K>;
+    /// Visits every element, in key order if `ordered`, and returns how many were visited.
+    fn iterate(&self, ordered: bool) -> usize;
+    /// All keys in ascending order, for checking candidates against each other.
+    fn sorted_keys(&self) -> Vec<K>;
+
+    fn apply(&mut self, operation: &Operation<K>, ordered: bool)
+    where
+        K: Clone,
+    {
+        match operation {
+            Operation::Insert(key) => self.insert(key.clone()),
+            Operation::InsertFront(key) => self.insert_front(key.clone()),
+            Operation::InsertMiddle(key) => self.insert_middle(key.clone()),
+            Operation::Lookup(key) => {
+                black_box(self.lookup(key));
+            }
+            Operation::Remove(key) => {
+                black_box(self.remove(key));
+            }
+            Operation::PopMin => {
+                black_box(self.pop_min());
+            }
+            Operation::Iterate => {
+                black_box(self.iterate(ordered));
+            }
+        }
+    }
+}
+
+fn visit<'a, K: 'a>(keys: impl Iterator<Item = &'a K>) -> usize {
+    keys.map(black_box).count()
+}
+
+fn visit_sorted<'a, K: Ord + 'a>(keys: impl Iterator<Item = &'a K>) -> usize {
+    let mut keys: Vec<&K> = keys.collect();
+    keys.sort_unstable();
+    visit(keys.into_iter())
+}
+
+fn sorted<'a, K: Ord + Clone + 'a>(keys: impl Iterator<Item = &'a K>) -> Vec<K> {
+    let mut keys: Vec<K> = keys.cloned().collect();
+    keys.sort();
+    keys
+}
+
+fn position_of_min<'a, K: Ord + 'a>(keys: impl Iterator<Item = &'a K>) -> Option<usize> {
+    keys.enumerate()
+        .min_by(|a, b| a.1.cmp(b.1))
+        .map(|(index, _)| index)
+}
+
+impl<K: Key> Candidate<K> for Vec<K> {
+    fn name(&self) -> &'static str {
+        "Vec"
+    }
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+    fn insert(&mut self, key: K) {
+        self.push(key);
+    }
+    fn insert_front(&mut self, key: K) {
+        Vec::insert(self, 0, key);
+    }
+    fn insert_middle(&mut self, key: K) {
+        Vec::insert(self, self.len() / 2, key);
+    }
+    fn lookup(&self, key: &K) -> bool {
+        self.contains(key)
+    }
+    fn remove(&mut self, key: &K) -> bool {
+        match self.iter().position(|k| k == key) {
+            Some(index) => {
+                Vec::remove(self, index);
+                true
+            }
+            None => false,
+        }
+    }
+    fn pop_min(&mut self) -> Option<K> {
+        position_of_min(self.iter()).map(|index| Vec::remove(self, index))
+    }
+    fn iterate(&self, ordered: bool) -> usize {
+        if ordered {
+            visit_sorted(self.iter())
+        } else {
+            visit(self.iter())
+        }
+    }
+    fn sorted_keys(&self) -> Vec<K> {
+        sorted(self.iter())
+    }
+}
+
+impl<K: Key> Candidate<K> for VecDeque<K> {
+    fn name(&self) -> &'static str {
+        "VecDeque"
+    }
+    fn len(&self) -> usize {
+        VecDeque::len(self)
+    }
+    fn insert(&mut self, key: K) {
+        self.push_back(key);
+    }
+    fn insert_front(&mut self, key: K) {
+        self.push_front(key);
+    }
+    fn insert_middle(&mut self, key: K) {
+        VecDeque::insert(self, self.len() / 2, key);
+    }
+    fn lookup(&self, key: &K) -> bool {
+        self.contains(key)
+    }
+    fn remove(&mut self, key: &K) -> bool {
+        match self.iter().position(|k| k == key) {
+            Some(index) => VecDeque::remove(self, index).is_some(),
+            None => false,
+        }
+    }
+    fn pop_min(&mut self) -> Option<K> {
+        position_of_min(self.iter()).and_then(|index| VecDeque::remove(self, index))
+    }
+    fn iterate(&self, ordered: bool) -> usize {
+        if ordered {
+            visit_sorted(self.iter())
+        } else {
+            visit(self.iter())
+        }
+    }
+    fn sorted_keys(&self) -> Vec<K> {
+        sorted(self.iter())
+    }
+}
+
+/// Removes the element at `index` by splitting the list there.
+fn remove_at<K>(list: &mut LinkedList<K>, index: usize) -> Option<K> {
+    let mut tail = list.split_off(index);
+    let removed = tail.pop_front();
+    list.append(&mut tail);
+    removed
+}
+
+impl<K: Key> Candidate<K> for LinkedList<K> {
+    fn name(&self) -> &'static str {
+        "LinkedList"
+    }
+    fn len(&self) -> usize {
+        LinkedList::len(self)
+    }
+    fn insert(&mut self, key: K) {
+        self.push_back(key);
+    }
+    fn insert_front(&mut self, key: K) {
+        self.push_front(key);
+    }
+    fn insert_middle(&mut self, key: K) {
+        let mut tail = self.split_off(self.len() / 2);
+        self.push_back(key);
+        self.append(&mut tail);
+    }
+    fn lookup(&self, key: &K) -> bool {
+        self.contains(key)
+    }
+    fn remove(&mut self, key: &K) -> bool {
+        match self.iter().position(|k| k == key) {
+            Some(index) => remove_at(self, index).is_some(),
+            None => false,
+        }
+    }
+    fn pop_min(&mut self) -> Option<K> {
+        position_of_min(self.iter()).and_then(|index| remove_at(self, index))
+    }
+    fn iterate(&self, ordered: bool) -> usize {
+        if ordered {
+            visit_sorted(self.iter())
+        } else {
+            visit(self.iter())
+        }
+    }
+    fn sorted_keys(&self) -> Vec<K> {
+        sorted(self.iter())
+    }
+}
+
+impl<K: Key> Candidate<K> for HashMap<K, u64> {
+    fn name(&self) -> &'static str {
+        "HashMap"
+    }
+    fn len(&self) -> usize {
+        HashMap::len(self)
+    }
+    fn insert(&mut self, key: K) {
+        HashMap::insert(self, key, 0);
+    }
+    fn insert_front(&mut self, key: K) {
+        Candidate::insert(self, key);
+    }
+    fn insert_middle(&mut self, key: K) {
+        Candidate::insert(self, key);
+    }
+    fn lookup(&self, key: &K) -> bool {
+        self.contains_key(key)
+    }
+    fn remove(&mut self, key: &K) -> bool {
+        HashMap::remove(self, key).is_some()
+    }
+    fn pop_min(&mut self) -> Option<K> {
+        let min = self.keys().min()?.clone();
+        HashMap::remove(self, &min);
+        Some(min)
+    }
+    fn iterate(&self, ordered: bool) -> usize {
+        if ordered {
+            visit_sorted(self.keys())
+        } else {
+            visit(self.keys())
+        }
+    }
+    fn sorted_keys(&self) -> Vec<K> {
+        sorted(self.keys())
+    }
+}
+
+impl<K: Key> Candidate<K> for BTreeMap<K, u64> {
+    fn name(&self) -> &'static str {
+        "BTreeMap"
+    }
+    fn len(&self) -> usize {
+        BTreeMap::len(self)
+    }
+    fn insert(&mut self, key: K) {
+        BTreeMap::insert(self, key, 0);
+    }
+    fn insert_front(&mut self, key: K) {
+        Candidate::insert(self, key);
+    }
+    fn insert_middle(&mut self, key: K) {
+        Candidate::insert(self, key);
+    }
+    fn lookup(&self, key: &K) -> bool {
+        self.contains_key(key)
+    }
+    fn remove(&mut self, key: &K) -> bool {
+        BTreeMap::remove(self, key).is_some()
+    }
+    fn pop_min(&mut self) -> Option<K> {
+        self.pop_first().map(|(key, _)| key)
+    }
+    fn iterate(&self, _ordered: bool) -> usize {
+        visit(self.keys())
+    }
+    fn sorted_keys(&self) -> Vec<K> {
+        self.keys().cloned().collect()
+    }
+}
+
+impl<K: Key> Candidate<K> for HashSet<K> {
+    fn name(&self) -> &'static str {
+        "HashSet"
+    }
+    fn len(&self) -> usize {
+        HashSet::len(self)
+    }
+    fn insert(&mut self, key: K) {
+        HashSet::insert(self, key);
+    }
+    fn insert_front(&mut self, key: K) {
+        HashSet::insert(self, key);
+    }
+    fn insert_middle(&mut self, key: K) {
+        HashSet::insert(self, key);
+    }
+    fn lookup(&self, key: &K) -> bool {
+        self.contains(key)
+    }
+    fn remove(&mut self, key: &K) -> bool {
+        HashSet::remove(self, key)
+    }
+    fn pop_min(&mut self) -> Option<K> {
+        let min = self.iter().min()?.clone();
+        HashSet::remove(self, &min);
+        Some(min)
+    }
+    fn iterate(&self, ordered: bool) -> usize {
+        if ordered {
+            visit_sorted(self.iter())
+        } else {
+            visit(self.iter())
+        }
+    }
+    fn sorted_keys(&self) -> Vec<K> {
+        sorted(self.iter())
+    }
+}
+
+impl<K: Key> Candidate<K> for BTreeSet<K> {
+    fn name(&self) -> &'static str {
+        "BTreeSet"
+    }
+    fn len(&self) -> usize {
+        BTreeSet::len(self)
+    }
+    fn insert(&mut self, key: K) {
+        BTreeSet::insert(self, key);
+    }
+    fn insert_front(&mut self, key: K) {
+        BTreeSet::insert(self, key);
+    }
+    fn insert_middle(&mut self, key: K) {
+        BTreeSet::insert(self, key);
+    }
+    fn lookup(&self, key: &K) -> bool {
+        self.contains(key)
+    }
+    fn remove(&mut self, key: &K) -> bool {
+        BTreeSet::remove(self, key)
+    }
+    fn pop_min(&mut self) -> Option<K> {
+        self.pop_first()
+    }
+    fn iterate(&self, _ordered: bool) -> usize {
+        visit(self.iter())
+    }
+    fn sorted_keys(&self) -> Vec<K> {
+        self.iter().cloned().collect()
+    }
+}
+
+/// A min-heap, so `pop_min` is the heap's native operation.
+impl<K: Key> Candidate<K> for BinaryHeap<Reverse<K>> {
+    fn name(&self) -> &'static str {
+        "BinaryHeap"
+    }
+    fn len(&self) -> usize {
+        BinaryHeap::len(self)
+    }
+    fn insert(&mut self, key: K) {
+        self.push(Reverse(key));
+    }
+    fn insert_front(&mut self, key: K) {
+        self.push(Reverse(key));
+    }
+    fn insert_middle(&mut self, key: K) {
+        self.push(Reverse(key));
+    }
+    fn lookup(&self, key: &K) -> bool {
+        self.iter().any(|Reverse(k)| k == key)
+    }
+    fn remove(&mut self, key: &K) -> bool {
+        let before = BinaryHeap::len(self);
+        self.retain(|Reverse(k)| k != key);
+        BinaryHeap::len(self) < before
+    }
+    fn pop_min(&mut self) -> Option<K> {
+        self.pop().map(|Reverse(key)| key)
+    }
+    fn iterate(&self, ordered: bool) -> usize {
+        if ordered {
+            visit(self.clone().into_sorted_vec().iter())
+        } else {
+            visit(self.iter())
+        }
+    }
+    fn sorted_keys(&self) -> Vec<K> {
+        sorted(self.iter().map(|Reverse(key)| key))
+    }
+}
+
+/// Every candidate collection, filled with `initial`.
+pub fn candidates<K: Key + 'static>(initial: &[K]) -> Vec<Box<dyn Candidate<K>>> {
+    (0..).map_while(|index| candidate(index, initial)).collect()
+}
+
+/// The candidate at `index` in [`candidates`], filled with `initial`, or `None` past the last
+/// one. Builds only that collection.
+pub fn candidate<K: Key + 'static>(index: usize, initial: &[K]) -> Option<Box<dyn Candidate<K>>> {
+    let candidate: Box<dyn Candidate<K>> = match index {
+        0 => Box::new(initial.to_vec()),
+        1 => Box::new(initial.iter().cloned().collect::<VecDeque<K>>()),
+        2 => Box::new(initial.iter().cloned().collect::<LinkedList<K>>()),
+        3 => Box::new(
+            initial
+                .iter()
+                .map(|k| (k.clone(), 0))
+                .collect::<HashMap<K, u64>>(),
+        ),
+        4 => Box::new(
+            initial
+                .iter()
+                .map(|k| (k.clone(), 0))
+                .collect::<BTreeMap<K, u64>>(),
+        ),
+        5 => Box::new(initial.iter().cloned().collect::<HashSet<K>>()),
+        6 => Box::new(initial.iter().cloned().collect::<BTreeSet<K>>()),
+        7 => Box::new(
+            initial
+                .iter()
+                .cloned()
+                .map(Reverse)
+                .collect::<BinaryHeap<Reverse<K>>>(),
+        ),
+        _ => return None,
+    };
+    Some(candidate)
+}
+
+/// How long a single replay may take before a candidate is given up on.
+pub const DEFAULT_BUDGET: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone)]
+pub struct Measurement {
+    pub name: &'static str,
+    /// Median time of the complete replays, or the time of the replay the budget cut short.
+    pub total: Duration,
+    /// Operations replayed before the budget ran out; equals the script length when complete.
+    pub completed: usize,
+    pub operations: usize,
+}
+
+impl Measurement {
+    pub fn finished(&self) -> bool {
+        self.completed == self.operations
+    }
+
+    /// Nanoseconds per operation. For unfinished candidates this is a lower bound.
+    pub fn nanos_per_op(&self) -> f64 {
+        self.total.as_nanos() as f64 / self.completed.max(1) as f64
+    }
+}
+
+/// Replays `script` on every candidate `rounds` times and returns them ranked, fastest first.
+/// Candidates that exceed `budget` in a round are stopped and ranked after the finished ones, by
+/// the operations that round got through in its time.
+pub fn measure<K: Key + 'static>(
+    script: &Script<K>,
+    ordered: bool,
+    rounds: usize,
+    budget: Duration,
+) -> Vec<Measurement> {
+    let rounds = rounds.max(1);
+    let operations = script.operations.len();
+    let mut measurements: Vec<Measurement> = (0..)
+        .map_while(|index| candidate::<K>(index, &[]).map(|empty| (index, empty.name())))
+        .map(|(index, name)| {
+            let mut totals = Vec::with_capacity(rounds);
+            let mut cut_short = None;
+            for _ in 0..rounds {
+                let mut candidate =
+                    candidate(index, &script.initial).expect("index came from the candidates");
+                let (elapsed, done) = replay(candidate.as_mut(), script, ordered, budget);
+                if done < operations {
+                    cut_short = Some((elapsed, done));
+                    break;
+                }
+                totals.push(elapsed);
+            }
+            let (total, completed) = cut_short.unwrap_or_else(|| {
+                totals.sort();
+                (totals[totals.len() / 2], operations)
+            });
+            Measurement {
+                name,
+                total,
+                completed,
+                operations,
+            }
+        })
+        .collect();
+
+    measurements.sort_by(|a, b| {
+        b.finished()
+            .cmp(&a.finished())
+            .then(a.nanos_per_op().total_cmp(&b.nanos_per_op()))
+    });
+    measurements
+}
+
+fn replay<K: Clone>(
+    candidate: &mut dyn Candidate<K>,
+    script: &Script<K>,
+    ordered: bool,
+    budget: Duration,
+) -> (Duration, usize) {
+    let start = Instant::now();
+    for (index, operation) in script.operations.iter().enumerate() {
+        candidate.apply(operation, ordered);
+        if index % 64 == 63 && start.elapsed() > budget {
+            return (start.elapsed(), index + 1);
+        }
+    }
+    (start.elapsed(), script.operations.len())
+}
+
+/// Builds the script for `workload` with the requested key type and measures every candidate.
+pub fn advise(workload: &Workload, rounds: usize, budget: Duration) -> Vec<Measurement> {
+    const SEED: u64 = 0x00c0_ffee;
+    match workload.key {
+        KeyType::Integer => measure(
+            &script::<u64>(workload, SEED),
+            workload.ordered,
+            rounds,
+            budget,
+        ),
+        KeyType::String => measure(
+            &script::<String>(workload, SEED),
+            workload.ordered,
+            rounds,
+            budget,
+        ),
+    }
+}
+
+/// Human readable ranking with the measured costs.
+pub fn render(workload: &Workload, measurements: &[Measurement]) -> String {
+    let mut out = format!(
+        "Workload: {} elements, {} operations, {} keys{}\nMix: {}\n\n",
+        workload.size,
+        workload.operations,
+        workload.key,
+        if workload.ordered {
+            ", ordered iteration"
+        } else {
+            ""
+        },
+        workload.mix,
+    );
+    out.push_str(&format!(
+        "{:>4}  {:<12} {:>14} {:>12}\n",
+        "Rank", "Collection", "ns/op", "total"
+    ));
+    for (rank, measurement) in measurements.iter().enumerate() {
+        let cost = if measurement.finished() {
+            format!("{:.1}", measurement.nanos_per_op())
+        } else {
+            format!(">{:.1}", measurement.nanos_per_op())
+        };
+        out.push_str(&format!(
+            "{:>4}  {:<12} {:>14} {:>12}\n",
+            rank + 1,
+            measurement.name,
+            cost,
+            format!("{:.2?}", measurement.total),
+        ));
+    }
+    if let Some(best) = measurements.first().filter(|best| best.finished()) {
+        out.push_str(&format!("\nRecommendation: {}\n", best.name));
+    }
+    if measurements.iter().any(|m| !m.finished()) {
+        out.push_str("(>) exceeded the time budget; the cost is a lower bound\n");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workload(mix: &str) -> Workload {
+        Workload {
+            size: 200,
+            operations: 500,
+            mix: mix.parse().unwrap(),
+            ordered: true,
+            key: KeyType::Integer,
+        }
+    }
+
+    #[test]
+    fn parses_operation_mix() {
+        let mix: OperationMix = "insert=40, lookup=50,pop-min=10".parse().unwrap();
+        assert_eq!(mix.insert, 40);
+        assert_eq!(mix.lookup, 50);
+        assert_eq!(mix.pop_min, 10);
+        assert_eq!(mix.total(), 100);
+        assert_eq!(mix.to_string(), "insert 40%, lookup 50%, pop_min 10%");
+
+        assert!("insert".parse::<OperationMix>().is_err());
+        assert!("shuffle=3".parse::<OperationMix>().is_err());
+        assert!("insert=x".parse::<OperationMix>().is_err());
+    }
+
+    #[test]
+    fn reads_toml_with_defaults() {
+        let workload = Workload::from_toml(
+            "size = 500\nordered = true\nkey = \"string\"\n[mix]\nlookup = 3\nremove = 1\n",
+        )
+        .unwrap();
+        assert_eq!(workload.size, 500);
+        assert_eq!(workload.operations, Workload::default().operations);
+        assert!(workload.ordered);
+        assert_eq!(workload.key, KeyType::String);
+        assert_eq!(workload.mix.lookup, 3);
+        assert_eq!(workload.mix.insert, 0);
+
+        assert!(Workload::from_toml("sizes = 3").is_err());
+    }
+
+    #[test]
+    fn validates_workloads() {
+        assert!(workload("insert=1").validate().is_ok());
+        assert!(workload("").validate().is_err());
+        let empty_lookups = Workload {
+            size: 0,
+            ..workload("lookup=1")
+        };
+        assert!(empty_lookups.validate().is_err());
+    }
+
+    #[test]
+    fn script_follows_the_mix() {
+        let script: Script<u64> = script(&workload("insert=3,lookup=1"), 1);
+        assert_eq!(script.initial.len(), 200);
+        assert_eq!(script.operations.len(), 500);
+        let inserts = script
+            .operations
+            .iter()
+            .filter(|op| matches!(op, Operation::Insert(_)))
+            .count();
+        assert!((300..450).contains(&inserts), "{} inserts", inserts);
+        assert!(script
+            .operations
+            .iter()
+            .all(|op| matches!(op, Operation::Insert(_) | Operation::Lookup(_))));
+    }
+
+    #[test]
+    fn script_only_touches_live_keys() {
+        let script: Script<u64> = script(&workload("insert=1,lookup=2,remove=2,pop_min=1"), 9);
+        let mut live: BTreeSet<u64> = script.initial.iter().copied().collect();
+        for operation in &script.operations {
+            match operation {
+                Operation::Insert(k) | Operation::InsertFront(k) | Operation::InsertMiddle(k) => {
+                    assert!(live.insert(*k))
+                }
+                Operation::Lookup(k) => assert!(live.contains(k)),
+                Operation::Remove(k) => assert!(live.remove(k)),
+                Operation::PopMin => assert!(live.pop_first().is_some()),
+                Operation::Iterate => {}
+            }
+        }
+    }
+
+    #[test]
+    fn all_candidates_end_in_the_same_state() {
+        let workload = workload(
+            "insert=2,insert_front=1,insert_middle=1,lookup=2,remove=2,pop_min=2,iterate=1",
+        );
+        let script: Script<String> = script(&workload, 3);
+        let mut expected: Option<Vec<String>> = None;
+        for mut candidate in candidates(&script.initial) {
+            for operation in &script.operations {
+                candidate.apply(operation, workload.ordered);
+            }
+            let keys = candidate.sorted_keys();
+            assert_eq!(keys.len(), candidate.len(), "{}", candidate.name());
+            match &expected {
+                Some(expected) => assert_eq!(&keys, expected, "{}", candidate.name()),
+                None => expected = Some(keys),
+            }
+        }
+    }
+
+    #[test]
+    fn builds_single_candidates() {
+        let names: Vec<&str> = candidates::<u64>(&[]).iter().map(|c| c.name()).collect();
+        assert_eq!(names.len(), 8);
+        for (index, name) in names.iter().enumerate() {
+            let candidate = candidate(index, &[4u64, 2]).unwrap();
+            assert_eq!(candidate.name(), *name);
+            assert_eq!(candidate.len(), 2);
+        }
+        assert!(candidate::<u64>(names.len(), &[]).is_none());
+    }
+
+    #[test]
+    fn pop_min_returns_the_smallest_key() {
+        for mut candidate in candidates(&[5u64, 1, 9, 3]) {
+            assert_eq!(candidate.pop_min(), Some(1), "{}", candidate.name());
+            assert_eq!(candidate.pop_min(), Some(3), "{}", candidate.name());
+            assert_eq!(candidate.len(), 2);
+        }
+    }
+
+    #[test]
+    fn ranks_every_candidate() {
+        let workload = workload("insert=1,lookup=1");
+        let measurements = advise(&workload, 1, DEFAULT_BUDGET);
+        assert_eq!(measurements.len(), 8);
+        assert!(measurements.iter().all(Measurement::finished));
+        assert!(measurements
+            .windows(2)
+            .all(|pair| pair[0].nanos_per_op() <= pair[1].nanos_per_op()));
+
+        let report = render(&workload, &measurements);
+        assert!(report.contains("Recommendation: "));
+        assert!(report.contains("Mix: insert 50%, lookup 50%"));
+    }
+
+    #[test]
+    fn stops_candidates_over_budget() {
+        let measurements = advise(&workload("iterate=1"), 3, Duration::ZERO);
+        assert!(measurements.iter().all(|m| m.completed == 64));
+    }
+}
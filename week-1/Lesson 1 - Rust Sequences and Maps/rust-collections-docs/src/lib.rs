@@ -0,0 +1,5 @@
+//! Library side of the collections lesson: reusable versions of the challenges in `main.rs`.
+
+pub mod advisor;
+pub mod priority_queue;
+pub mod word_count;
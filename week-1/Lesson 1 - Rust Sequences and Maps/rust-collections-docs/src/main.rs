@@ -65,13 +65,165 @@
 //! This approach is particularly useful in scenarios requiring frequent modifications to a collection based on key presence.
 
 use std::collections::{BinaryHeap, HashMap};
+use std::error::Error;
+use std::fs::File;
+use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
 
-fn main() {
+use clap::{Parser, Subcommand};
+use rust_collections_docs::advisor::{self, KeyType, OperationMix, Workload};
+use rust_collections_docs::priority_queue::IndexedPriorityQueue;
+use rust_collections_docs::word_count::{top_n, WordCounter};
+
+#[derive(Parser)]
+#[clap(version = "1.0", about = "Rust collections lesson challenges")]
+struct Opts {
+    #[clap(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Count words or n-grams in files, or in stdin when no file is given
+    Words {
+        /// Files to read; "-" reads stdin
+        files: Vec<PathBuf>,
+        /// Number of entries to print
+        #[clap(short, long, default_value_t = 10)]
+        top: usize,
+        /// Count sequences of this many words (2 for bigrams, 3 for trigrams)
+        #[clap(short, long, default_value_t = 1)]
+        ngram: usize,
+        /// Remove common English stopwords
+        #[clap(short, long)]
+        stopwords: bool,
+        /// Remove the stopwords listed in this file, one per line
+        #[clap(long)]
+        stopwords_file: Option<PathBuf>,
+    },
+    /// Measure every std collection on a synthetic workload and recommend one
+    Advise {
+        /// TOML file describing the workload; flags override its values
+        #[clap(short, long)]
+        file: Option<PathBuf>,
+        /// Number of elements held before the operations start
+        #[clap(short, long)]
+        size: Option<usize>,
+        /// Number of operations to replay
+        #[clap(short, long)]
+        operations: Option<usize>,
+        /// Operation weights, e.g. "insert=40,lookup=50,remove=10"; operations are insert,
+        /// insert_front, insert_middle, lookup, remove, pop_min and iterate
+        #[clap(short, long)]
+        mix: Option<OperationMix>,
+        /// Iteration has to visit elements in key order; `--ordered=false` overrides the file
+        #[clap(long, num_args = 0..=1, require_equals = true, default_missing_value = "true")]
+        ordered: Option<bool>,
+        /// Key type: integer or string
+        #[clap(short, long)]
+        key: Option<KeyType>,
+        /// Replays per collection; the median is reported
+        #[clap(short, long, default_value_t = 3)]
+        rounds: usize,
+        /// Seconds a single replay may take before a collection is given up on
+        #[clap(short, long, default_value_t = advisor::DEFAULT_BUDGET.as_secs_f64())]
+        budget: f64,
+    },
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    match Opts::parse().command {
+        Some(Command::Words {
+            files,
+            top,
+            ngram,
+            stopwords,
+            stopwords_file,
+        }) => {
+            if ngram == 0 {
+                return Err("--ngram must be at least 1".into());
+            }
+            let mut counter = WordCounter::new().with_ngram(ngram);
+            if stopwords {
+                counter = counter.with_english_stopwords();
+            }
+            if let Some(path) = stopwords_file {
+                let list = std::fs::read_to_string(path)?;
+                counter = counter.with_stopwords(list.lines().map(str::trim));
+            }
+            words(&counter, &files, top)
+        }
+        Some(Command::Advise {
+            file,
+            size,
+            operations,
+            mix,
+            ordered,
+            key,
+            rounds,
+            budget,
+        }) => {
+            let mut workload = match file {
+                Some(path) => Workload::from_toml(&std::fs::read_to_string(path)?)?,
+                None => Workload::default(),
+            };
+            workload.size = size.unwrap_or(workload.size);
+            workload.operations = operations.unwrap_or(workload.operations);
+            workload.mix = mix.unwrap_or(workload.mix);
+            workload.ordered = ordered.unwrap_or(workload.ordered);
+            workload.key = key.unwrap_or(workload.key);
+            workload.validate()?;
+            if !budget.is_finite() || budget <= 0.0 {
+                return Err("--budget must be a positive number of seconds".into());
+            }
+
+            let measurements = advisor::advise(&workload, rounds, Duration::from_secs_f64(budget));
+            print!("{}", advisor::render(&workload, &measurements));
+            Ok(())
+        }
+        None => {
+            demo();
+            Ok(())
+        }
+    }
+}
+
+fn words(counter: &WordCounter, files: &[PathBuf], top: usize) -> Result<(), Box<dyn Error>> {
+    let mut counts = HashMap::new();
+    if files.is_empty() {
+        read_into(counter, io::stdin().lock(), &mut counts)?;
+    }
+    for path in files {
+        if path.as_os_str() == "-" {
+            read_into(counter, io::stdin().lock(), &mut counts)?;
+        } else {
+            read_into(counter, File::open(path)?, &mut counts)?;
+        }
+    }
+    for (word, count) in top_n(&counts, top) {
+        println!("{:>8} {}", count, word);
+    }
+    Ok(())
+}
+
+fn read_into<R: io::Read>(
+    counter: &WordCounter,
+    mut reader: R,
+    counts: &mut HashMap<String, u32>,
+) -> io::Result<()> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)?;
+    counter.count_into(&text, counts);
+    Ok(())
+}
+
+fn demo() {
     println!("Hello, world!");
 
     // Challenge(1): Implement a word counter with HashMap
     let sample_text = "Hello, world! This is a sample text. This is another sample text.";
-    let word_count = word_counter(sample_text);
+    let word_count = WordCounter::new().count(sample_text);
     println!("Word count: {:?}", word_count);
 
     println!();
@@ -93,14 +245,22 @@ fn main() {
     println!("Priority Queue: {:?}", priority_queue);
     priority_queue.pop();
     println!("Priority Queue: {:?}", priority_queue);
-}
 
-fn word_counter(text: &str) -> HashMap<String, u32> {
-    let mut word_count = HashMap::new();
-    for word in text.split_whitespace() {
-        *word_count.entry(word.to_string()).or_insert(0) += 1;
+    println!();
+
+    // Indexed priority queue: equal priorities pop in insertion order, and items can be
+    // re-prioritised or removed through the handle returned by push.
+    let mut indexed = IndexedPriorityQueue::new();
+    let a = indexed.push(1, "A".to_string());
+    let b = indexed.push(2, "B".to_string());
+    indexed.push(2, "B2".to_string());
+    indexed.push(3, "C".to_string());
+    indexed.change_priority(a, 3);
+    indexed.remove(b);
+    println!("Peek: {:?}", indexed.peek());
+    while let Some((priority, value)) = indexed.pop() {
+        println!("Popped {} with priority {}", value, priority);
     }
-    word_count
 }
 
 use std::cmp::Ordering;
@@ -135,7 +295,7 @@ struct Item {
 
 impl PartialOrd for Item {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(&other))
+        Some(self.cmp(other))
     }
 }
 
@@ -0,0 +1,337 @@
+//! Indexed max-priority queue with stable FIFO ordering.
+//!
+//! `std::collections::BinaryHeap` cannot find an element once it has been pushed, so it cannot
+//! change an element's priority or remove it. This queue keeps a binary heap of slot indices plus
+//! the heap position of every slot, so both operations take O(log n) given the [`Handle`] returned
+//! by [`IndexedPriorityQueue::push`].
+//!
+//! Every push gets an increasing sequence number. Among equal priorities the lowest sequence number
+//! wins, so items with the same priority come out in insertion order.
+
+use std::cmp::Ordering;
+
+/// Refers to an item pushed into an [`IndexedPriorityQueue`]. A handle goes stale once its item is
+/// popped or removed, and is never confused with a later item that reuses the same slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Handle {
+    slot: usize,
+    generation: u64,
+}
+
+#[derive(Debug)]
+struct Entry<P, T> {
+    priority: P,
+    sequence: u64,
+    value: T,
+    position: usize,
+}
+
+#[derive(Debug)]
+struct Slot<P, T> {
+    generation: u64,
+    entry: Option<Entry<P, T>>,
+}
+
+#[derive(Debug)]
+pub struct IndexedPriorityQueue<P, T> {
+    slots: Vec<Slot<P, T>>,
+    free: Vec<usize>,
+    /// Slot indices laid out as a binary heap.
+    heap: Vec<usize>,
+    next_sequence: u64,
+}
+
+impl<P: Ord, T> Default for IndexedPriorityQueue<P, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P: Ord, T> IndexedPriorityQueue<P, T> {
+    pub fn new() -> Self {
+        Self {
+            slots: Vec::new(),
+            free: Vec::new(),
+            heap: Vec::new(),
+            next_sequence: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Inserts `value` and returns a handle for later updates.
+    pub fn push(&mut self, priority: P, value: T) -> Handle {
+        let sequence = self.next_sequence;
+        self.next_sequence += 1;
+
+        let slot = match self.free.pop() {
+            Some(slot) => slot,
+            None => {
+                self.slots.push(Slot {
+                    generation: 0,
+                    entry: None,
+                });
+                self.slots.len() - 1
+            }
+        };
+        let position = self.heap.len();
+        self.slots[slot].entry = Some(Entry {
+            priority,
+            sequence,
+            value,
+            position,
+        });
+        self.heap.push(slot);
+        self.sift_up(position);
+
+        Handle {
+            slot,
+            generation: self.slots[slot].generation,
+        }
+    }
+
+    /// The highest priority item without removing it.
+    pub fn peek(&self) -> Option<(&P, &T)> {
+        let slot = *self.heap.first()?;
+        let entry = self.entry(slot);
+        Some((&entry.priority, &entry.value))
+    }
+
+    /// Removes and returns the highest priority item.
+    pub fn pop(&mut self) -> Option<(P, T)> {
+        let slot = *self.heap.first()?;
+        Some(self.remove_slot(slot))
+    }
+
+    /// Whether `handle` still refers to an item in the queue.
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.live_slot(handle).is_some()
+    }
+
+    pub fn get(&self, handle: Handle) -> Option<(&P, &T)> {
+        let entry = self.entry(self.live_slot(handle)?);
+        Some((&entry.priority, &entry.value))
+    }
+
+    /// Sets a new priority for the item behind `handle`, moving it up or down as needed, and
+    /// returns the old priority. The item keeps its place among items of equal priority that were
+    /// pushed before or after it. Returns `None` for stale handles.
+    pub fn change_priority(&mut self, handle: Handle, priority: P) -> Option<P> {
+        let slot = self.live_slot(handle)?;
+        let entry = self.slots[slot]
+            .entry
+            .as_mut()
+            .expect("live slot has an entry");
+        let old = std::mem::replace(&mut entry.priority, priority);
+        let position = entry.position;
+        let position = self.sift_up(position);
+        self.sift_down(position);
+        Some(old)
+    }
+
+    /// Removes the item behind `handle`. Returns `None` for stale handles.
+    pub fn remove(&mut self, handle: Handle) -> Option<(P, T)> {
+        let slot = self.live_slot(handle)?;
+        Some(self.remove_slot(slot))
+    }
+
+    fn live_slot(&self, handle: Handle) -> Option<usize> {
+        let slot = self.slots.get(handle.slot)?;
+        (slot.generation == handle.generation && slot.entry.is_some()).then_some(handle.slot)
+    }
+
+    fn entry(&self, slot: usize) -> &Entry<P, T> {
+        self.slots[slot]
+            .entry
+            .as_ref()
+            .expect("slot in the heap has an entry")
+    }
+
+    fn remove_slot(&mut self, slot: usize) -> (P, T) {
+        let position = self.entry(slot).position;
+        let last = self.heap.len() - 1;
+        self.swap(position, last);
+        self.heap.pop();
+        if position < self.heap.len() {
+            let position = self.sift_up(position);
+            self.sift_down(position);
+        }
+
+        let removed = &mut self.slots[slot];
+        let entry = removed.entry.take().expect("slot in the heap has an entry");
+        removed.generation += 1;
+        self.free.push(slot);
+        (entry.priority, entry.value)
+    }
+
+    /// Whether the item at heap position `a` should come out before the one at `b`.
+    fn precedes(&self, a: usize, b: usize) -> bool {
+        let a = self.entry(self.heap[a]);
+        let b = self.entry(self.heap[b]);
+        match a.priority.cmp(&b.priority) {
+            Ordering::Greater => true,
+            Ordering::Less => false,
+            Ordering::Equal => a.sequence < b.sequence,
+        }
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        self.heap.swap(a, b);
+        let (slot_a, slot_b) = (self.heap[a], self.heap[b]);
+        self.slots[slot_a].entry.as_mut().unwrap().position = a;
+        self.slots[slot_b].entry.as_mut().unwrap().position = b;
+    }
+
+    fn sift_up(&mut self, mut position: usize) -> usize {
+        while position > 0 {
+            let parent = (position - 1) / 2;
+            if !self.precedes(position, parent) {
+                break;
+            }
+            self.swap(position, parent);
+            position = parent;
+        }
+        position
+    }
+
+    fn sift_down(&mut self, mut position: usize) {
+        loop {
+            let left = 2 * position + 1;
+            let right = left + 1;
+            let mut first = position;
+            if left < self.heap.len() && self.precedes(left, first) {
+                first = left;
+            }
+            if right < self.heap.len() && self.precedes(right, first) {
+                first = right;
+            }
+            if first == position {
+                break;
+            }
+            self.swap(position, first);
+            position = first;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drain<P: Ord, T>(queue: &mut IndexedPriorityQueue<P, T>) -> Vec<T> {
+        std::iter::from_fn(|| queue.pop().map(|(_, value)| value)).collect()
+    }
+
+    #[test]
+    fn pops_highest_priority_first() {
+        let mut queue = IndexedPriorityQueue::new();
+        queue.push(1, "A");
+        queue.push(3, "C");
+        queue.push(2, "B");
+        assert_eq!(queue.peek(), Some((&3, &"C")));
+        assert_eq!(drain(&mut queue), vec!["C", "B", "A"]);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn equal_priorities_are_fifo() {
+        let mut queue = IndexedPriorityQueue::new();
+        for value in ["first", "second", "third", "fourth"] {
+            queue.push(5, value);
+        }
+        queue.push(9, "urgent");
+        assert_eq!(
+            drain(&mut queue),
+            vec!["urgent", "first", "second", "third", "fourth"]
+        );
+    }
+
+    #[test]
+    fn change_priority_moves_items_both_ways() {
+        let mut queue = IndexedPriorityQueue::new();
+        let a = queue.push(1, 'a');
+        let b = queue.push(2, 'b');
+        let c = queue.push(3, 'c');
+
+        assert_eq!(queue.change_priority(a, 10), Some(1));
+        assert_eq!(queue.peek(), Some((&10, &'a')));
+
+        // Decrease-key: drop `a` below everything else.
+        assert_eq!(queue.change_priority(a, 0), Some(10));
+        assert_eq!(queue.change_priority(c, 2), Some(3));
+        assert_eq!(queue.get(b), Some((&2, &'b')));
+
+        // `b` and `c` now share a priority, and `b` was pushed first.
+        assert_eq!(drain(&mut queue), vec!['b', 'c', 'a']);
+    }
+
+    #[test]
+    fn remove_by_handle() {
+        let mut queue = IndexedPriorityQueue::new();
+        let handles: Vec<Handle> = (0..10).map(|i| queue.push(i % 4, i)).collect();
+        assert_eq!(queue.remove(handles[7]), Some((3, 7)));
+        assert_eq!(queue.remove(handles[0]), Some((0, 0)));
+        assert_eq!(queue.len(), 8);
+        assert_eq!(drain(&mut queue), vec![3, 2, 6, 1, 5, 9, 4, 8]);
+    }
+
+    #[test]
+    fn stale_handles_are_rejected() {
+        let mut queue = IndexedPriorityQueue::new();
+        let old = queue.push(1, "old");
+        assert_eq!(queue.pop(), Some((1, "old")));
+
+        // The new item reuses the slot of the old one.
+        let new = queue.push(2, "new");
+        assert!(!queue.contains(old));
+        assert_eq!(queue.remove(old), None);
+        assert_eq!(queue.change_priority(old, 7), None);
+        assert_eq!(queue.get(new), Some((&2, &"new")));
+    }
+
+    #[test]
+    fn matches_a_sorted_reference_under_random_operations() {
+        // Deterministic pseudo-random operations, checked against a stable sort.
+        let mut state: u64 = 12345;
+        let mut next = move || {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1);
+            (state >> 33) as usize
+        };
+
+        let mut queue = IndexedPriorityQueue::new();
+        let mut reference: Vec<(usize, u64, Handle)> = Vec::new();
+        for sequence in 0..2_000u64 {
+            match next() % 4 {
+                0 | 1 => {
+                    let priority = next() % 20;
+                    let handle = queue.push(priority, sequence);
+                    reference.push((priority, sequence, handle));
+                }
+                2 if !reference.is_empty() => {
+                    let index = next() % reference.len();
+                    let priority = next() % 20;
+                    queue.change_priority(reference[index].2, priority);
+                    reference[index].0 = priority;
+                }
+                _ if !reference.is_empty() => {
+                    let index = next() % reference.len();
+                    let (_, sequence, handle) = reference.swap_remove(index);
+                    assert_eq!(queue.remove(handle).map(|(_, value)| value), Some(sequence));
+                }
+                _ => {}
+            }
+        }
+
+        reference.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+        let expected: Vec<u64> = reference.iter().map(|(_, sequence, _)| *sequence).collect();
+        assert_eq!(drain(&mut queue), expected);
+    }
+}
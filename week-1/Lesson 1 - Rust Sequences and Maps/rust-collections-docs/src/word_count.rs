@@ -0,0 +1,241 @@
+//! Word and n-gram counting with text normalisation.
+//!
+//! Text goes through the following steps before it is counted:
+//!
+//! 1. [`tokenize`]: split on whitespace and on any punctuation that is not word-internal, using the
+//!    Unicode definition of alphanumeric characters.
+//! 2. [`strip_punctuation`]: trim apostrophes and hyphens left at the edges of a token.
+//! 3. [`fold_case`]: lowercase with the Unicode case mapping, so "Text" and "text" are one word.
+//! 4. Optional stopword removal.
+//!
+//! The resulting sequence of words is then counted as single words or as n-grams.
+
+use std::collections::{HashMap, HashSet};
+use std::io::{self, Read};
+
+/// Characters that may appear inside a word, as in "don't" or "well-known".
+const CONNECTORS: [char; 3] = ['\'', '\u{2019}', '-'];
+
+/// A small list of common English stopwords.
+pub const ENGLISH_STOPWORDS: [&str; 48] = [
+    "a", "an", "and", "are", "as", "at", "be", "but", "by", "for", "from", "had", "has", "have",
+    "he", "her", "his", "i", "in", "is", "it", "its", "of", "on", "or", "our", "she", "so", "that",
+    "the", "their", "them", "there", "they", "this", "to", "was", "we", "were", "what", "when",
+    "which", "who", "will", "with", "you", "your", "not",
+];
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || CONNECTORS.contains(&c)
+}
+
+/// Splits `text` into raw tokens. Anything that is neither alphanumeric nor a connector separates
+/// tokens, so "text." yields "text" and "a—b" yields "a" and "b".
+pub fn tokenize(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| !is_word_char(c))
+        .filter(|token| !token.is_empty())
+}
+
+/// Removes connectors from both ends of a token, e.g. "'quoted'" becomes "quoted".
+pub fn strip_punctuation(token: &str) -> &str {
+    token.trim_matches(|c: char| CONNECTORS.contains(&c))
+}
+
+/// Lowercases a token using the Unicode case mapping.
+pub fn fold_case(token: &str) -> String {
+    token.to_lowercase()
+}
+
+#[derive(Debug, Clone)]
+pub struct WordCounter {
+    stopwords: HashSet<String>,
+    ngram: usize,
+}
+
+impl Default for WordCounter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WordCounter {
+    /// Counts single words and keeps stopwords.
+    pub fn new() -> Self {
+        Self {
+            stopwords: HashSet::new(),
+            ngram: 1,
+        }
+    }
+
+    /// Removes the given words (after case folding) before counting.
+    pub fn with_stopwords<I, S>(mut self, stopwords: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.stopwords
+            .extend(stopwords.into_iter().map(|word| fold_case(word.as_ref())));
+        self
+    }
+
+    /// Removes [`ENGLISH_STOPWORDS`] before counting.
+    pub fn with_english_stopwords(self) -> Self {
+        self.with_stopwords(ENGLISH_STOPWORDS)
+    }
+
+    /// Counts sequences of `n` consecutive words instead of single words.
+    pub fn with_ngram(mut self, n: usize) -> Self {
+        assert!(n > 0, "n-gram size must be greater than zero");
+        self.ngram = n;
+        self
+    }
+
+    /// Runs every normalisation step and returns the words in order.
+    pub fn normalize(&self, text: &str) -> Vec<String> {
+        tokenize(text)
+            .map(strip_punctuation)
+            .filter(|token| !token.is_empty())
+            .map(fold_case)
+            .filter(|word| !self.stopwords.contains(word))
+            .collect()
+    }
+
+    /// Counts words, or n-grams joined by a single space.
+    pub fn count(&self, text: &str) -> HashMap<String, u32> {
+        let mut counts = HashMap::new();
+        self.count_into(text, &mut counts);
+        counts
+    }
+
+    /// Adds the counts of `text` to `counts`, so several documents can share one table.
+    /// N-grams never span two documents.
+    pub fn count_into(&self, text: &str, counts: &mut HashMap<String, u32>) {
+        let words = self.normalize(text);
+        for gram in words.windows(self.ngram) {
+            *counts.entry(gram.join(" ")).or_insert(0) += 1;
+        }
+    }
+
+    /// Counts everything readable from `reader`, e.g. a file or stdin.
+    pub fn count_reader<R: Read>(&self, mut reader: R) -> io::Result<HashMap<String, u32>> {
+        let mut text = String::new();
+        reader.read_to_string(&mut text)?;
+        Ok(self.count(&text))
+    }
+}
+
+/// The `n` most frequent entries, ties broken alphabetically.
+pub fn top_n(counts: &HashMap<String, u32>, n: usize) -> Vec<(&str, u32)> {
+    let mut entries: Vec<(&str, u32)> = counts
+        .iter()
+        .map(|(word, &count)| (word.as_str(), count))
+        .collect();
+    entries.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    entries.truncate(n);
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokenize_splits_on_punctuation_and_whitespace() {
+        let tokens: Vec<&str> = tokenize("Hello, world! text.\ttext—again (x)").collect();
+        assert_eq!(tokens, vec!["Hello", "world", "text", "text", "again", "x"]);
+    }
+
+    #[test]
+    fn tokenize_keeps_word_internal_connectors() {
+        let tokens: Vec<&str> = tokenize("don't stop — well-known l’été").collect();
+        assert_eq!(tokens, vec!["don't", "stop", "well-known", "l’été"]);
+    }
+
+    #[test]
+    fn tokenize_is_unicode_aware() {
+        let tokens: Vec<&str> = tokenize("Größe «naïve» 東京、大阪 café").collect();
+        assert_eq!(tokens, vec!["Größe", "naïve", "東京", "大阪", "café"]);
+    }
+
+    #[test]
+    fn strip_punctuation_trims_edges_only() {
+        assert_eq!(strip_punctuation("'quoted'"), "quoted");
+        assert_eq!(strip_punctuation("-dash-"), "dash");
+        assert_eq!(strip_punctuation("rock'n'roll"), "rock'n'roll");
+        assert_eq!(strip_punctuation("--"), "");
+    }
+
+    #[test]
+    fn fold_case_lowercases_unicode() {
+        assert_eq!(fold_case("Text"), "text");
+        assert_eq!(fold_case("ÉCOLE"), "école");
+        assert_eq!(fold_case("ΣΟΦΙΑ"), "σοφια");
+    }
+
+    #[test]
+    fn normalize_drops_empty_tokens_and_stopwords() {
+        let counter = WordCounter::new().with_english_stopwords();
+        assert_eq!(
+            counter.normalize("This is THE text -- 'text'."),
+            vec!["text", "text"]
+        );
+    }
+
+    #[test]
+    fn custom_stopwords_are_case_folded() {
+        let counter = WordCounter::new().with_stopwords(["Sample"]);
+        assert_eq!(counter.normalize("a SAMPLE text"), vec!["a", "text"]);
+    }
+
+    #[test]
+    fn counts_words_after_normalisation() {
+        let counts = WordCounter::new()
+            .count("Hello, world! This is a sample text. This is another sample text.");
+        assert_eq!(counts["text"], 2);
+        assert_eq!(counts["this"], 2);
+        assert_eq!(counts["hello"], 1);
+        assert!(!counts.contains_key("text."));
+    }
+
+    #[test]
+    fn counts_bigrams_and_trigrams() {
+        let text = "the quick fox, the quick dog";
+        let bigrams = WordCounter::new().with_ngram(2).count(text);
+        assert_eq!(bigrams["the quick"], 2);
+        assert_eq!(bigrams["fox the"], 1);
+        assert_eq!(bigrams.len(), 4);
+
+        let trigrams = WordCounter::new().with_ngram(3).count(text);
+        assert_eq!(trigrams["the quick fox"], 1);
+        assert_eq!(trigrams.len(), 4);
+    }
+
+    #[test]
+    fn ngrams_longer_than_text_count_nothing() {
+        assert!(WordCounter::new()
+            .with_ngram(3)
+            .count("too short")
+            .is_empty());
+    }
+
+    #[test]
+    fn ngrams_do_not_span_documents() {
+        let counter = WordCounter::new().with_ngram(2);
+        let mut counts = HashMap::new();
+        counter.count_into("one two", &mut counts);
+        counter.count_into("three four", &mut counts);
+        assert!(!counts.contains_key("two three"));
+        assert_eq!(counts.len(), 2);
+    }
+
+    #[test]
+    fn counts_from_reader() {
+        let counts = WordCounter::new().count_reader("b a b".as_bytes()).unwrap();
+        assert_eq!(top_n(&counts, 1), vec![("b", 2)]);
+    }
+
+    #[test]
+    fn top_n_breaks_ties_alphabetically() {
+        let counts = WordCounter::new().count("c b a b c d");
+        assert_eq!(top_n(&counts, 3), vec![("b", 2), ("c", 2), ("a", 1)]);
+    }
+}
@@ -0,0 +1,7 @@
+//! Reusable `VecDeque`-based building blocks for the fruit salad pipeline in `main.rs`.
+
+pub mod ring_queue;
+pub mod window;
+
+pub use ring_queue::{Closed, OverflowPolicy, RingQueue};
+pub use window::SlidingWindow;
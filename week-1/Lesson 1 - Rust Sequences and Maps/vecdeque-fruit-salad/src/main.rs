@@ -1,37 +1,52 @@
-///! # What is a VecDeque in Rust and how is it different from a Vector or LinkedList?
-///! In Rust, VecDeque is a double-ended queue, which is a data structure that allows 
-///! insertion and deletion at both ends of the queue. It is different from a Vector 
-///! (or Vec in Rust) in that a Vec only allows efficient insertion and deletion at the 
-///! end of the vector, while a LinkedList allows efficient insertion and deletion at both ends, 
-///! but it does not provide random access to elements like a Vec does. VecDeque provides a 
-///! combination of the capabilities of both Vec and LinkedList, allowing efficient insertion and
-///! deletion at both ends as well as random access to elements.
-///!
-///! # What is the significance of converting VecDeque to a Vector and then back to VecDeque in the program?
-///! Converting VecDeque to a Vector and then back to VecDeque in the program allows 
-///! shuffling the elements using the shuffle method, which is available for the 
-///! standard library Vec. After shuffling, the elements are converted back to 
-///! VecDeque to maintain the double-ended queue properties, allowing further 
-///! operations on both ends of the queue.
-///!
-///! # Why do we push "Pomegranate" to the front of the queue and "Fig" and "Cherry" to the back of the queue after shuffling?
-///! Pushing "Pomegranate" to the front of the queue and "Fig" and "Cherry" to the back of 
-///! the queue after shuffling is done to demonstrate the double-ended queue properties of 
-///! VecDeque. This illustrates how elements can be efficiently added to both ends of the 
-///! queue, showcasing the flexibility of VecDeque as a double-ended queue.
+//! # What is a VecDeque in Rust and how is it different from a Vector or LinkedList?
+//! In Rust, VecDeque is a double-ended queue, which is a data structure that allows 
+//! insertion and deletion at both ends of the queue. It is different from a Vector 
+//! (or Vec in Rust) in that a Vec only allows efficient insertion and deletion at the 
+//! end of the vector, while a LinkedList allows efficient insertion and deletion at both ends, 
+//! but it does not provide random access to elements like a Vec does. VecDeque provides a 
+//! combination of the capabilities of both Vec and LinkedList, allowing efficient insertion and
+//! deletion at both ends as well as random access to elements.
+//!
+//! # What is the significance of converting VecDeque to a Vector and then back to VecDeque in the program?
+//! Converting VecDeque to a Vector and then back to VecDeque in the program allows 
+//! shuffling the elements using the shuffle method, which is available for the 
+//! standard library Vec. After shuffling, the elements are converted back to 
+//! VecDeque to maintain the double-ended queue properties, allowing further 
+//! operations on both ends of the queue.
+//!
+//! # Why do we push one fruit to the front of the queue and two to the back of the queue after shuffling?
+//! Pushing one fruit to the front of the queue and two more to the back of 
+//! the queue after shuffling is done to demonstrate the double-ended queue properties of 
+//! VecDeque. This illustrates how elements can be efficiently added to both ends of the 
+//! queue, showcasing the flexibility of VecDeque as a double-ended queue.
 
+use fruit_catalogue::{sample_without_replacement, FRUITS};
 use rand::seq::SliceRandom; // rand is a random number generation library in Rust
-use rand::thread_rng;
+use rand::{thread_rng, Rng};
 use std::collections::VecDeque;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use vecdeque_fruit_salad::{OverflowPolicy, RingQueue, SlidingWindow};
+
+/// An order streamed from the producer thread to the consumer thread.
+#[derive(Debug)]
+struct Order {
+    id: u32,
+    fruit: &'static str,
+    quantity: u32,
+}
 
 fn main() {
-    let mut fruit: VecDeque<&str> = VecDeque::new();
-    fruit.push_back("Arbutus");
-    fruit.push_back("Loquat");
-    fruit.push_back("Strawberry Tree Berry");
+    // Pick six different fruits from the catalogue: three start the queue and the other three
+    // are added to its ends after shuffling
+    let mut rng = thread_rng();
+    let picks = sample_without_replacement(&FRUITS, 6, &mut rng)
+        .expect("the catalogue has at least six fruits");
+    let (start, ends) = picks.split_at(3);
+    let fruit: VecDeque<&str> = start.iter().copied().collect();
 
     // Scramble (shuffle) the fruit
-    let mut rng = thread_rng();
     let mut fruit: Vec<_> = fruit.into_iter().collect();
     fruit.shuffle(&mut rng);
 
@@ -39,9 +54,9 @@ fn main() {
     let mut fruit: VecDeque<_> = fruit.into_iter().collect();
 
     // Add fruits to the both ends of the queue after shuffling
-    fruit.push_front("Pomegranate");
-    fruit.push_back("Fig");
-    fruit.push_back("Cherry");
+    fruit.push_front(ends[0]);
+    fruit.push_back(ends[1]);
+    fruit.push_back(ends[2]);
 
     // Print out the fruit salad
     print_fruit_salad(&fruit);
@@ -56,6 +71,58 @@ fn main() {
 
     // Print out the fruit salad again
     print_fruit_salad(&fruit);
+
+    println!();
+    stream_orders(OverflowPolicy::Block);
+    println!();
+    stream_orders(OverflowPolicy::OverwriteOldest);
+}
+
+/// A producer thread streams fruit orders through a small `RingQueue` to a slower consumer, which
+/// keeps rolling statistics over the quantities of the last five orders it received.
+fn stream_orders(policy: OverflowPolicy) {
+    const ORDERS: u32 = 12;
+    let queue = Arc::new(RingQueue::new(3, policy));
+    println!("Streaming {} orders through a queue of 3 ({:?}):", ORDERS, policy);
+
+    let producer = {
+        let queue = Arc::clone(&queue);
+        thread::spawn(move || {
+            let mut rng = thread_rng();
+            for id in 1..=ORDERS {
+                let order = Order {
+                    id,
+                    fruit: fruit_catalogue::choose(&FRUITS, &mut rng)
+                        .copied()
+                        .expect("the catalogue is not empty"),
+                    quantity: rng.gen_range(1..=10),
+                };
+                if let Ok(Some(dropped)) = queue.push(order) {
+                    println!("  dropped order #{} ({})", dropped.id, dropped.fruit);
+                }
+            }
+            queue.close();
+        })
+    };
+
+    let mut window = SlidingWindow::new(5);
+    while let Some(order) = queue.pop() {
+        // The consumer is slower than the producer, so the queue fills up.
+        thread::sleep(Duration::from_millis(10));
+        window.push(order.quantity as f64);
+        println!(
+            "  order #{:>2}: {:>2} x {:<12} last {}: mean {:.1}, min {}, max {}",
+            order.id,
+            order.quantity,
+            order.fruit,
+            window.len(),
+            window.mean().unwrap_or(0.0),
+            window.min().unwrap_or(0.0),
+            window.max().unwrap_or(0.0),
+        );
+    }
+    producer.join().expect("producer thread panicked");
+    println!("Orders dropped: {}", queue.overwritten());
 }
 
 fn print_fruit_salad(fruit: &VecDeque<&str>) {
@@ -0,0 +1,267 @@
+//! A bounded queue for passing items between threads, built on a `VecDeque`.
+//!
+//! The `VecDeque` is allocated once with the queue's capacity and never grows. What happens when
+//! a producer pushes into a full queue is decided by the [`OverflowPolicy`]:
+//!
+//! - [`OverflowPolicy::OverwriteOldest`] drops the oldest item to make room, so the producer never
+//!   waits and consumers always see the most recent items.
+//! - [`OverflowPolicy::Block`] makes the producer wait until a consumer has popped an item, which
+//!   slows a fast producer down to the consumer's pace (backpressure).
+//!
+//! Share a queue between threads with an `Arc`. Closing it wakes every waiting thread: producers
+//! get their item back and consumers drain what is left, then receive `None`.
+
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+use std::sync::{Condvar, Mutex, MutexGuard};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Drop the oldest item when the queue is full.
+    OverwriteOldest,
+    /// Wait until there is room.
+    Block,
+}
+
+/// Returned by [`RingQueue::push`] when the queue has been closed, with the rejected item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Closed<T>(pub T);
+
+impl<T> fmt::Display for Closed<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the queue is closed")
+    }
+}
+
+impl<T: fmt::Debug> Error for Closed<T> {}
+
+#[derive(Debug)]
+struct State<T> {
+    items: VecDeque<T>,
+    closed: bool,
+    overwritten: u64,
+}
+
+#[derive(Debug)]
+pub struct RingQueue<T> {
+    capacity: usize,
+    policy: OverflowPolicy,
+    state: Mutex<State<T>>,
+    not_empty: Condvar,
+    not_full: Condvar,
+}
+
+impl<T> RingQueue<T> {
+    /// Creates an empty queue holding at most `capacity` items.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn new(capacity: usize, policy: OverflowPolicy) -> Self {
+        assert!(capacity > 0, "capacity must be greater than zero");
+        Self {
+            capacity,
+            policy,
+            state: Mutex::new(State {
+                items: VecDeque::with_capacity(capacity),
+                closed: false,
+                overwritten: 0,
+            }),
+            not_empty: Condvar::new(),
+            not_full: Condvar::new(),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn policy(&self) -> OverflowPolicy {
+        self.policy
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().items.is_empty()
+    }
+
+    /// How many items [`OverflowPolicy::OverwriteOldest`] has dropped so far.
+    pub fn overwritten(&self) -> u64 {
+        self.lock().overwritten
+    }
+
+    /// Adds `item` at the back. With [`OverflowPolicy::OverwriteOldest`] a full queue drops its
+    /// oldest item and returns it; with [`OverflowPolicy::Block`] this waits for room instead.
+    pub fn push(&self, item: T) -> Result<Option<T>, Closed<T>> {
+        let mut state = self.lock();
+        if self.policy == OverflowPolicy::Block {
+            state = self
+                .not_full
+                .wait_while(state, |state| {
+                    !state.closed && state.items.len() == self.capacity
+                })
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+        }
+        if state.closed {
+            return Err(Closed(item));
+        }
+
+        let dropped = if state.items.len() == self.capacity {
+            state.overwritten += 1;
+            state.items.pop_front()
+        } else {
+            None
+        };
+        state.items.push_back(item);
+        drop(state);
+        self.not_empty.notify_one();
+        Ok(dropped)
+    }
+
+    /// Removes the oldest item, waiting for one if the queue is empty. Returns `None` once the
+    /// queue is closed and drained.
+    pub fn pop(&self) -> Option<T> {
+        let state = self.lock();
+        let mut state = self
+            .not_empty
+            .wait_while(state, |state| !state.closed && state.items.is_empty())
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let item = state.items.pop_front();
+        drop(state);
+        if item.is_some() {
+            self.not_full.notify_one();
+        }
+        item
+    }
+
+    /// Removes the oldest item without waiting.
+    pub fn try_pop(&self) -> Option<T> {
+        let item = self.lock().items.pop_front();
+        if item.is_some() {
+            self.not_full.notify_one();
+        }
+        item
+    }
+
+    /// Stops accepting new items and wakes every waiting producer and consumer.
+    pub fn close(&self) {
+        self.lock().closed = true;
+        self.not_empty.notify_all();
+        self.not_full.notify_all();
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.lock().closed
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        // A panic while holding the lock cannot leave the deque half-updated, so keep going.
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn overwrite_oldest_keeps_the_newest_items() {
+        let queue = RingQueue::new(3, OverflowPolicy::OverwriteOldest);
+        for i in 0..3 {
+            assert_eq!(queue.push(i), Ok(None));
+        }
+        assert_eq!(queue.push(3), Ok(Some(0)));
+        assert_eq!(queue.push(4), Ok(Some(1)));
+        assert_eq!(queue.len(), 3);
+        assert_eq!(queue.overwritten(), 2);
+
+        let drained: Vec<i32> = std::iter::from_fn(|| queue.try_pop()).collect();
+        assert_eq!(drained, vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn block_waits_for_a_consumer() {
+        let queue = Arc::new(RingQueue::new(2, OverflowPolicy::Block));
+        queue.push(1).unwrap();
+        queue.push(2).unwrap();
+
+        let producer = {
+            let queue = Arc::clone(&queue);
+            thread::spawn(move || queue.push(3))
+        };
+        thread::sleep(Duration::from_millis(50));
+        assert_eq!(
+            queue.len(),
+            2,
+            "the producer must wait while the queue is full"
+        );
+
+        assert_eq!(queue.pop(), Some(1));
+        assert_eq!(producer.join().unwrap(), Ok(None));
+        assert_eq!(queue.pop(), Some(2));
+        assert_eq!(queue.pop(), Some(3));
+        assert_eq!(queue.overwritten(), 0);
+    }
+
+    #[test]
+    fn close_wakes_blocked_threads() {
+        let queue = Arc::new(RingQueue::new(1, OverflowPolicy::Block));
+        queue.push("kept").unwrap();
+
+        let producer = {
+            let queue = Arc::clone(&queue);
+            thread::spawn(move || queue.push("rejected"))
+        };
+        thread::sleep(Duration::from_millis(50));
+        queue.close();
+        assert_eq!(producer.join().unwrap(), Err(Closed("rejected")));
+
+        // Consumers still drain what was queued before the close.
+        assert_eq!(queue.pop(), Some("kept"));
+        assert_eq!(queue.pop(), None);
+    }
+
+    #[test]
+    fn pop_waits_for_a_producer() {
+        let queue = Arc::new(RingQueue::new(4, OverflowPolicy::Block));
+        let consumer = {
+            let queue = Arc::clone(&queue);
+            thread::spawn(move || queue.pop())
+        };
+        thread::sleep(Duration::from_millis(20));
+        queue.push(7).unwrap();
+        assert_eq!(consumer.join().unwrap(), Some(7));
+    }
+
+    #[test]
+    fn every_item_arrives_once_and_in_order() {
+        let queue = Arc::new(RingQueue::new(8, OverflowPolicy::Block));
+        let producer = {
+            let queue = Arc::clone(&queue);
+            thread::spawn(move || {
+                for i in 0..10_000 {
+                    queue.push(i).unwrap();
+                }
+                queue.close();
+            })
+        };
+        let received: Vec<i32> = std::iter::from_fn(|| queue.pop()).collect();
+        producer.join().unwrap();
+        assert_eq!(received, (0..10_000).collect::<Vec<_>>());
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity")]
+    fn rejects_zero_capacity() {
+        RingQueue::<()>::new(0, OverflowPolicy::Block);
+    }
+}
@@ -0,0 +1,184 @@
+//! Rolling mean, minimum and maximum over the last `N` values.
+//!
+//! The values themselves live in a `VecDeque` used as a fixed-size ring. The minimum and maximum
+//! come from two monotonic deques: each keeps only the values that can still become the extreme
+//! once older values leave the window, so every push costs amortised O(1) and every query O(1).
+
+use std::collections::VecDeque;
+
+#[derive(Debug, Clone)]
+pub struct SlidingWindow {
+    size: usize,
+    values: VecDeque<f64>,
+    sum: f64,
+    /// Sequence number of the next value, used to tell when an extreme has left the window.
+    next: u64,
+    /// `(sequence, value)` pairs with increasing values; the front is the minimum.
+    minima: VecDeque<(u64, f64)>,
+    /// `(sequence, value)` pairs with decreasing values; the front is the maximum.
+    maxima: VecDeque<(u64, f64)>,
+}
+
+impl SlidingWindow {
+    /// Creates a window over the last `size` values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    pub fn new(size: usize) -> Self {
+        assert!(size > 0, "window size must be greater than zero");
+        Self {
+            size,
+            values: VecDeque::with_capacity(size),
+            sum: 0.0,
+            next: 0,
+            minima: VecDeque::new(),
+            maxima: VecDeque::new(),
+        }
+    }
+
+    /// Adds `value`, evicting the oldest value once the window is full. NaN is ignored.
+    pub fn push(&mut self, value: f64) {
+        if value.is_nan() {
+            return;
+        }
+        if self.values.len() == self.size {
+            if let Some(oldest) = self.values.pop_front() {
+                self.sum -= oldest;
+            }
+        }
+        self.values.push_back(value);
+        self.sum += value;
+
+        let sequence = self.next;
+        self.next += 1;
+        let first_in_window = self.next - self.values.len() as u64;
+
+        while matches!(self.minima.back(), Some(&(_, back)) if back >= value) {
+            self.minima.pop_back();
+        }
+        self.minima.push_back((sequence, value));
+        while matches!(self.minima.front(), Some(&(seq, _)) if seq < first_in_window) {
+            self.minima.pop_front();
+        }
+
+        while matches!(self.maxima.back(), Some(&(_, back)) if back <= value) {
+            self.maxima.pop_back();
+        }
+        self.maxima.push_back((sequence, value));
+        while matches!(self.maxima.front(), Some(&(seq, _)) if seq < first_in_window) {
+            self.maxima.pop_front();
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.values.len() == self.size
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        (!self.is_empty()).then(|| self.sum / self.values.len() as f64)
+    }
+
+    pub fn min(&self) -> Option<f64> {
+        self.minima.front().map(|&(_, value)| value)
+    }
+
+    pub fn max(&self) -> Option<f64> {
+        self.maxima.front().map(|&(_, value)| value)
+    }
+
+    /// The values in the window, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &f64> {
+        self.values.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn empty_window_has_no_statistics() {
+        let window = SlidingWindow::new(3);
+        assert_eq!(window.mean(), None);
+        assert_eq!(window.min(), None);
+        assert_eq!(window.max(), None);
+    }
+
+    #[test]
+    fn tracks_the_last_n_values() {
+        let mut window = SlidingWindow::new(3);
+        for value in [4.0, 1.0, 7.0] {
+            window.push(value);
+        }
+        assert!(window.is_full());
+        assert_eq!(window.mean(), Some(4.0));
+        assert_eq!(window.min(), Some(1.0));
+        assert_eq!(window.max(), Some(7.0));
+
+        // 4 and then 1 leave the window.
+        window.push(2.0);
+        window.push(3.0);
+        assert_eq!(
+            window.iter().copied().collect::<Vec<_>>(),
+            vec![7.0, 2.0, 3.0]
+        );
+        assert_eq!(window.mean(), Some(4.0));
+        assert_eq!(window.min(), Some(2.0));
+        assert_eq!(window.max(), Some(7.0));
+
+        window.push(1.0);
+        assert_eq!(window.max(), Some(3.0));
+        assert_eq!(window.min(), Some(1.0));
+    }
+
+    #[test]
+    fn handles_repeated_extremes() {
+        let mut window = SlidingWindow::new(2);
+        for value in [5.0, 5.0, 1.0] {
+            window.push(value);
+        }
+        assert_eq!(window.max(), Some(5.0));
+        window.push(1.0);
+        assert_eq!(window.max(), Some(1.0));
+    }
+
+    #[test]
+    fn ignores_nan() {
+        let mut window = SlidingWindow::new(2);
+        window.push(1.0);
+        window.push(f64::NAN);
+        assert_eq!(window.len(), 1);
+        assert_eq!(window.mean(), Some(1.0));
+    }
+
+    #[test]
+    fn matches_a_brute_force_window() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut window = SlidingWindow::new(5);
+        let mut history = Vec::new();
+        for _ in 0..500 {
+            let value = rng.gen_range(0.0..1000.0);
+            window.push(value);
+            history.push(value);
+
+            let recent = &history[history.len().saturating_sub(5)..];
+            let min = recent.iter().copied().fold(f64::INFINITY, f64::min);
+            let max = recent.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            let mean = recent.iter().sum::<f64>() / recent.len() as f64;
+            assert_eq!(window.min(), Some(min));
+            assert_eq!(window.max(), Some(max));
+            assert!((window.mean().unwrap() - mean).abs() < 1e-6);
+        }
+    }
+}
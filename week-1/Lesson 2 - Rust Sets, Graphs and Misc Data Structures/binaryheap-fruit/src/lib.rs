@@ -0,0 +1,13 @@
+//! Serving fruit by priority with a `BinaryHeap`.
+//!
+//! [`rules::PriorityRules`] decide which fruit comes first and are usually loaded from a TOML file.
+//! [`scheduler::Scheduler`] serves items by any ordered key, including those rules, and
+//! [`scheduler::top_k`] picks the best few items without sorting everything. [`merge`] uses the
+//! same heap technique to merge sorted CSV files and to sort files larger than memory.
+
+pub mod merge;
+pub mod rules;
+pub mod scheduler;
+
+pub use rules::{Priority, PriorityRules, RestOrder};
+pub use scheduler::{top_k, top_k_by_key, Scheduler};
@@ -89,84 +89,172 @@
 //! complexity of operations like element addition, removal, and access to
 //! extremes.
 //!
+use binaryheap_fruit::merge::{self, SortLimits, SortOptions};
+use binaryheap_fruit::{top_k_by_key, PriorityRules, Scheduler};
+use clap::{Args, Parser, Subcommand};
+use fruit_catalogue::{sample_with_replacement, FRUITS};
 use rand::seq::SliceRandom;
-use rand::thread_rng;
-use std::cmp::Ord;
-use std::collections::BinaryHeap;
-
-#[derive(Eq, PartialEq, Debug)]
-enum Fruit {
-    Fig,
-    Other(String),
+use rand::{thread_rng, Rng};
+use std::error::Error;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[clap(
+    version = "1.0",
+    about = "Serve a fruit salad by priority, or merge and sort CSV files with a heap",
+    args_conflicts_with_subcommands = true
+)]
+struct Opts {
+    /// TOML file with the priority rules (default: figs first, then alphabetical)
+    #[clap(short, long)]
+    rules: Option<PathBuf>,
+    /// Number of top fruits to show before serving
+    #[clap(short, long, default_value_t = 3)]
+    top: usize,
+    #[clap(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Merge already sorted CSV files into one sorted output
+    Merge {
+        /// Sorted input files
+        #[clap(required = true)]
+        inputs: Vec<PathBuf>,
+        #[clap(flatten)]
+        csv: CsvArgs,
+    },
+    /// Sort a CSV file of any size by spilling sorted runs to temporary files
+    Sort {
+        input: PathBuf,
+        #[clap(flatten)]
+        csv: CsvArgs,
+        /// Rows sorted in memory at a time
+        #[clap(long, default_value_t = SortLimits::default().run_rows)]
+        run_rows: usize,
+        /// Runs merged at once
+        #[clap(long, default_value_t = SortLimits::default().fan_in)]
+        fan_in: usize,
+        /// Directory for the sorted runs (default: the system temporary directory)
+        #[clap(long)]
+        temp_dir: Option<PathBuf>,
+    },
+}
+
+#[derive(Args)]
+struct CsvArgs {
+    /// Column to sort by, counting from 0
+    #[clap(short, long, default_value_t = 0)]
+    key_column: usize,
+    /// Compare the key column as numbers
+    #[clap(short, long)]
+    numeric: bool,
+    /// The files have no header row
+    #[clap(long)]
+    no_headers: bool,
+    /// Output file (default: standard output)
+    #[clap(short, long)]
+    output: Option<PathBuf>,
 }
 
-// We define Figs as the highest priority by implementing Ord
-impl Ord for Fruit {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        match (self, other) {
-            (Fruit::Fig, Fruit::Fig) => std::cmp::Ordering::Equal,
-            (Fruit::Fig, Fruit::Other(_)) => std::cmp::Ordering::Greater,
-            (Fruit::Other(_), Fruit::Fig) => std::cmp::Ordering::Less,
-            (Fruit::Other(_), Fruit::Other(_)) => std::cmp::Ordering::Equal,
+impl CsvArgs {
+    fn options(&self) -> SortOptions {
+        SortOptions {
+            key_column: self.key_column,
+            numeric: self.numeric,
+            has_headers: !self.no_headers,
         }
     }
-}
 
-impl PartialOrd for Fruit {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
+    fn writer(&self) -> Result<Box<dyn Write>, Box<dyn Error>> {
+        Ok(match &self.output {
+            Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+            None => Box::new(BufWriter::new(io::stdout().lock())),
+        })
     }
 }
 
-fn generate_fruit_salad() -> BinaryHeap<Fruit> {
-    let mut rng = thread_rng();
-    let fruits = vec![
-        "Apple", "Orange", "Pear", "Peach", "Banana", "Fig", "Fig", "Fig", "Fig",
-    ];
-    let mut fruit_salad = BinaryHeap::new();
-
-    let mut figs_count = 0;
-    while figs_count < 2 {
-        let fruit = fruits.choose(&mut rng).unwrap();
-        if *fruit == "Fig" {
-            figs_count += 1;
-            fruit_salad.push(Fruit::Fig);
-        } else {
-            fruit_salad.push(Fruit::Other(fruit.to_string()));
+// Two servings of figs and five servings of other fruits from the catalogue, in random order.
+fn generate_fruit_salad<R: Rng + ?Sized>(rng: &mut R) -> Vec<&'static str> {
+    let others: Vec<&str> = FRUITS
+        .iter()
+        .copied()
+        .filter(|fruit| *fruit != "Fig")
+        .collect();
+    let mut fruits = sample_with_replacement(&others, 5, rng);
+    fruits.extend(["Fig"; 2]);
+    fruits.shuffle(rng);
+    fruits
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let opts: Opts = Opts::parse();
+
+    match opts.command {
+        Some(Command::Merge { inputs, csv }) => {
+            let readers = merge::open_all(&inputs)?;
+            let rows = merge::merge(readers, csv.writer()?, &csv.options())?;
+            eprintln!("Merged {} rows from {} files", rows, inputs.len());
+            Ok(())
+        }
+        Some(Command::Sort {
+            input,
+            csv,
+            run_rows,
+            fan_in,
+            temp_dir,
+        }) => {
+            let limits = SortLimits { run_rows, fan_in };
+            let temp_dir = temp_dir.unwrap_or_else(std::env::temp_dir);
+            let stats = merge::external_sort(
+                merge::open(&input)?,
+                csv.writer()?,
+                &csv.options(),
+                &limits,
+                &temp_dir,
+            )?;
+            eprintln!(
+                "Sorted {} rows in {} runs and {} merge passes",
+                stats.rows, stats.runs, stats.passes
+            );
+            Ok(())
         }
+        None => serve(opts.rules, opts.top),
     }
-
-    fruit_salad
 }
 
-fn main() {
-    let mut fruit_salad = generate_fruit_salad();
+fn serve(rules: Option<PathBuf>, top: usize) -> Result<(), Box<dyn Error>> {
+    let rules = match &rules {
+        Some(path) => PriorityRules::load(path)?,
+        None => PriorityRules::default(),
+    };
+
+    let fruits = generate_fruit_salad(&mut thread_rng());
     println!("Random Fruit Salad With Two Servings of Figs:");
+    let top_fruits = top_k_by_key(fruits.iter().copied(), top, |fruit| rules.priority(fruit));
+    println!("Top {}: {:?}", top, top_fruits);
+
+    let mut fruit_salad = Scheduler::with_rules(rules);
+    fruit_salad.extend(fruits);
 
     // Challenge(1): Ask the user how many fruits they would like to remove
-    let fruits_to_remove = ask_fruits_to_remove();
-    match fruits_to_remove {
-        Some(num) => {
-            for _ in 0..num {
-                let removed_fruit = fruit_salad.pop();
-                match removed_fruit {
-                    Some(_) => println!("Removed one {:?}", removed_fruit.unwrap()),
-                    None => {
-                        println!("No fruit removed.");
-                        break;
-                    }
-                }
-            }
+    if let Some(num) = ask_fruits_to_remove() {
+        let removed = fruit_salad.pop_batch(num);
+        for fruit in &removed {
+            println!("Removed one {}", fruit);
+        }
+        if removed.len() < num {
+            println!("No fruit removed.");
         }
-        None => {}
     }
 
     for fruit in fruit_salad.into_sorted_vec() {
-        match fruit {
-            Fruit::Fig => println!("Fig"),
-            Fruit::Other(fruit_name) => println!("{}", fruit_name),
-        }
+        println!("{}", fruit);
     }
+    Ok(())
 }
 
 // This functions list all the fruits and ask the user which one they want to eliminate, return the selected fruit by the user.
@@ -178,8 +266,5 @@ fn ask_fruits_to_remove() -> Option<usize> {
         .read_line(&mut input)
         .expect("Failed to read line");
 
-    match input.trim().parse() {
-        Ok(num) => Some(num),
-        Err(_) => None,
-    }
+    input.trim().parse().ok()
 }
@@ -0,0 +1,483 @@
+//! K-way merge of sorted CSV files, and an external sort built on top of it.
+//!
+//! [`merge`] keeps one cursor per input in a min-heap ordered by `(key, input index)`. Popping the
+//! heap yields the smallest row left in any input; the cursor then advances and goes back in. Only
+//! one row per input is ever held in memory, however large the inputs are, and rows with equal keys
+//! come out in input order, so the merge is stable.
+//!
+//! [`external_sort`] sorts a file that does not fit in memory: it cuts the input into runs of at
+//! most [`SortLimits::run_rows`] rows, sorts each run in memory and spills it to a temporary file,
+//! then merges the runs. When there are more runs than [`SortLimits::fan_in`], groups of runs are
+//! merged into longer runs first, so no more than `fan_in` files are open at once.
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+
+use csv::{ReaderBuilder, StringRecord, WriterBuilder};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SortOptions {
+    /// Column the rows are sorted by, counting from 0.
+    pub key_column: usize,
+    /// Compare keys as numbers instead of as text.
+    pub numeric: bool,
+    /// The first row of every input is a header. Headers must match and are written once.
+    pub has_headers: bool,
+}
+
+impl Default for SortOptions {
+    fn default() -> Self {
+        Self {
+            key_column: 0,
+            numeric: false,
+            has_headers: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SortLimits {
+    /// Rows sorted in memory at a time.
+    pub run_rows: usize,
+    /// Runs merged at once.
+    pub fan_in: usize,
+}
+
+impl Default for SortLimits {
+    fn default() -> Self {
+        Self {
+            run_rows: 100_000,
+            fan_in: 64,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SortStats {
+    pub rows: u64,
+    /// Sorted runs spilled to disk by the first pass.
+    pub runs: usize,
+    /// Merge passes, including the final one into the output.
+    pub passes: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum SortKey {
+    Text(String),
+    Number(f64),
+}
+
+impl Eq for SortKey {}
+
+impl Ord for SortKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (SortKey::Text(a), SortKey::Text(b)) => a.cmp(b),
+            (SortKey::Number(a), SortKey::Number(b)) => a.total_cmp(b),
+            // Every key of one sort is built with the same options, so this never happens.
+            (SortKey::Text(_), SortKey::Number(_)) => Ordering::Greater,
+            (SortKey::Number(_), SortKey::Text(_)) => Ordering::Less,
+        }
+    }
+}
+
+impl PartialOrd for SortKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl SortOptions {
+    fn key(&self, record: &StringRecord) -> Result<SortKey, String> {
+        let field = record.get(self.key_column).ok_or_else(|| {
+            format!(
+                "row has {} columns, there is no column {}",
+                record.len(),
+                self.key_column
+            )
+        })?;
+        if self.numeric {
+            field
+                .trim()
+                .parse()
+                .map(SortKey::Number)
+                .map_err(|_| format!("'{}' is not a number", field))
+        } else {
+            Ok(SortKey::Text(field.to_string()))
+        }
+    }
+}
+
+/// The next row of one input, waiting in the heap.
+struct Head {
+    key: SortKey,
+    input: usize,
+    record: StringRecord,
+}
+
+impl PartialEq for Head {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Head {}
+
+impl Ord for Head {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key
+            .cmp(&other.key)
+            .then_with(|| self.input.cmp(&other.input))
+    }
+}
+
+impl PartialOrd for Head {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+struct Cursor<R: Read> {
+    input: usize,
+    reader: csv::Reader<R>,
+    row: u64,
+    last_key: Option<SortKey>,
+}
+
+impl<R: Read> Cursor<R> {
+    /// Reads the next row and checks that the input is still sorted.
+    fn next(&mut self, options: &SortOptions) -> Result<Option<Head>, Box<dyn Error>> {
+        let mut record = StringRecord::new();
+        if !self.reader.read_record(&mut record)? {
+            return Ok(None);
+        }
+        self.row += 1;
+        let context =
+            |message: String| format!("input {}, row {}: {}", self.input, self.row, message);
+        let key = options.key(&record).map_err(context)?;
+        if matches!(&self.last_key, Some(last) if *last > key) {
+            return Err(context("the input is not sorted".to_string()).into());
+        }
+        self.last_key = Some(key.clone());
+        Ok(Some(Head {
+            key,
+            input: self.input,
+            record,
+        }))
+    }
+}
+
+/// Merges already sorted CSV `inputs` into one sorted `output` and returns the number of rows
+/// written. Fails if an input turns out not to be sorted, or if the inputs' headers differ.
+pub fn merge<R: Read, W: Write>(
+    inputs: Vec<R>,
+    output: W,
+    options: &SortOptions,
+) -> Result<u64, Box<dyn Error>> {
+    let mut cursors: Vec<Cursor<R>> = inputs
+        .into_iter()
+        .enumerate()
+        .map(|(input, reader)| Cursor {
+            input,
+            reader: ReaderBuilder::new()
+                .has_headers(options.has_headers)
+                .from_reader(reader),
+            row: 0,
+            last_key: None,
+        })
+        .collect();
+    let mut writer = WriterBuilder::new().from_writer(output);
+
+    if options.has_headers {
+        let mut expected: Option<StringRecord> = None;
+        for cursor in &mut cursors {
+            let headers = cursor.reader.headers()?.clone();
+            match &expected {
+                Some(expected) if *expected != headers => {
+                    return Err(format!(
+                        "input {} has headers {:?}, expected {:?}",
+                        cursor.input, headers, expected
+                    )
+                    .into())
+                }
+                Some(_) => {}
+                None => expected = Some(headers),
+            }
+        }
+        if let Some(headers) = expected {
+            writer.write_record(&headers)?;
+        }
+    }
+
+    let mut heap = BinaryHeap::with_capacity(cursors.len());
+    for cursor in &mut cursors {
+        if let Some(head) = cursor.next(options)? {
+            heap.push(Reverse(head));
+        }
+    }
+
+    let mut rows = 0;
+    while let Some(Reverse(head)) = heap.pop() {
+        writer.write_record(&head.record)?;
+        rows += 1;
+        if let Some(next) = cursors[head.input].next(options)? {
+            heap.push(Reverse(next));
+        }
+    }
+    writer.flush()?;
+    Ok(rows)
+}
+
+/// Merges the sorted CSV files at `paths` into the file at `output`.
+pub fn merge_files(
+    paths: &[PathBuf],
+    output: &Path,
+    options: &SortOptions,
+) -> Result<u64, Box<dyn Error>> {
+    let inputs = open_all(paths)?;
+    merge(inputs, BufWriter::new(File::create(output)?), options)
+}
+
+/// Opens every path for reading; the error names the file that could not be opened.
+pub fn open_all(paths: &[PathBuf]) -> Result<Vec<BufReader<File>>, Box<dyn Error>> {
+    paths.iter().map(|path| open(path)).collect()
+}
+
+/// Opens `path` for reading; the error names the file.
+pub fn open(path: &Path) -> Result<BufReader<File>, Box<dyn Error>> {
+    File::open(path)
+        .map(BufReader::new)
+        .map_err(|err| format!("could not open {}: {}", path.display(), err).into())
+}
+
+/// A directory for spilled runs that is removed again when dropped, even after an error.
+struct SpillDir {
+    path: PathBuf,
+    files: usize,
+}
+
+impl SpillDir {
+    fn create(parent: &Path) -> Result<Self, Box<dyn Error>> {
+        static CREATED: AtomicUsize = AtomicUsize::new(0);
+        let path = parent.join(format!(
+            "binaryheap-fruit-sort-{}-{}",
+            std::process::id(),
+            CREATED.fetch_add(1, AtomicOrdering::Relaxed)
+        ));
+        fs::create_dir_all(&path)?;
+        Ok(Self { path, files: 0 })
+    }
+
+    fn next_file(&mut self) -> PathBuf {
+        self.files += 1;
+        self.path.join(format!("run-{}.csv", self.files))
+    }
+}
+
+impl Drop for SpillDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+fn write_run(
+    path: &Path,
+    headers: Option<&StringRecord>,
+    run: &mut Vec<(SortKey, StringRecord)>,
+) -> Result<(), Box<dyn Error>> {
+    // `sort_by` is stable, so rows with equal keys keep their input order within a run.
+    run.sort_by(|a, b| a.0.cmp(&b.0));
+    let mut writer = WriterBuilder::new().from_writer(BufWriter::new(File::create(path)?));
+    if let Some(headers) = headers {
+        writer.write_record(headers)?;
+    }
+    for (_, record) in run.drain(..) {
+        writer.write_record(&record)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Sorts a CSV `input` of any size into `output`, holding at most `limits.run_rows` rows in memory.
+/// Runs are spilled under `temp_dir` and removed afterwards. The sort is stable.
+pub fn external_sort<R: Read, W: Write>(
+    input: R,
+    output: W,
+    options: &SortOptions,
+    limits: &SortLimits,
+    temp_dir: &Path,
+) -> Result<SortStats, Box<dyn Error>> {
+    if limits.run_rows == 0 || limits.fan_in < 2 {
+        return Err("runs need at least one row and merges at least two runs".into());
+    }
+    let mut reader = ReaderBuilder::new()
+        .has_headers(options.has_headers)
+        .from_reader(input);
+    let headers = if options.has_headers {
+        Some(reader.headers()?.clone())
+    } else {
+        None
+    };
+
+    let mut spill = SpillDir::create(temp_dir)?;
+    let mut runs = Vec::new();
+    let mut run = Vec::with_capacity(limits.run_rows);
+    let mut stats = SortStats::default();
+    for record in reader.records() {
+        let record = record?;
+        stats.rows += 1;
+        let key = options
+            .key(&record)
+            .map_err(|message| format!("row {}: {}", stats.rows, message))?;
+        run.push((key, record));
+        if run.len() == limits.run_rows {
+            let path = spill.next_file();
+            write_run(&path, headers.as_ref(), &mut run)?;
+            runs.push(path);
+        }
+    }
+    if !run.is_empty() || runs.is_empty() {
+        let path = spill.next_file();
+        write_run(&path, headers.as_ref(), &mut run)?;
+        runs.push(path);
+    }
+    stats.runs = runs.len();
+
+    // Merge groups of runs, oldest first, until one merge can take them all.
+    while runs.len() > limits.fan_in {
+        let mut merged = Vec::with_capacity(runs.len().div_ceil(limits.fan_in));
+        for group in runs.chunks(limits.fan_in) {
+            let path = spill.next_file();
+            merge_files(group, &path, options)?;
+            for run in group {
+                fs::remove_file(run)?;
+            }
+            merged.push(path);
+        }
+        runs = merged;
+        stats.passes += 1;
+    }
+    merge(open_all(&runs)?, output, options)?;
+    stats.passes += 1;
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn merge_strings(inputs: &[&str], options: &SortOptions) -> Result<String, Box<dyn Error>> {
+        let mut output = Vec::new();
+        merge(
+            inputs.iter().map(|input| input.as_bytes()).collect(),
+            &mut output,
+            options,
+        )?;
+        Ok(String::from_utf8(output)?)
+    }
+
+    #[test]
+    fn merges_sorted_inputs() {
+        let merged = merge_strings(
+            &[
+                "fruit,shard\nApple,a\nFig,a\nPear,a\n",
+                "fruit,shard\n",
+                "fruit,shard\nBanana,c\nFig,c\nPlum,c\n",
+            ],
+            &SortOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            merged,
+            "fruit,shard\nApple,a\nBanana,c\nFig,a\nFig,c\nPear,a\nPlum,c\n"
+        );
+    }
+
+    #[test]
+    fn merges_on_a_numeric_column_without_headers() {
+        let options = SortOptions {
+            key_column: 1,
+            numeric: true,
+            has_headers: false,
+        };
+        let merged = merge_strings(&["Fig,2\nPear,10\n", "Plum,1.5\nDate,30\n"], &options).unwrap();
+        assert_eq!(merged, "Plum,1.5\nFig,2\nPear,10\nDate,30\n");
+    }
+
+    #[test]
+    fn rejects_bad_inputs() {
+        let options = SortOptions::default();
+        let unsorted = merge_strings(&["fruit\nPear\nApple\n"], &options).unwrap_err();
+        assert!(unsorted.to_string().contains("input 0, row 2"));
+
+        assert!(merge_strings(&["fruit\nApple\n", "name\nFig\n"], &options).is_err());
+
+        let numeric = SortOptions {
+            numeric: true,
+            ..SortOptions::default()
+        };
+        assert!(merge_strings(&["count\nlots\n"], &numeric).is_err());
+    }
+
+    #[test]
+    fn external_sort_matches_an_in_memory_sort() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut rows: Vec<(u32, usize)> = (0..500).map(|id| (rng.gen_range(0..50), id)).collect();
+        let mut input = String::from("weight,id\n");
+        for (weight, id) in &rows {
+            input.push_str(&format!("{},{}\n", weight, id));
+        }
+
+        let options = SortOptions {
+            numeric: true,
+            ..SortOptions::default()
+        };
+        let limits = SortLimits {
+            run_rows: 7,
+            fan_in: 4,
+        };
+        let temp_dir = std::env::temp_dir();
+        let mut output = Vec::new();
+        let stats =
+            external_sort(input.as_bytes(), &mut output, &options, &limits, &temp_dir).unwrap();
+
+        // A stable sort keeps equal weights in id order.
+        rows.sort_by_key(|&(weight, _)| weight);
+        let mut expected = String::from("weight,id\n");
+        for (weight, id) in &rows {
+            expected.push_str(&format!("{},{}\n", weight, id));
+        }
+        assert_eq!(String::from_utf8(output).unwrap(), expected);
+        assert_eq!(stats.rows, 500);
+        assert_eq!(stats.runs, 72);
+        // 72 runs -> 18 -> 5 -> 2 -> output.
+        assert_eq!(stats.passes, 4);
+    }
+
+    #[test]
+    fn external_sort_cleans_up_and_handles_empty_input() {
+        let temp_dir =
+            std::env::temp_dir().join(format!("binaryheap-fruit-{}", std::process::id()));
+        fs::create_dir_all(&temp_dir).unwrap();
+        let mut output = Vec::new();
+        let stats = external_sort(
+            "fruit\n".as_bytes(),
+            &mut output,
+            &SortOptions::default(),
+            &SortLimits::default(),
+            &temp_dir,
+        )
+        .unwrap();
+        assert_eq!(stats.rows, 0);
+        assert_eq!(String::from_utf8(output).unwrap(), "fruit\n");
+        assert_eq!(fs::read_dir(&temp_dir).unwrap().count(), 0);
+        fs::remove_dir(&temp_dir).unwrap();
+    }
+}
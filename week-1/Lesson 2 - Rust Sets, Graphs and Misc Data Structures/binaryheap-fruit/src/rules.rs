@@ -0,0 +1,177 @@
+//! Configurable ordering rules that decide which fruit is served first.
+//!
+//! Rules are written in TOML:
+//!
+//! ```toml
+//! # Served first to last; names are matched case-insensitively.
+//! ranked = ["Fig", "Mango"]
+//! # How every other fruit is ordered: "alphabetical", "reverse-alphabetical" or "equal".
+//! rest = "alphabetical"
+//! ```
+//!
+//! [`PriorityRules::priority`] turns a fruit name into a [`Priority`], where a greater priority is
+//! served first, which is what a max-heap such as `BinaryHeap` pops first.
+
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+/// How fruits that are not ranked are ordered among themselves.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RestOrder {
+    /// Apple before Banana.
+    #[default]
+    Alphabetical,
+    /// Banana before Apple.
+    ReverseAlphabetical,
+    /// All unranked fruits tie.
+    Equal,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PriorityRules {
+    pub ranked: Vec<String>,
+    pub rest: RestOrder,
+}
+
+/// Figs first, then everything else alphabetically.
+impl Default for PriorityRules {
+    fn default() -> Self {
+        Self {
+            ranked: vec!["Fig".to_string()],
+            rest: RestOrder::Alphabetical,
+        }
+    }
+}
+
+impl PriorityRules {
+    pub fn from_toml(text: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(toml::from_str(text)?)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let text = fs::read_to_string(path)
+            .map_err(|err| format!("could not read {}: {}", path.display(), err))?;
+        Self::from_toml(&text)
+    }
+
+    pub fn priority(&self, name: &str) -> Priority {
+        let name = name.to_lowercase();
+        let tier = self
+            .ranked
+            .iter()
+            .position(|ranked| ranked.to_lowercase() == name)
+            .unwrap_or(self.ranked.len());
+        Priority {
+            tier,
+            name,
+            rest: self.rest,
+        }
+    }
+
+    /// Compares two fruits; `Greater` means `a` is served before `b`.
+    pub fn compare(&self, a: &str, b: &str) -> Ordering {
+        self.priority(a).cmp(&self.priority(b))
+    }
+}
+
+/// Where a fruit stands under a set of [`PriorityRules`]. Only compare priorities made by the same
+/// rules. Priorities are equal whenever they order as equal, so two unranked fruits under
+/// [`RestOrder::Equal`] are equal even with different names.
+#[derive(Debug, Clone)]
+pub struct Priority {
+    /// Position in `ranked`, or its length for unranked fruits. Lower is served first.
+    tier: usize,
+    name: String,
+    rest: RestOrder,
+}
+
+impl PartialEq for Priority {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Priority {}
+
+impl Ord for Priority {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.tier.cmp(&self.tier).then_with(|| match self.rest {
+            RestOrder::Alphabetical => other.name.cmp(&self.name),
+            RestOrder::ReverseAlphabetical => self.name.cmp(&other.name),
+            RestOrder::Equal => Ordering::Equal,
+        })
+    }
+}
+
+impl PartialOrd for Priority {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn serve_order(rules: &PriorityRules, fruits: &[&'static str]) -> Vec<&'static str> {
+        let mut fruits = fruits.to_vec();
+        fruits.sort_by(|a, b| rules.compare(b, a));
+        fruits
+    }
+
+    #[test]
+    fn default_rules_serve_figs_first() {
+        let rules = PriorityRules::default();
+        assert_eq!(
+            serve_order(&rules, &["Pear", "fig", "Apple", "Fig", "Cherry"]),
+            vec!["fig", "Fig", "Apple", "Cherry", "Pear"]
+        );
+    }
+
+    #[test]
+    fn loads_rules_from_toml() {
+        let rules = PriorityRules::from_toml(
+            "ranked = [\"Fig\", \"Mango\"]\nrest = \"reverse-alphabetical\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            serve_order(&rules, &["Apple", "Mango", "Pear", "Fig"]),
+            vec!["Fig", "Mango", "Pear", "Apple"]
+        );
+        assert_eq!(
+            PriorityRules::from_toml("ranked = [\"Kiwi\"]")
+                .unwrap()
+                .rest,
+            RestOrder::Alphabetical
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_settings() {
+        assert!(PriorityRules::from_toml("rest = \"random\"").is_err());
+        assert!(PriorityRules::from_toml("rank = [\"Fig\"]").is_err());
+    }
+
+    #[test]
+    fn equal_rest_only_orders_ranked_fruits() {
+        let rules = PriorityRules {
+            ranked: vec!["Fig".to_string()],
+            rest: RestOrder::Equal,
+        };
+        assert_eq!(rules.compare("Apple", "Pear"), Ordering::Equal);
+        assert_eq!(rules.compare("Fig", "Pear"), Ordering::Greater);
+        // Equality agrees with the ordering.
+        assert_eq!(
+            rules.priority("Apple").cmp(&rules.priority("Pear")),
+            Ordering::Equal
+        );
+        assert_eq!(rules.priority("Apple"), rules.priority("Pear"));
+        assert_ne!(rules.priority("Fig"), rules.priority("Pear"));
+    }
+}
@@ -0,0 +1,234 @@
+//! A `BinaryHeap`-backed scheduler that serves items by a key computed when they are pushed.
+//!
+//! The greatest key is served first. Wrapping the key in `Reverse` (see
+//! [`Scheduler::min_first`]) serves the smallest first instead. Items with equal keys are served
+//! in the order they were pushed, which a bare `BinaryHeap` does not guarantee.
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::fmt;
+
+use crate::rules::{Priority, PriorityRules};
+
+struct Entry<K, T> {
+    key: K,
+    /// Push order, so that equal keys come out first in, first out.
+    sequence: Reverse<u64>,
+    item: T,
+}
+
+impl<K: Ord, T> PartialEq for Entry<K, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<K: Ord, T> Eq for Entry<K, T> {}
+
+impl<K: Ord, T> Ord for Entry<K, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key
+            .cmp(&other.key)
+            .then_with(|| self.sequence.cmp(&other.sequence))
+    }
+}
+
+impl<K: Ord, T> PartialOrd for Entry<K, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+pub struct Scheduler<T, K: Ord> {
+    heap: BinaryHeap<Entry<K, T>>,
+    key: Box<dyn Fn(&T) -> K>,
+    pushed: u64,
+}
+
+impl<T, K: Ord> Scheduler<T, K> {
+    /// Creates a scheduler that serves the item with the greatest `key` first.
+    pub fn new(key: impl Fn(&T) -> K + 'static) -> Self {
+        Self {
+            heap: BinaryHeap::new(),
+            key: Box::new(key),
+            pushed: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    pub fn push(&mut self, item: T) {
+        let key = (self.key)(&item);
+        self.heap.push(Entry {
+            key,
+            sequence: Reverse(self.pushed),
+            item,
+        });
+        self.pushed += 1;
+    }
+
+    /// The item that [`Scheduler::pop`] would return.
+    pub fn peek(&self) -> Option<&T> {
+        self.heap.peek().map(|entry| &entry.item)
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.heap.pop().map(|entry| entry.item)
+    }
+
+    /// Pops up to `count` items, in the order they are served.
+    pub fn pop_batch(&mut self, count: usize) -> Vec<T> {
+        let mut batch = Vec::with_capacity(count.min(self.len()));
+        while batch.len() < count {
+            match self.pop() {
+                Some(item) => batch.push(item),
+                None => break,
+            }
+        }
+        batch
+    }
+
+    /// Every remaining item, in the order they would be served.
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        let mut entries = self.heap.into_sorted_vec();
+        entries.reverse();
+        entries.into_iter().map(|entry| entry.item).collect()
+    }
+}
+
+impl<T, K: Ord> Scheduler<T, Reverse<K>> {
+    /// Creates a scheduler that serves the item with the smallest `key` first.
+    pub fn min_first(key: impl Fn(&T) -> K + 'static) -> Self {
+        Self::new(move |item| Reverse(key(item)))
+    }
+}
+
+impl<T: AsRef<str>> Scheduler<T, Priority> {
+    /// Creates a scheduler that serves fruits in the order given by `rules`.
+    pub fn with_rules(rules: PriorityRules) -> Self {
+        Self::new(move |fruit: &T| rules.priority(fruit.as_ref()))
+    }
+}
+
+impl<T, K: Ord> Extend<T> for Scheduler<T, K> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push(item);
+        }
+    }
+}
+
+impl<T: fmt::Debug, K: Ord> fmt::Debug for Scheduler<T, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Scheduler")
+            .field("len", &self.len())
+            .field("next", &self.peek())
+            .finish()
+    }
+}
+
+/// The `k` greatest items by `key`, greatest first, keeping the earlier item on ties.
+///
+/// Holds at most `k` items in a min-heap while scanning, so it runs in O(n log k) and needs
+/// O(k) memory however long `items` is.
+pub fn top_k_by_key<T, K: Ord>(
+    items: impl IntoIterator<Item = T>,
+    k: usize,
+    key: impl Fn(&T) -> K,
+) -> Vec<T> {
+    if k == 0 {
+        return Vec::new();
+    }
+    let mut smallest_kept = BinaryHeap::with_capacity(k + 1);
+    for (index, item) in items.into_iter().enumerate() {
+        smallest_kept.push(Reverse(Entry {
+            key: key(&item),
+            sequence: Reverse(index as u64),
+            item,
+        }));
+        if smallest_kept.len() > k {
+            smallest_kept.pop();
+        }
+    }
+    // Sorting `Reverse` entries ascending puts the greatest entry first.
+    smallest_kept
+        .into_sorted_vec()
+        .into_iter()
+        .map(|Reverse(entry)| entry.item)
+        .collect()
+}
+
+/// The `k` greatest items, greatest first.
+pub fn top_k<T: Ord + Clone>(items: impl IntoIterator<Item = T>, k: usize) -> Vec<T> {
+    top_k_by_key(items, k, T::clone)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serves_greatest_first_and_ties_in_push_order() {
+        let mut scheduler = Scheduler::new(|&(priority, _): &(u8, char)| priority);
+        scheduler.extend([(1, 'a'), (3, 'b'), (1, 'c'), (3, 'd'), (2, 'e')]);
+        assert_eq!(scheduler.peek(), Some(&(3, 'b')));
+        let served: String = scheduler
+            .pop_batch(10)
+            .into_iter()
+            .map(|(_, c)| c)
+            .collect();
+        assert_eq!(served, "bdeac");
+        assert!(scheduler.is_empty());
+    }
+
+    #[test]
+    fn min_first_serves_smallest_first() {
+        let mut scheduler = Scheduler::min_first(|minutes: &u32| *minutes);
+        scheduler.extend([30, 5, 20, 5]);
+        assert_eq!(scheduler.pop_batch(3), vec![5, 5, 20]);
+        assert_eq!(scheduler.pop_batch(3), vec![30]);
+        assert_eq!(scheduler.pop(), None);
+    }
+
+    #[test]
+    fn rules_drive_the_order() {
+        let rules =
+            PriorityRules::from_toml("ranked = [\"Fig\", \"Mango\"]\nrest = \"alphabetical\"")
+                .unwrap();
+        let mut scheduler = Scheduler::with_rules(rules);
+        scheduler.extend(["Pear", "Mango", "Apple", "Fig", "Cherry", "Fig"]);
+        assert_eq!(scheduler.len(), 6);
+        assert_eq!(
+            scheduler.into_sorted_vec(),
+            vec!["Fig", "Fig", "Mango", "Apple", "Cherry", "Pear"]
+        );
+    }
+
+    #[test]
+    fn top_k_keeps_the_greatest() {
+        assert_eq!(top_k([4, 9, 1, 7, 3, 9], 3), vec![9, 9, 7]);
+        assert_eq!(top_k([2, 1], 5), vec![2, 1]);
+        assert!(top_k([2, 1], 0).is_empty());
+    }
+
+    #[test]
+    fn top_k_by_key_keeps_earlier_items_on_ties() {
+        let words = ["kiwi", "fig", "plum", "date", "pear"];
+        assert_eq!(
+            top_k_by_key(words, 2, |word| word.len()),
+            vec!["kiwi", "plum"]
+        );
+
+        let rules = PriorityRules::default();
+        assert_eq!(
+            top_k_by_key(["Pear", "Fig", "Apple"], 2, |fruit| rules.priority(fruit)),
+            vec!["Fig", "Apple"]
+        );
+    }
+}
@@ -0,0 +1,180 @@
+//! A fruit catalogue kept in sorted order, with the queries a `BTreeMap` makes cheap.
+//!
+//! Names are compared case-insensitively: each fruit is keyed by its lowercase name and keeps the
+//! spelling it was added with for display. Prefix, range, floor and ceiling lookups walk the tree
+//! from the first matching key, so they cost O(log n) plus the number of fruits returned.
+//! `BTreeMap` keeps no subtree sizes, so [`OrderedCatalogue::rank`] and
+//! [`OrderedCatalogue::select`] have to count and are O(k).
+
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Default)]
+pub struct OrderedCatalogue {
+    fruits: BTreeMap<String, String>,
+}
+
+fn key(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+impl OrderedCatalogue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.fruits.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fruits.is_empty()
+    }
+
+    /// Adds `name`, returning `false` if the catalogue already had it (in any case).
+    pub fn insert(&mut self, name: &str) -> bool {
+        let key = key(name);
+        if key.is_empty() || self.fruits.contains_key(&key) {
+            return false;
+        }
+        self.fruits.insert(key, name.trim().to_string());
+        true
+    }
+
+    /// Removes `name`, returning the spelling it was stored with.
+    pub fn remove(&mut self, name: &str) -> Option<String> {
+        self.fruits.remove(&key(name))
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.fruits.contains_key(&key(name))
+    }
+
+    /// All fruits in order.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &str> {
+        self.fruits.values().map(String::as_str)
+    }
+
+    /// Fruits whose name starts with `prefix`. An empty prefix matches everything.
+    pub fn prefix(&self, prefix: &str) -> Vec<&str> {
+        let prefix = key(prefix);
+        self.fruits
+            .range(prefix.clone()..)
+            .take_while(|(key, _)| key.starts_with(&prefix))
+            .map(|(_, name)| name.as_str())
+            .collect()
+    }
+
+    /// Fruits from `from` to `to`, both included. Empty if `from` sorts after `to`.
+    pub fn range(&self, from: &str, to: &str) -> Vec<&str> {
+        let (from, to) = (key(from), key(to));
+        if from > to {
+            return Vec::new();
+        }
+        self.fruits
+            .range(from..=to)
+            .map(|(_, name)| name.as_str())
+            .collect()
+    }
+
+    /// Number of fruits that sort before `name`, whether or not `name` is in the catalogue.
+    pub fn rank(&self, name: &str) -> usize {
+        self.fruits.range(..key(name)).count()
+    }
+
+    /// The `k`-th fruit in order, counting from 0.
+    pub fn select(&self, k: usize) -> Option<&str> {
+        self.iter().nth(k)
+    }
+
+    /// The last fruit that sorts at or before `name`.
+    pub fn floor(&self, name: &str) -> Option<&str> {
+        self.fruits
+            .range(..=key(name))
+            .next_back()
+            .map(|(_, name)| name.as_str())
+    }
+
+    /// The first fruit that sorts at or after `name`.
+    pub fn ceiling(&self, name: &str) -> Option<&str> {
+        self.fruits
+            .range(key(name)..)
+            .next()
+            .map(|(_, name)| name.as_str())
+    }
+}
+
+impl<'a> FromIterator<&'a str> for OrderedCatalogue {
+    fn from_iter<I: IntoIterator<Item = &'a str>>(iter: I) -> Self {
+        let mut catalogue = Self::new();
+        for name in iter {
+            catalogue.insert(name);
+        }
+        catalogue
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn catalogue() -> OrderedCatalogue {
+        ["Cherry", "apple", "Chestnut", "Fig", "Banana", "Grape"]
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn keeps_fruits_sorted_case_insensitively() {
+        let mut catalogue = catalogue();
+        assert!(!catalogue.insert("APPLE"));
+        assert_eq!(
+            catalogue.iter().collect::<Vec<_>>(),
+            vec!["apple", "Banana", "Cherry", "Chestnut", "Fig", "Grape"]
+        );
+        assert_eq!(catalogue.remove("cherry").as_deref(), Some("Cherry"));
+        assert!(!catalogue.contains("Cherry"));
+    }
+
+    #[test]
+    fn prefix_search() {
+        let catalogue = catalogue();
+        assert_eq!(catalogue.prefix("ch"), vec!["Cherry", "Chestnut"]);
+        assert_eq!(catalogue.prefix("CHE"), vec!["Cherry", "Chestnut"]);
+        assert!(catalogue.prefix("kiwi").is_empty());
+        assert_eq!(catalogue.prefix("").len(), catalogue.len());
+    }
+
+    #[test]
+    fn range_between_names() {
+        let catalogue = catalogue();
+        assert_eq!(
+            catalogue.range("banana", "date"),
+            vec!["Banana", "Cherry", "Chestnut"]
+        );
+        assert_eq!(catalogue.range("b", "c"), vec!["Banana"]);
+        assert!(catalogue.range("grape", "apple").is_empty());
+    }
+
+    #[test]
+    fn rank_and_select_are_inverse() {
+        let catalogue = catalogue();
+        for k in 0..catalogue.len() {
+            let name = catalogue.select(k).unwrap();
+            assert_eq!(catalogue.rank(name), k);
+        }
+        assert_eq!(catalogue.select(catalogue.len()), None);
+        assert_eq!(catalogue.rank("Date"), 4);
+        assert_eq!(catalogue.rank("Zucchini"), catalogue.len());
+    }
+
+    #[test]
+    fn floor_and_ceiling() {
+        let catalogue = catalogue();
+        assert_eq!(catalogue.floor("Date"), Some("Chestnut"));
+        assert_eq!(catalogue.ceiling("Date"), Some("Fig"));
+        assert_eq!(catalogue.floor("fig"), Some("Fig"));
+        assert_eq!(catalogue.ceiling("fig"), Some("Fig"));
+        assert_eq!(catalogue.floor("Aardvark"), None);
+        assert_eq!(catalogue.ceiling("Zucchini"), None);
+    }
+}
@@ -0,0 +1,7 @@
+//! A sorted fruit catalogue with prefix, range, rank/select and floor/ceiling queries, and an
+//! interactive prompt to run them.
+
+pub mod catalogue;
+pub mod repl;
+
+pub use catalogue::OrderedCatalogue;
@@ -87,21 +87,44 @@
 //! to a `Vec` or `LinkedList` would require significant changes to both the data
 //! handling and the related algorithms.
 //!
-use rand::seq::SliceRandom;
+use btreeset_fruit::{repl, OrderedCatalogue};
+use clap::{Parser, Subcommand};
+use fruit_catalogue::{sample_without_replacement, FRUITS};
 use rand::thread_rng;
 use std::collections::{BTreeSet, HashMap};
+use std::io::{self, BufRead};
 
-fn main() {
-    let fruits = vec![
-        "apple",
-        "banana",
-        "cherry",
-        "date",
-        "elderberry",
-        "fig",
-        "grape",
-        "honeydew",
-    ];
+#[derive(Parser)]
+#[clap(version = "1.0", about = "Sorted fruit sets with BTreeSet")]
+struct Opts {
+    #[clap(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Build random salads and remove a fruit from the catalogue (default)
+    Demo,
+    /// Query the sorted catalogue interactively
+    Repl,
+}
+
+fn main() -> io::Result<()> {
+    let opts: Opts = Opts::parse();
+
+    match opts.command.unwrap_or(Command::Demo) {
+        Command::Demo => demo(),
+        Command::Repl => {
+            let mut catalogue: OrderedCatalogue = FRUITS.into_iter().collect();
+            let stdin = io::stdin();
+            repl::run(&mut catalogue, stdin.lock(), io::stdout())?;
+        }
+    }
+    Ok(())
+}
+
+fn demo() {
+    let fruits = FRUITS;
     let amounts = [1, 3, 5, 7, 9];
 
     let mut rng = thread_rng();
@@ -110,10 +133,10 @@ fn main() {
 
     for amount in amounts.iter() {
         let mut fruit_set = BTreeSet::new();
-        let mut shuffled_fruits = fruits.clone();
-        shuffled_fruits.shuffle(&mut rng);
+        let salad = sample_without_replacement(&fruits, *amount, &mut rng)
+            .expect("every amount fits in the catalogue");
 
-        for fruit in shuffled_fruits {
+        for fruit in salad {
             fruit_set.insert(fruit);
 
             // Challenge(3): Count the number of times each fruit is generated
@@ -121,10 +144,6 @@ fn main() {
                 .entry(fruit)
                 .and_modify(|count| *count += 1)
                 .or_insert(1);
-
-            if fruit_set.len() >= *amount {
-                break;
-            }
         }
 
         println!("{}: {:?}", amount, fruit_set);
@@ -134,11 +153,9 @@ fn main() {
     let mut fruits_set: BTreeSet<_> = fruits.iter().copied().collect();
     println!("Fruits: {:?}", fruits_set);
 
-    let index = select_fruit(fruits.as_slice());
-
-    match index {
-        Some(index) => {
-            fruits_set.remove(fruits.get(index).unwrap());
+    match select_fruit(fruits.as_slice()) {
+        Some(fruit) => {
+            fruits_set.remove(fruit);
             println!("Fruits: {:?}", fruits_set);
         }
         None => {
@@ -153,20 +170,32 @@ fn main() {
 }
 
 // This functions list all the fruits and ask the user which one they want to eliminate, return the selected fruit by the user.
-fn select_fruit(fruits: &[&str]) -> Option<usize> {
+// Anything other than a listed index or "n" asks again; the end of the input selects nothing.
+fn select_fruit<'a>(fruits: &[&'a str]) -> Option<&'a str> {
     println!("Select a fruit to eliminate:");
     for (i, fruit) in fruits.iter().enumerate() {
         println!("{}: {}", i, fruit);
     }
     println!("n: None");
 
+    let stdin = io::stdin();
     let mut input = String::new();
-    std::io::stdin()
-        .read_line(&mut input)
-        .expect("Failed to read line");
-
-    match input.trim().parse() {
-        Ok(num) => Some(num),
-        Err(_) => None,
+    loop {
+        input.clear();
+        match stdin.lock().read_line(&mut input) {
+            Ok(0) | Err(_) => return None,
+            Ok(_) => {}
+        }
+        let input = input.trim();
+        if input.eq_ignore_ascii_case("n") {
+            return None;
+        }
+        match input.parse::<usize>().ok().and_then(|i| fruits.get(i)) {
+            Some(fruit) => return Some(fruit),
+            None => println!(
+                "Error: Invalid input. Enter a number from 0 to {} or n.",
+                fruits.len() - 1
+            ),
+        }
     }
 }
@@ -0,0 +1,214 @@
+//! An interactive prompt over an [`OrderedCatalogue`].
+//!
+//! Each line is one command. Commands that take two fruit names separate them with a comma, since
+//! names can contain spaces (`range Cherry, Grape`). Invalid lines print an error and the prompt
+//! carries on; `quit` or the end of the input stops it.
+
+use std::io::{self, BufRead, Write};
+
+use crate::OrderedCatalogue;
+
+const HELP: &str = "\
+Commands:
+  list                     every fruit in order
+  prefix <text>            fruits starting with <text>
+  range <from>, <to>       fruits from <from> to <to>, both included
+  rank <fruit>             how many fruits sort before <fruit>
+  select <k>               the k-th fruit, counting from 0
+  floor <fruit>            the last fruit at or before <fruit>
+  ceiling <fruit>          the first fruit at or after <fruit>
+  add <fruit>              add a fruit
+  remove <fruit>           remove a fruit
+  help                     show this message
+  quit                     leave";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    List,
+    Prefix(String),
+    Range(String, String),
+    Rank(String),
+    Select(usize),
+    Floor(String),
+    Ceiling(String),
+    Add(String),
+    Remove(String),
+    Help,
+    Quit,
+}
+
+impl Command {
+    /// Parses one line of input. Returns `Ok(None)` for a blank line.
+    pub fn parse(line: &str) -> Result<Option<Command>, String> {
+        let line = line.trim();
+        if line.is_empty() {
+            return Ok(None);
+        }
+        let (name, argument) = match line.split_once(char::is_whitespace) {
+            Some((name, argument)) => (name, argument.trim()),
+            None => (line, ""),
+        };
+        let fruit = || -> Result<String, String> {
+            if argument.is_empty() {
+                Err(format!("'{}' needs a fruit name", name))
+            } else {
+                Ok(argument.to_string())
+            }
+        };
+
+        let command = match name.to_lowercase().as_str() {
+            "list" | "ls" => Command::List,
+            "prefix" => Command::Prefix(argument.to_string()),
+            "range" => match argument.split_once(',') {
+                Some((from, to)) if !from.trim().is_empty() && !to.trim().is_empty() => {
+                    Command::Range(from.trim().to_string(), to.trim().to_string())
+                }
+                _ => return Err("'range' needs two fruit names separated by a comma".to_string()),
+            },
+            "rank" => Command::Rank(fruit()?),
+            "select" => Command::Select(
+                argument
+                    .parse()
+                    .map_err(|_| format!("'{}' is not a position, expected 0 or more", argument))?,
+            ),
+            "floor" => Command::Floor(fruit()?),
+            "ceiling" | "ceil" => Command::Ceiling(fruit()?),
+            "add" => Command::Add(fruit()?),
+            "remove" | "rm" => Command::Remove(fruit()?),
+            "help" | "?" => Command::Help,
+            "quit" | "exit" => Command::Quit,
+            other => return Err(format!("unknown command '{}', try 'help'", other)),
+        };
+        Ok(Some(command))
+    }
+}
+
+fn print_list<W: Write>(output: &mut W, fruits: &[&str]) -> io::Result<()> {
+    if fruits.is_empty() {
+        writeln!(output, "(none)")
+    } else {
+        writeln!(output, "{}", fruits.join(", "))
+    }
+}
+
+fn print_one<W: Write>(output: &mut W, fruit: Option<&str>) -> io::Result<()> {
+    writeln!(output, "{}", fruit.unwrap_or("(none)"))
+}
+
+/// Applies `command` to the catalogue and writes the answer. Returns `false` on `quit`.
+pub fn execute<W: Write>(
+    catalogue: &mut OrderedCatalogue,
+    command: Command,
+    output: &mut W,
+) -> io::Result<bool> {
+    match command {
+        Command::List => print_list(output, &catalogue.iter().collect::<Vec<_>>())?,
+        Command::Prefix(prefix) => print_list(output, &catalogue.prefix(&prefix))?,
+        Command::Range(from, to) => print_list(output, &catalogue.range(&from, &to))?,
+        Command::Rank(fruit) => writeln!(output, "{}", catalogue.rank(&fruit))?,
+        Command::Select(k) => match catalogue.select(k) {
+            Some(fruit) => writeln!(output, "{}", fruit)?,
+            None => writeln!(
+                output,
+                "Error: position {} is out of range, the catalogue has {} fruits",
+                k,
+                catalogue.len()
+            )?,
+        },
+        Command::Floor(fruit) => print_one(output, catalogue.floor(&fruit))?,
+        Command::Ceiling(fruit) => print_one(output, catalogue.ceiling(&fruit))?,
+        Command::Add(fruit) => {
+            if catalogue.insert(&fruit) {
+                writeln!(output, "Added {}", fruit)?;
+            } else {
+                writeln!(output, "{} is already in the catalogue", fruit)?;
+            }
+        }
+        Command::Remove(fruit) => match catalogue.remove(&fruit) {
+            Some(removed) => writeln!(output, "Removed {}", removed)?,
+            None => writeln!(output, "{} is not in the catalogue", fruit)?,
+        },
+        Command::Help => writeln!(output, "{}", HELP)?,
+        Command::Quit => return Ok(false),
+    }
+    Ok(true)
+}
+
+/// Reads commands from `input` until `quit` or the end of the input.
+pub fn run<R: BufRead, W: Write>(
+    catalogue: &mut OrderedCatalogue,
+    input: R,
+    mut output: W,
+) -> io::Result<()> {
+    writeln!(
+        output,
+        "{} fruits in the catalogue, type 'help' for commands.",
+        catalogue.len()
+    )?;
+    let mut lines = input.lines();
+    loop {
+        write!(output, "> ")?;
+        output.flush()?;
+        let Some(line) = lines.next() else {
+            writeln!(output)?;
+            return Ok(());
+        };
+        match Command::parse(&line?) {
+            Ok(Some(command)) => {
+                if !execute(catalogue, command, &mut output)? {
+                    return Ok(());
+                }
+            }
+            Ok(None) => {}
+            Err(message) => writeln!(output, "Error: {}", message)?,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_commands() {
+        assert_eq!(
+            Command::parse("  prefix ch "),
+            Ok(Some(Command::Prefix("ch".to_string())))
+        );
+        assert_eq!(
+            Command::parse("range Strawberry Tree Berry , Watermelon"),
+            Ok(Some(Command::Range(
+                "Strawberry Tree Berry".to_string(),
+                "Watermelon".to_string()
+            )))
+        );
+        assert_eq!(Command::parse("SELECT 3"), Ok(Some(Command::Select(3))));
+        assert_eq!(Command::parse(""), Ok(None));
+    }
+
+    #[test]
+    fn rejects_invalid_input() {
+        assert!(Command::parse("select -1").is_err());
+        assert!(Command::parse("select").is_err());
+        assert!(Command::parse("range apple").is_err());
+        assert!(Command::parse("floor").is_err());
+        assert!(Command::parse("juice apple").is_err());
+    }
+
+    #[test]
+    fn runs_a_session() {
+        let mut catalogue: OrderedCatalogue = ["Apple", "Cherry", "Fig"].into_iter().collect();
+        let input = "prefix c\nselect 9\nbogus\nadd Date\nfloor dragonfruit\nquit\nlist\n";
+        let mut output = Vec::new();
+        run(&mut catalogue, input.as_bytes(), &mut output).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("> Cherry\n"));
+        assert!(output.contains("position 9 is out of range"));
+        assert!(output.contains("unknown command 'bogus'"));
+        assert!(output.contains("> Added Date\n> Date\n"));
+        // Nothing after `quit` runs.
+        assert!(!output.contains("Apple, Cherry"));
+        assert_eq!(catalogue.len(), 4);
+    }
+}
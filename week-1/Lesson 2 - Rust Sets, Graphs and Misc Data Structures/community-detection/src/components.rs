@@ -0,0 +1,109 @@
+//! Connected components as partitions.
+//!
+//! Two users are in the same weakly connected component when some chain of interactions joins
+//! them, whichever way each interaction points. This is the coarsest split of the graph: every
+//! community found by the other algorithms lies inside one component. Weak components are found
+//! with a union-find forest; strong ones, which follow the direction of every interaction, with
+//! Kosaraju's algorithm.
+
+use petgraph::algo::kosaraju_scc;
+
+use crate::partition::Partition;
+use crate::undirected::UndirectedGraph;
+use crate::InteractionGraph;
+
+/// Disjoint sets over `0..n` with union by rank and path halving.
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+}
+
+impl UnionFind {
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            rank: vec![0; n],
+        }
+    }
+
+    /// The representative of the set containing `element`.
+    pub fn find(&mut self, mut element: usize) -> usize {
+        while self.parent[element] != element {
+            self.parent[element] = self.parent[self.parent[element]];
+            element = self.parent[element];
+        }
+        element
+    }
+
+    /// Merges the sets containing `a` and `b`. Returns false if they were already the same set.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        let (parent, child) = if self.rank[a] >= self.rank[b] {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        self.parent[child] = parent;
+        if self.rank[parent] == self.rank[child] {
+            self.rank[parent] += 1;
+        }
+        true
+    }
+
+    /// The set of every element as a partition.
+    pub fn partition(&mut self) -> Partition {
+        let roots: Vec<usize> = (0..self.parent.len()).map(|e| self.find(e)).collect();
+        Partition::from_labels(roots)
+    }
+}
+
+pub fn weakly_connected_components(graph: &UndirectedGraph) -> Partition {
+    let mut sets = UnionFind::new(graph.node_count());
+    for node in 0..graph.node_count() {
+        for &(neighbour, _) in graph.neighbours(node) {
+            sets.union(node, neighbour);
+        }
+    }
+    sets.partition()
+}
+
+pub fn strongly_connected_components(graph: &InteractionGraph) -> Partition {
+    let mut labels = vec![0; graph.node_count()];
+    for (component, nodes) in kosaraju_scc(graph.graph()).into_iter().enumerate() {
+        for node in nodes {
+            labels[node.index()] = component;
+        }
+    }
+    Partition::from_labels(labels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unions_merge_sets() {
+        let mut sets = UnionFind::new(5);
+        assert!(sets.union(0, 1));
+        assert!(sets.union(3, 4));
+        assert!(sets.union(1, 4));
+        assert!(!sets.union(0, 3));
+        assert_eq!(sets.find(0), sets.find(4));
+        assert_ne!(sets.find(0), sets.find(2));
+        assert_eq!(sets.partition(), Partition::from_labels([0, 0, 1, 0, 0]));
+    }
+
+    #[test]
+    fn finds_components_including_isolated_users() {
+        let graph =
+            UndirectedGraph::from_edges(6, [(0, 1, 1.0), (1, 2, 1.0), (3, 4, 1.0), (5, 5, 1.0)]);
+        assert_eq!(
+            weakly_connected_components(&graph),
+            Partition::from_labels([0, 0, 0, 1, 1, 2])
+        );
+    }
+}
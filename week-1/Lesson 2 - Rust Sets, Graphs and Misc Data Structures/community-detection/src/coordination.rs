@@ -0,0 +1,462 @@
+//! Flags groups of accounts that act in lockstep, the signature of bot and troll networks.
+//!
+//! Organic users retweet or mention the same account too, but spread out over hours or days.
+//! Coordinated accounts pile onto the same target within minutes, again and again. The detector
+//! works in four steps:
+//!
+//! 1. Interactions with the same type and target are chained into *bursts*: consecutive
+//!    interactions less than [`Options::window`] seconds apart belong to the same burst. Chaining
+//!    avoids splitting a burst that straddles a fixed clock boundary.
+//! 2. Each user is described by the set of bursts they took part in. Two users are similar when
+//!    those sets overlap, by [`Similarity::Jaccard`] or [`Similarity::Cosine`].
+//! 3. Pairs of users that share at least [`Options::min_shared`] bursts with a similarity of at
+//!    least [`Options::threshold`] are joined in a similarity graph. Dense clusters are taken from
+//!    it with [`leiden`].
+//! 4. Each cluster is scored by how consistently its members act together, see [`Group::score`].
+//!
+//! Interactions without a timestamp are ignored.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::louvain::{self, leiden};
+use crate::undirected::UndirectedGraph;
+use crate::Interaction;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Similarity {
+    /// Shared bursts over the bursts either user took part in.
+    #[default]
+    Jaccard,
+    /// Shared bursts over the geometric mean of both users' burst counts. Kinder than Jaccard to
+    /// pairs where one account is far more active than the other.
+    Cosine,
+}
+
+impl Similarity {
+    /// Similarity of two users with `a` and `b` bursts, `shared` of them in common.
+    pub fn of(self, shared: usize, a: usize, b: usize) -> f64 {
+        if shared == 0 {
+            return 0.0;
+        }
+        let shared = shared as f64;
+        match self {
+            Similarity::Jaccard => shared / ((a + b) as f64 - shared),
+            Similarity::Cosine => shared / ((a * b) as f64).sqrt(),
+        }
+    }
+}
+
+impl FromStr for Similarity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "jaccard" => Ok(Similarity::Jaccard),
+            "cosine" => Ok(Similarity::Cosine),
+            _ => Err(format!(
+                "unknown similarity '{}', expected jaccard or cosine",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Similarity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(match self {
+            Similarity::Jaccard => "jaccard",
+            Similarity::Cosine => "cosine",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Options {
+    /// Largest gap in seconds between two interactions of the same burst.
+    pub window: i64,
+    /// Interaction types to look at, such as `retweet`. Empty means all of them.
+    pub kinds: Vec<String>,
+    pub similarity: Similarity,
+    /// Smallest similarity that joins two users in the similarity graph.
+    pub threshold: f64,
+    /// Fewest bursts two users must share to be joined, so that one coincidence is not enough.
+    pub min_shared: usize,
+    /// Smallest group worth reporting.
+    pub min_size: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            window: 600,
+            kinds: Vec::new(),
+            similarity: Similarity::Jaccard,
+            threshold: 0.3,
+            min_shared: 2,
+            min_size: 3,
+        }
+    }
+}
+
+/// Interactions of one type aimed at one target, each within [`Options::window`] of the last.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Burst {
+    pub kind: String,
+    pub target: String,
+    pub start: i64,
+    pub end: i64,
+    /// Users taking part, sorted, each once.
+    pub users: Vec<String>,
+}
+
+/// Chains the timestamped interactions of `options.kinds` into bursts, ordered by start time.
+pub fn bursts(interactions: &[Interaction], options: &Options) -> Vec<Burst> {
+    let mut events: HashMap<(&str, &str), Vec<(i64, &str)>> = HashMap::new();
+    for interaction in interactions {
+        let Some(timestamp) = interaction.timestamp else {
+            continue;
+        };
+        if options.kinds.is_empty() || options.kinds.contains(&interaction.kind) {
+            events
+                .entry((interaction.kind.as_str(), interaction.target.as_str()))
+                .or_default()
+                .push((timestamp, interaction.source.as_str()));
+        }
+    }
+
+    let mut bursts = Vec::new();
+    for ((kind, target), mut events) in events {
+        events.sort_unstable();
+        let mut current: Option<Burst> = None;
+        for (timestamp, user) in events {
+            match &mut current {
+                Some(burst) if timestamp - burst.end <= options.window => {
+                    burst.end = timestamp;
+                    burst.users.push(user.to_string());
+                }
+                _ => {
+                    bursts.extend(current.take());
+                    current = Some(Burst {
+                        kind: kind.to_string(),
+                        target: target.to_string(),
+                        start: timestamp,
+                        end: timestamp,
+                        users: vec![user.to_string()],
+                    });
+                }
+            }
+        }
+        bursts.extend(current);
+    }
+    for burst in &mut bursts {
+        burst.users.sort();
+        burst.users.dedup();
+    }
+    bursts.sort_by(|a, b| (a.start, &a.kind, &a.target).cmp(&(b.start, &b.kind, &b.target)));
+    bursts
+}
+
+/// Which users took part in which bursts, and how many bursts every pair of users shares.
+#[derive(Debug, Clone)]
+pub struct CoActivity {
+    /// Every user taking part in at least one burst, sorted. Users are referred to by their index
+    /// in this list.
+    users: Vec<String>,
+    bursts: Vec<Burst>,
+    /// The users of each burst, as indices.
+    members: Vec<Vec<usize>>,
+    /// Bursts each user took part in.
+    activity: Vec<usize>,
+    /// Bursts shared by each pair of users `(a, b)` with `a < b`.
+    shared: HashMap<(usize, usize), usize>,
+}
+
+impl CoActivity {
+    pub fn new(interactions: &[Interaction], options: &Options) -> Self {
+        let bursts = bursts(interactions, options);
+        let mut users: Vec<String> = bursts
+            .iter()
+            .flat_map(|burst| burst.users.iter().cloned())
+            .collect();
+        users.sort();
+        users.dedup();
+        let index: HashMap<&str, usize> = users
+            .iter()
+            .enumerate()
+            .map(|(i, user)| (user.as_str(), i))
+            .collect();
+
+        let members: Vec<Vec<usize>> = bursts
+            .iter()
+            .map(|burst| {
+                burst
+                    .users
+                    .iter()
+                    .map(|user| index[user.as_str()])
+                    .collect()
+            })
+            .collect();
+        let mut activity = vec![0; users.len()];
+        let mut shared = HashMap::new();
+        for users in &members {
+            for (i, &a) in users.iter().enumerate() {
+                activity[a] += 1;
+                for &b in &users[i + 1..] {
+                    *shared.entry((a.min(b), a.max(b))).or_default() += 1;
+                }
+            }
+        }
+
+        Self {
+            users,
+            bursts,
+            members,
+            activity,
+            shared,
+        }
+    }
+
+    pub fn users(&self) -> &[String] {
+        &self.users
+    }
+
+    pub fn bursts(&self) -> &[Burst] {
+        &self.bursts
+    }
+
+    /// Bursts that both `a` and `b` took part in.
+    pub fn shared(&self, a: usize, b: usize) -> usize {
+        self.shared.get(&(a.min(b), a.max(b))).copied().unwrap_or(0)
+    }
+
+    pub fn similarity(&self, a: usize, b: usize, similarity: Similarity) -> f64 {
+        similarity.of(self.shared(a, b), self.activity[a], self.activity[b])
+    }
+
+    /// Joins every pair of users that passes `options.min_shared` and `options.threshold`,
+    /// weighted by their similarity.
+    pub fn similarity_graph(&self, options: &Options) -> UndirectedGraph {
+        let edges = self.shared.iter().filter_map(|(&(a, b), &shared)| {
+            let similarity = self.similarity(a, b, options.similarity);
+            (shared >= options.min_shared && similarity >= options.threshold)
+                .then_some((a, b, similarity))
+        });
+        UndirectedGraph::from_edges(self.users.len(), edges)
+    }
+}
+
+/// A cluster of users that act together.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Group {
+    /// Sorted usernames.
+    pub users: Vec<String>,
+    /// Share of member pairs joined in the similarity graph.
+    pub density: f64,
+    /// Mean similarity over all member pairs, joined or not.
+    pub cohesion: f64,
+    /// `density × cohesion`, between 0 and 1. Near 1 means every member took part in nearly every
+    /// burst of every other member, which organic audiences rarely do.
+    pub score: f64,
+    /// Bursts in which at least two members took part.
+    pub bursts: usize,
+    /// Targets of those bursts with how many of them each drew, most first.
+    pub targets: Vec<(String, usize)>,
+}
+
+/// Finds groups of coordinated users in `interactions`, most suspicious first.
+pub fn detect(interactions: &[Interaction], options: &Options) -> Vec<Group> {
+    let activity = CoActivity::new(interactions, options);
+    let graph = activity.similarity_graph(options);
+    let clusters = leiden(&graph, &louvain::Options::default()).partition;
+
+    let mut groups: Vec<Group> = clusters
+        .communities()
+        .into_iter()
+        .filter(|members| members.len() >= options.min_size.max(2))
+        .map(|members| group(&activity, &graph, &members, options.similarity))
+        .collect();
+    groups.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then(b.users.len().cmp(&a.users.len()))
+            .then(a.users.cmp(&b.users))
+    });
+    groups
+}
+
+fn group(
+    activity: &CoActivity,
+    graph: &UndirectedGraph,
+    members: &[usize],
+    similarity: Similarity,
+) -> Group {
+    let mut pairs = 0;
+    let mut joined = 0;
+    let mut total_similarity = 0.0;
+    for (i, &a) in members.iter().enumerate() {
+        for &b in &members[i + 1..] {
+            pairs += 1;
+            total_similarity += activity.similarity(a, b, similarity);
+            if graph
+                .neighbours(a)
+                .binary_search_by_key(&b, |&(neighbour, _)| neighbour)
+                .is_ok()
+            {
+                joined += 1;
+            }
+        }
+    }
+    let density = joined as f64 / pairs as f64;
+    let cohesion = total_similarity / pairs as f64;
+
+    let mut bursts = 0;
+    let mut targets: HashMap<&str, usize> = HashMap::new();
+    for (burst, users) in activity.bursts.iter().zip(&activity.members) {
+        if users.iter().filter(|user| members.contains(user)).count() >= 2 {
+            bursts += 1;
+            *targets.entry(&burst.target).or_default() += 1;
+        }
+    }
+    let mut targets: Vec<(String, usize)> = targets
+        .into_iter()
+        .map(|(target, count)| (target.to_string(), count))
+        .collect();
+    targets.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+    Group {
+        users: members
+            .iter()
+            .map(|&member| activity.users[member].clone())
+            .collect(),
+        density,
+        cohesion,
+        score: density * cohesion,
+        bursts,
+        targets,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(source: &str, target: &str, timestamp: i64) -> Interaction {
+        Interaction {
+            timestamp: Some(timestamp),
+            ..Interaction::new(source, target, "retweet")
+        }
+    }
+
+    /// Four bots that retweet each of five targets within a minute of each other, and four
+    /// people who retweet the same targets hours apart.
+    fn bots_and_people() -> Vec<Interaction> {
+        let bots = ["bot_a", "bot_b", "bot_c", "bot_d"];
+        let people = ["ann", "ben", "cat", "dan"];
+        let mut interactions = Vec::new();
+        for (round, target) in ["news", "meme", "poll", "rally", "link"].iter().enumerate() {
+            let start = round as i64 * 86_400;
+            for (i, bot) in bots.iter().enumerate() {
+                interactions.push(at(bot, target, start + 20 * i as i64));
+            }
+            for (i, person) in people.iter().enumerate() {
+                interactions.push(at(person, target, start + 7_200 * (i as i64 + 1)));
+            }
+        }
+        interactions.push(Interaction::new("ann", "ben", "mention"));
+        interactions
+    }
+
+    #[test]
+    fn chains_interactions_into_bursts() {
+        let options = Options {
+            window: 60,
+            ..Options::default()
+        };
+        let interactions = [
+            at("a", "t", 0),
+            at("b", "t", 50),
+            at("c", "t", 100),
+            at("a", "t", 110),
+            at("d", "t", 500),
+            at("a", "u", 10),
+            Interaction::new("e", "t", "retweet"),
+        ];
+        let bursts = bursts(&interactions, &options);
+        let summary: Vec<(&str, i64, i64, Vec<String>)> = bursts
+            .iter()
+            .map(|b| (b.target.as_str(), b.start, b.end, b.users.clone()))
+            .collect();
+        let users = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                ("t", 0, 110, users(&["a", "b", "c"])),
+                ("u", 10, 10, users(&["a"])),
+                ("t", 500, 500, users(&["d"])),
+            ]
+        );
+
+        let mentions = Options {
+            kinds: vec!["mention".to_string()],
+            ..options
+        };
+        assert!(super::bursts(&interactions, &mentions).is_empty());
+    }
+
+    #[test]
+    fn measures_burst_overlap() {
+        assert_eq!(Similarity::Jaccard.of(2, 4, 2), 0.5);
+        assert_eq!(Similarity::Cosine.of(2, 8, 2), 0.5);
+        assert_eq!(Similarity::Cosine.of(0, 3, 3), 0.0);
+        assert_eq!("Cosine".parse(), Ok(Similarity::Cosine));
+
+        let activity = CoActivity::new(&bots_and_people(), &Options::default());
+        assert_eq!(activity.users().len(), 8);
+        assert_eq!(activity.bursts().len(), 5 * 5);
+        let index = |name: &str| {
+            activity
+                .users()
+                .iter()
+                .position(|user| user == name)
+                .unwrap()
+        };
+        let (bot_a, bot_b) = (index("bot_a"), index("bot_b"));
+        assert_eq!(activity.shared(bot_a, bot_b), 5);
+        assert_eq!(activity.similarity(bot_a, bot_b, Similarity::Jaccard), 1.0);
+        assert_eq!(activity.shared(index("ann"), index("ben")), 0);
+        assert_eq!(activity.shared(index("ann"), bot_a), 0);
+    }
+
+    #[test]
+    fn ranks_the_bot_network_first() {
+        for similarity in [Similarity::Jaccard, Similarity::Cosine] {
+            let options = Options {
+                similarity,
+                ..Options::default()
+            };
+            let groups = detect(&bots_and_people(), &options);
+            assert_eq!(groups.len(), 1, "{}", similarity);
+            let bots = &groups[0];
+            assert_eq!(bots.users, vec!["bot_a", "bot_b", "bot_c", "bot_d"]);
+            assert_eq!(bots.score, 1.0);
+            assert_eq!(bots.bursts, 5);
+            assert_eq!(bots.targets.len(), 5);
+        }
+    }
+
+    #[test]
+    fn finds_the_trolls_in_the_bundled_data() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/data/interactions.csv");
+        let interactions = crate::interactions::load(path.as_ref()).unwrap();
+        let groups = detect(&interactions, &Options::default());
+        let top = &groups[0];
+        assert!(top.users.contains(&"ten_gop".to_string()));
+        assert!(top
+            .users
+            .iter()
+            .all(|user| !user.starts_with("journalist") && !user.starts_with("citizen")));
+        assert!(top.score > 0.3);
+    }
+}
@@ -0,0 +1,132 @@
+//! k-core decomposition.
+//!
+//! The k-core is what remains after repeatedly deleting users with fewer than `k` distinct
+//! neighbours. A user's core number is the largest `k` whose core still contains them. High
+//! cores pick out the densely interlinked centre of the graph, such as accounts that all amplify
+//! one another, without having to choose a number of communities. Edge weights and self-loops are
+//! ignored.
+
+use crate::components::UnionFind;
+use crate::partition::Partition;
+use crate::undirected::UndirectedGraph;
+
+/// The core number of every node, in `O(n + m)` with the bucket algorithm of Batagelj and
+/// Zaversnik (2003).
+pub fn core_numbers(graph: &UndirectedGraph) -> Vec<usize> {
+    let n = graph.node_count();
+    let mut degrees: Vec<usize> = (0..n).map(|node| graph.neighbours(node).len()).collect();
+    let max_degree = degrees.iter().copied().max().unwrap_or(0);
+
+    // Nodes sorted by current degree, with the position where each degree's bucket starts.
+    let mut starts = vec![0; max_degree + 1];
+    for &degree in &degrees {
+        starts[degree] += 1;
+    }
+    let mut start = 0;
+    for bucket in &mut starts {
+        let size = *bucket;
+        *bucket = start;
+        start += size;
+    }
+    let mut sorted = vec![0; n];
+    let mut positions = vec![0; n];
+    let mut next = starts.clone();
+    for node in 0..n {
+        positions[node] = next[degrees[node]];
+        sorted[positions[node]] = node;
+        next[degrees[node]] += 1;
+    }
+
+    // Peel nodes in order of degree. Each removal lowers a neighbour's degree by moving it to the
+    // front of its bucket and shrinking the bucket by one.
+    for i in 0..n {
+        let node = sorted[i];
+        for &(neighbour, _) in graph.neighbours(node) {
+            let degree = degrees[neighbour];
+            if degree <= degrees[node] {
+                continue;
+            }
+            let first = sorted[starts[degree]];
+            if first != neighbour {
+                sorted.swap(positions[neighbour], starts[degree]);
+                positions[first] = positions[neighbour];
+                positions[neighbour] = starts[degree];
+            }
+            starts[degree] += 1;
+            degrees[neighbour] -= 1;
+        }
+    }
+    degrees
+}
+
+/// The nodes of the `k`-core, in increasing order.
+pub fn k_core(graph: &UndirectedGraph, k: usize) -> Vec<usize> {
+    core_numbers(graph)
+        .into_iter()
+        .enumerate()
+        .filter(|&(_, core)| core >= k)
+        .map(|(node, _)| node)
+        .collect()
+}
+
+/// The connected components of the `k`-core as communities. Nodes outside the core are left on
+/// their own.
+pub fn core_partition(graph: &UndirectedGraph, k: usize) -> Partition {
+    let cores = core_numbers(graph);
+    let mut sets = UnionFind::new(graph.node_count());
+    for node in 0..graph.node_count() {
+        for &(neighbour, _) in graph.neighbours(node) {
+            if cores[node] >= k && cores[neighbour] >= k {
+                sets.union(node, neighbour);
+            }
+        }
+    }
+    sets.partition()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 4-clique (0-3) with a triangle (3, 4, 5) hanging off node 3 and a path 5-6-7.
+    fn clique_with_tail() -> UndirectedGraph {
+        UndirectedGraph::from_edges(
+            9,
+            [
+                (0, 1, 1.0),
+                (0, 2, 1.0),
+                (0, 3, 1.0),
+                (1, 2, 1.0),
+                (1, 3, 1.0),
+                (2, 3, 1.0),
+                (3, 4, 1.0),
+                (4, 5, 1.0),
+                (3, 5, 1.0),
+                (5, 6, 1.0),
+                (6, 7, 5.0),
+                (8, 8, 1.0),
+            ],
+        )
+    }
+
+    #[test]
+    fn peels_nodes_into_cores() {
+        let graph = clique_with_tail();
+        assert_eq!(core_numbers(&graph), vec![3, 3, 3, 3, 2, 2, 1, 1, 0]);
+        assert_eq!(k_core(&graph, 2), vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(k_core(&graph, 4), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn core_components_become_communities() {
+        let graph = clique_with_tail();
+        assert_eq!(
+            core_partition(&graph, 3),
+            Partition::from_labels([0, 0, 0, 0, 1, 2, 3, 4, 5])
+        );
+        assert_eq!(
+            core_partition(&graph, 1),
+            Partition::from_labels([0, 0, 0, 0, 0, 0, 0, 0, 1])
+        );
+    }
+}
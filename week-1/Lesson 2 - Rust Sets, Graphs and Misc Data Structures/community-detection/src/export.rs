@@ -0,0 +1,548 @@
+//! Writes an interaction graph, optionally with the community of every user, for other tools.
+//!
+//! - [`Format::Dot`] for Graphviz, with nodes filled by community colour.
+//! - [`Format::GraphMl`] and [`Format::Gexf`] for Gephi, Cytoscape and most graph libraries.
+//! - [`Format::Json`] in the node-link layout read by networkx and d3-force.
+//!
+//! Every format keeps the direction, type, weight, count and first and last timestamps of each
+//! edge. DOT stores the weight as `interaction_weight`, since Graphviz's own `weight` attribute
+//! only takes integers. Users are labelled with their username and, when a partition is given, a
+//! `community` attribute.
+
+use std::fmt;
+use std::io::{self, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+use petgraph::visit::EdgeRef;
+use serde::{Deserialize, Serialize};
+
+use crate::partition::Partition;
+use crate::InteractionGraph;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Dot,
+    GraphMl,
+    Gexf,
+    Json,
+}
+
+impl Format {
+    /// The format matching the extension of `path`, if any.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension()?.to_str()?.parse().ok()
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "dot" | "gv" => Ok(Format::Dot),
+            "graphml" => Ok(Format::GraphMl),
+            "gexf" => Ok(Format::Gexf),
+            "json" => Ok(Format::Json),
+            _ => Err(format!(
+                "unknown format '{}', expected dot, graphml, gexf or json",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(match self {
+            Format::Dot => "dot",
+            Format::GraphMl => "graphml",
+            Format::Gexf => "gexf",
+            Format::Json => "json",
+        })
+    }
+}
+
+/// Fill colours for DOT nodes, cycled when there are more communities than colours. Taken from
+/// ColorBrewer's Set3, which stays readable behind black labels.
+const PALETTE: [&str; 12] = [
+    "#8dd3c7", "#ffffb3", "#bebada", "#fb8072", "#80b1d3", "#fdb462", "#b3de69", "#fccde5",
+    "#d9d9d9", "#bc80bd", "#ccebc5", "#ffed6f",
+];
+
+/// Writes `graph` in `format`. `communities`, if given, must cover every node of `graph`.
+///
+/// # Panics
+///
+/// Panics if `communities` has a different number of nodes than `graph`.
+pub fn write<W: Write>(
+    graph: &InteractionGraph,
+    communities: Option<&Partition>,
+    format: Format,
+    out: W,
+) -> io::Result<()> {
+    if let Some(communities) = communities {
+        assert_eq!(
+            communities.node_count(),
+            graph.node_count(),
+            "the partition does not match the graph"
+        );
+    }
+    match format {
+        Format::Dot => write_dot(graph, communities, out),
+        Format::GraphMl => write_graphml(graph, communities, out),
+        Format::Gexf => write_gexf(graph, communities, out),
+        Format::Json => write_json(graph, communities, out),
+    }
+}
+
+fn write_dot<W: Write>(
+    graph: &InteractionGraph,
+    communities: Option<&Partition>,
+    mut out: W,
+) -> io::Result<()> {
+    writeln!(out, "digraph interactions {{")?;
+    writeln!(
+        out,
+        "  node [shape=ellipse, style=filled, fillcolor=\"#ffffff\"];"
+    )?;
+    for node in graph.graph().node_indices() {
+        write!(
+            out,
+            "  {} [label=\"{}\"",
+            node.index(),
+            escape_dot(graph.username(node))
+        )?;
+        if let Some(communities) = communities {
+            let community = communities.community(node.index());
+            write!(
+                out,
+                ", community={}, fillcolor=\"{}\"",
+                community,
+                PALETTE[community % PALETTE.len()]
+            )?;
+        }
+        writeln!(out, "];")?;
+    }
+    for edge in graph.graph().edge_references() {
+        let data = edge.weight();
+        write!(
+            out,
+            "  {} -> {} [kind=\"{}\", interaction_weight={}, count={}",
+            edge.source().index(),
+            edge.target().index(),
+            escape_dot(&data.kind),
+            data.weight,
+            data.count
+        )?;
+        if let Some(first_seen) = data.first_seen {
+            write!(out, ", first_seen={}", first_seen)?;
+        }
+        if let Some(last_seen) = data.last_seen {
+            write!(out, ", last_seen={}", last_seen)?;
+        }
+        writeln!(out, "];")?;
+    }
+    writeln!(out, "}}")
+}
+
+fn write_graphml<W: Write>(
+    graph: &InteractionGraph,
+    communities: Option<&Partition>,
+    mut out: W,
+) -> io::Result<()> {
+    writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        out,
+        r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#
+    )?;
+    let keys = [
+        ("node", "label", "string"),
+        ("node", "community", "int"),
+        ("edge", "kind", "string"),
+        ("edge", "weight", "double"),
+        ("edge", "count", "int"),
+        ("edge", "first_seen", "long"),
+        ("edge", "last_seen", "long"),
+    ];
+    for (domain, name, kind) in keys {
+        if name == "community" && communities.is_none() {
+            continue;
+        }
+        writeln!(
+            out,
+            r#"  <key id="{name}" for="{domain}" attr.name="{name}" attr.type="{kind}"/>"#
+        )?;
+    }
+    writeln!(out, r#"  <graph id="interactions" edgedefault="directed">"#)?;
+    for node in graph.graph().node_indices() {
+        write!(
+            out,
+            r#"    <node id="n{}"><data key="label">{}</data>"#,
+            node.index(),
+            escape_xml(graph.username(node))
+        )?;
+        if let Some(communities) = communities {
+            write!(
+                out,
+                r#"<data key="community">{}</data>"#,
+                communities.community(node.index())
+            )?;
+        }
+        writeln!(out, "</node>")?;
+    }
+    for edge in graph.graph().edge_references() {
+        let data = edge.weight();
+        write!(
+            out,
+            r#"    <edge id="e{}" source="n{}" target="n{}"><data key="kind">{}</data><data key="weight">{}</data><data key="count">{}</data>"#,
+            edge.id().index(),
+            edge.source().index(),
+            edge.target().index(),
+            escape_xml(&data.kind),
+            data.weight,
+            data.count
+        )?;
+        if let Some(first_seen) = data.first_seen {
+            write!(out, r#"<data key="first_seen">{}</data>"#, first_seen)?;
+        }
+        if let Some(last_seen) = data.last_seen {
+            write!(out, r#"<data key="last_seen">{}</data>"#, last_seen)?;
+        }
+        writeln!(out, "</edge>")?;
+    }
+    writeln!(out, "  </graph>")?;
+    writeln!(out, "</graphml>")
+}
+
+fn write_gexf<W: Write>(
+    graph: &InteractionGraph,
+    communities: Option<&Partition>,
+    mut out: W,
+) -> io::Result<()> {
+    writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(out, r#"<gexf xmlns="http://gexf.net/1.3" version="1.3">"#)?;
+    writeln!(out, r#"  <graph mode="static" defaultedgetype="directed">"#)?;
+    if communities.is_some() {
+        writeln!(out, r#"    <attributes class="node">"#)?;
+        writeln!(
+            out,
+            r#"      <attribute id="community" title="community" type="integer"/>"#
+        )?;
+        writeln!(out, "    </attributes>")?;
+    }
+    writeln!(out, r#"    <attributes class="edge">"#)?;
+    for (name, kind) in [
+        ("kind", "string"),
+        ("count", "integer"),
+        ("first_seen", "long"),
+        ("last_seen", "long"),
+    ] {
+        writeln!(
+            out,
+            r#"      <attribute id="{name}" title="{name}" type="{kind}"/>"#
+        )?;
+    }
+    writeln!(out, "    </attributes>")?;
+
+    writeln!(out, "    <nodes>")?;
+    for node in graph.graph().node_indices() {
+        write!(
+            out,
+            r#"      <node id="{}" label="{}">"#,
+            node.index(),
+            escape_xml(graph.username(node))
+        )?;
+        if let Some(communities) = communities {
+            write!(
+                out,
+                r#"<attvalues><attvalue for="community" value="{}"/></attvalues>"#,
+                communities.community(node.index())
+            )?;
+        }
+        writeln!(out, "</node>")?;
+    }
+    writeln!(out, "    </nodes>")?;
+
+    writeln!(out, "    <edges>")?;
+    for edge in graph.graph().edge_references() {
+        let data = edge.weight();
+        write!(
+            out,
+            r#"      <edge id="{}" source="{}" target="{}" weight="{}"><attvalues><attvalue for="kind" value="{}"/><attvalue for="count" value="{}"/>"#,
+            edge.id().index(),
+            edge.source().index(),
+            edge.target().index(),
+            data.weight,
+            escape_xml(&data.kind),
+            data.count
+        )?;
+        if let Some(first_seen) = data.first_seen {
+            write!(
+                out,
+                r#"<attvalue for="first_seen" value="{}"/>"#,
+                first_seen
+            )?;
+        }
+        if let Some(last_seen) = data.last_seen {
+            write!(out, r#"<attvalue for="last_seen" value="{}"/>"#, last_seen)?;
+        }
+        writeln!(out, "</attvalues></edge>")?;
+    }
+    writeln!(out, "    </edges>")?;
+    writeln!(out, "  </graph>")?;
+    writeln!(out, "</gexf>")
+}
+
+/// The node-link JSON layout. Nodes are identified by username.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeLink {
+    pub directed: bool,
+    /// Two users can be joined by one link per interaction type.
+    pub multigraph: bool,
+    pub nodes: Vec<Node>,
+    pub links: Vec<Link>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Node {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub community: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Link {
+    pub source: String,
+    pub target: String,
+    pub kind: String,
+    pub weight: f64,
+    pub count: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_seen: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_seen: Option<i64>,
+}
+
+impl NodeLink {
+    pub fn new(graph: &InteractionGraph, communities: Option<&Partition>) -> Self {
+        let nodes = graph
+            .graph()
+            .node_indices()
+            .map(|node| Node {
+                id: graph.username(node).to_string(),
+                community: communities.map(|communities| communities.community(node.index())),
+            })
+            .collect();
+        let links = graph
+            .graph()
+            .edge_references()
+            .map(|edge| {
+                let data = edge.weight();
+                Link {
+                    source: graph.username(edge.source()).to_string(),
+                    target: graph.username(edge.target()).to_string(),
+                    kind: data.kind.clone(),
+                    weight: data.weight,
+                    count: data.count,
+                    first_seen: data.first_seen,
+                    last_seen: data.last_seen,
+                }
+            })
+            .collect();
+        Self {
+            directed: true,
+            multigraph: true,
+            nodes,
+            links,
+        }
+    }
+}
+
+fn write_json<W: Write>(
+    graph: &InteractionGraph,
+    communities: Option<&Partition>,
+    mut out: W,
+) -> io::Result<()> {
+    serde_json::to_writer_pretty(&mut out, &NodeLink::new(graph, communities))?;
+    writeln!(out)
+}
+
+fn escape_dot(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Interaction;
+
+    /// What every readable format must preserve: users with their community, and edges with
+    /// their data, all in node and edge index order.
+    type Snapshot = (Vec<(String, Option<usize>)>, Vec<Link>);
+    type Reader = fn(&str) -> Snapshot;
+
+    fn graph() -> (InteractionGraph, Partition) {
+        let mut retweets = Interaction::new("ten_gop", "jenn_abrams", "retweet");
+        retweets.weight = 2.5;
+        retweets.timestamp = Some(1_475_280_000);
+        let mut later = retweets.clone();
+        later.timestamp = Some(1_475_290_000);
+        let interactions = [
+            retweets,
+            later,
+            Interaction::new("ten_gop", "jenn_abrams", "mention"),
+            Interaction::new("jenn_abrams", "ten_gop", "mention"),
+            Interaction::new("<script>&\"quotes'", "ten_gop", "reply"),
+            Interaction::new("journalist1", "journalist2", "mention"),
+        ];
+        let graph = InteractionGraph::from_interactions(&interactions);
+        let communities = Partition::from_labels([0, 0, 1, 2, 2]);
+        (graph, communities)
+    }
+
+    fn snapshot(graph: &InteractionGraph, communities: Option<&Partition>) -> Snapshot {
+        let json = NodeLink::new(graph, communities);
+        let nodes = json
+            .nodes
+            .into_iter()
+            .map(|n| (n.id, n.community))
+            .collect();
+        (nodes, json.links)
+    }
+
+    fn export(format: Format, communities: Option<&Partition>) -> String {
+        let mut out = Vec::new();
+        write(&graph().0, communities, format, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    fn read_graphml(text: &str) -> Snapshot {
+        let document = roxmltree::Document::parse(text).unwrap();
+        let data = |element: roxmltree::Node, key: &str| -> Option<String> {
+            element
+                .children()
+                .find(|child| child.has_tag_name("data") && child.attribute("key") == Some(key))
+                .map(|child| child.text().unwrap_or_default().to_string())
+        };
+        let mut ids = Vec::new();
+        let mut nodes = Vec::new();
+        for node in document.descendants().filter(|n| n.has_tag_name("node")) {
+            ids.push(node.attribute("id").unwrap().to_string());
+            let community = data(node, "community").map(|c| c.parse().unwrap());
+            nodes.push((data(node, "label").unwrap(), community));
+        }
+        let name = |id: &str| nodes[ids.iter().position(|i| i == id).unwrap()].0.clone();
+        let links = document
+            .descendants()
+            .filter(|n| n.has_tag_name("edge"))
+            .map(|edge| Link {
+                source: name(edge.attribute("source").unwrap()),
+                target: name(edge.attribute("target").unwrap()),
+                kind: data(edge, "kind").unwrap(),
+                weight: data(edge, "weight").unwrap().parse().unwrap(),
+                count: data(edge, "count").unwrap().parse().unwrap(),
+                first_seen: data(edge, "first_seen").map(|t| t.parse().unwrap()),
+                last_seen: data(edge, "last_seen").map(|t| t.parse().unwrap()),
+            })
+            .collect();
+        (nodes, links)
+    }
+
+    fn read_gexf(text: &str) -> Snapshot {
+        let document = roxmltree::Document::parse(text).unwrap();
+        let value = |element: roxmltree::Node, key: &str| -> Option<String> {
+            element
+                .descendants()
+                .find(|n| n.has_tag_name("attvalue") && n.attribute("for") == Some(key))
+                .map(|n| n.attribute("value").unwrap().to_string())
+        };
+        let mut ids = Vec::new();
+        let mut nodes = Vec::new();
+        for node in document.descendants().filter(|n| n.has_tag_name("node")) {
+            ids.push(node.attribute("id").unwrap().to_string());
+            let community = value(node, "community").map(|c| c.parse().unwrap());
+            nodes.push((node.attribute("label").unwrap().to_string(), community));
+        }
+        let name = |id: &str| nodes[ids.iter().position(|i| i == id).unwrap()].0.clone();
+        let links = document
+            .descendants()
+            .filter(|n| n.has_tag_name("edge"))
+            .map(|edge| Link {
+                source: name(edge.attribute("source").unwrap()),
+                target: name(edge.attribute("target").unwrap()),
+                kind: value(edge, "kind").unwrap(),
+                weight: edge.attribute("weight").unwrap().parse().unwrap(),
+                count: value(edge, "count").unwrap().parse().unwrap(),
+                first_seen: value(edge, "first_seen").map(|t| t.parse().unwrap()),
+                last_seen: value(edge, "last_seen").map(|t| t.parse().unwrap()),
+            })
+            .collect();
+        (nodes, links)
+    }
+
+    fn read_json(text: &str) -> Snapshot {
+        let json: NodeLink = serde_json::from_str(text).unwrap();
+        assert!(json.directed && json.multigraph);
+        let nodes = json
+            .nodes
+            .into_iter()
+            .map(|n| (n.id, n.community))
+            .collect();
+        (nodes, json.links)
+    }
+
+    #[test]
+    fn readable_formats_round_trip() {
+        let (graph, communities) = graph();
+        let readers: [(Format, Reader); 3] = [
+            (Format::GraphMl, read_graphml),
+            (Format::Gexf, read_gexf),
+            (Format::Json, read_json),
+        ];
+        for (format, read) in readers {
+            for communities in [Some(&communities), None] {
+                let expected = snapshot(&graph, communities);
+                assert_eq!(read(&export(format, communities)), expected, "{}", format);
+            }
+        }
+    }
+
+    #[test]
+    fn dot_colours_nodes_by_community() {
+        let (_, communities) = graph();
+        let dot = export(Format::Dot, Some(&communities));
+        assert!(dot.starts_with("digraph interactions {\n"));
+        assert!(dot.contains(r##"  0 [label="ten_gop", community=0, fillcolor="#8dd3c7"];"##));
+        assert!(dot
+            .contains(r##"  2 [label="<script>&\"quotes'", community=1, fillcolor="#ffffb3"];"##));
+        assert!(dot.contains(
+            r#"  0 -> 1 [kind="retweet", interaction_weight=5, count=2, first_seen=1475280000, last_seen=1475290000];"#
+        ));
+        assert!(dot.contains(r#"  1 -> 0 [kind="mention", interaction_weight=1, count=1];"#));
+        assert!(dot.ends_with("}\n"));
+        // Graphviz's own `weight` must be an integer, so it is left to Graphviz.
+        assert!(!dot.contains(" weight="));
+        assert!(!export(Format::Dot, None).contains("community"));
+    }
+
+    #[test]
+    fn picks_the_format_from_the_extension() {
+        assert_eq!(
+            Format::from_path(Path::new("out/graph.GEXF")),
+            Some(Format::Gexf)
+        );
+        assert_eq!(Format::from_path(Path::new("graph.gv")), Some(Format::Dot));
+        assert_eq!(Format::from_path(Path::new("graph.csv")), None);
+        assert_eq!(Format::from_path(Path::new("graph")), None);
+        assert_eq!("GraphML".parse(), Ok(Format::GraphMl));
+    }
+}
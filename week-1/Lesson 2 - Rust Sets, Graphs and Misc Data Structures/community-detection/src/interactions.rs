@@ -0,0 +1,363 @@
+//! Interaction edge lists and the directed graph built from them.
+//!
+//! An edge list is a CSV or TSV file with a header row. `source` and `target` are required; `type`
+//! (default `interaction`), `weight` (default 1) and `timestamp` are optional:
+//!
+//! ```text
+//! source,target,type,weight,timestamp
+//! ten_gop,jenn_abrams,retweet,1,2016-10-01T12:00:00Z
+//! ten_gop,jenn_abrams,retweet,1,1475323800
+//! ```
+//!
+//! Timestamps are Unix seconds or UTC dates written as `YYYY-MM-DD`, optionally followed by
+//! `HH:MM[:SS]` with a `T` or a space in between and an optional trailing `Z`.
+//!
+//! [`InteractionGraph`] interns every username into a single node and merges interactions with the
+//! same source, target and type into one edge whose weight is the sum of theirs.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use petgraph::algo::kosaraju_scc;
+use petgraph::prelude::*;
+use serde::Deserialize;
+
+use crate::TWITTER_USERNAMES;
+
+/// One row of an edge list.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Interaction {
+    pub source: String,
+    pub target: String,
+    pub kind: String,
+    pub weight: f64,
+    /// Unix seconds.
+    pub timestamp: Option<i64>,
+}
+
+impl Interaction {
+    pub fn new(source: &str, target: &str, kind: &str) -> Self {
+        Self {
+            source: source.to_string(),
+            target: target.to_string(),
+            kind: kind.to_string(),
+            weight: 1.0,
+            timestamp: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Row {
+    source: String,
+    target: String,
+    #[serde(rename = "type", default)]
+    kind: Option<String>,
+    #[serde(default)]
+    weight: Option<f64>,
+    #[serde(default)]
+    timestamp: Option<String>,
+}
+
+/// Reads interactions from CSV (`delimiter` `b','`) or TSV (`b'\t'`) with a header row.
+pub fn from_reader<R: Read>(reader: R, delimiter: u8) -> Result<Vec<Interaction>, Box<dyn Error>> {
+    let mut rdr = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .trim(csv::Trim::All)
+        .from_reader(reader);
+    let mut interactions = Vec::new();
+    for (line, result) in rdr.deserialize().enumerate() {
+        // Line 1 is the header.
+        let context = |message: String| format!("line {}: {}", line + 2, message);
+        let row: Row = result.map_err(|err| context(err.to_string()))?;
+        if row.source.is_empty() || row.target.is_empty() {
+            return Err(context("source and target cannot be empty".to_string()).into());
+        }
+        let weight = row.weight.unwrap_or(1.0);
+        if !weight.is_finite() || weight <= 0.0 {
+            return Err(context(format!("weight must be positive, got {}", weight)).into());
+        }
+        let timestamp = match row.timestamp.as_deref() {
+            None | Some("") => None,
+            Some(text) => Some(parse_timestamp(text).map_err(context)?),
+        };
+        interactions.push(Interaction {
+            source: row.source,
+            target: row.target,
+            kind: row
+                .kind
+                .filter(|kind| !kind.is_empty())
+                .unwrap_or_else(|| "interaction".to_string()),
+            weight,
+            timestamp,
+        });
+    }
+    Ok(interactions)
+}
+
+/// Loads an edge list, reading `.tsv` and `.tab` files as TSV and anything else as CSV.
+pub fn load(path: &Path) -> Result<Vec<Interaction>, Box<dyn Error>> {
+    let ext = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_ascii_lowercase);
+    let delimiter = match ext.as_deref() {
+        Some("tsv") | Some("tab") => b'\t',
+        _ => b',',
+    };
+    let file =
+        File::open(path).map_err(|err| format!("could not open {}: {}", path.display(), err))?;
+    from_reader(file, delimiter).map_err(|err| format!("{}: {}", path.display(), err).into())
+}
+
+/// The bundled sample: consecutive entries of [`TWITTER_USERNAMES`] read as user → mention
+/// retweets. The pairs are made up, so use it to try the pipeline, not to draw conclusions.
+pub fn sample() -> Vec<Interaction> {
+    TWITTER_USERNAMES
+        .windows(2)
+        .map(|pair| Interaction::new(pair[0], pair[1], "retweets"))
+        .collect()
+}
+
+/// Parses Unix seconds or a UTC `YYYY-MM-DD[( |T)HH:MM[:SS]][Z]` date.
+pub fn parse_timestamp(text: &str) -> Result<i64, String> {
+    let invalid = || format!("'{}' is not a Unix timestamp or a YYYY-MM-DD date", text);
+    let text = text.trim();
+    if let Ok(seconds) = text.parse::<i64>() {
+        return Ok(seconds);
+    }
+
+    let text = text.strip_suffix('Z').unwrap_or(text);
+    let (date, time) = match text.split_once(['T', ' ']) {
+        Some((date, time)) => (date, Some(time)),
+        None => (text, None),
+    };
+    let numbers = |part: &str, sep: char| -> Result<Vec<i64>, String> {
+        part.split(sep)
+            .map(|n| n.parse::<i64>().map_err(|_| invalid()))
+            .collect()
+    };
+
+    let [year, month, day] = numbers(date, '-')?[..] else {
+        return Err(invalid());
+    };
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return Err(invalid());
+    }
+    let seconds_of_day = match time {
+        None => 0,
+        Some(time) => match numbers(time, ':')?[..] {
+            [h, m] if (0..24).contains(&h) && (0..60).contains(&m) => h * 3600 + m * 60,
+            [h, m, s] if (0..24).contains(&h) && (0..60).contains(&m) && (0..61).contains(&s) => {
+                h * 3600 + m * 60 + s
+            }
+            _ => return Err(invalid()),
+        },
+    };
+    Ok(days_from_civil(year, month, day) * 86_400 + seconds_of_day)
+}
+
+/// Days since 1970-01-01 in the proleptic Gregorian calendar (Howard Hinnant's algorithm).
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// All interactions of one type between one ordered pair of users.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Edge {
+    pub kind: String,
+    /// Sum of the merged interactions' weights.
+    pub weight: f64,
+    /// Number of merged interactions.
+    pub count: u32,
+    pub first_seen: Option<i64>,
+    pub last_seen: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct InteractionGraph {
+    graph: DiGraph<String, Edge>,
+    nodes: HashMap<String, NodeIndex>,
+    edges: HashMap<(NodeIndex, NodeIndex, String), EdgeIndex>,
+}
+
+impl InteractionGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_interactions<'a>(interactions: impl IntoIterator<Item = &'a Interaction>) -> Self {
+        let mut graph = Self::new();
+        for interaction in interactions {
+            graph.add(interaction);
+        }
+        graph
+    }
+
+    /// The directed graph: one node per username, one edge per merged interaction.
+    pub fn graph(&self) -> &DiGraph<String, Edge> {
+        &self.graph
+    }
+
+    /// The node for `user`, created on first use.
+    pub fn intern(&mut self, user: &str) -> NodeIndex {
+        if let Some(&node) = self.nodes.get(user) {
+            return node;
+        }
+        let node = self.graph.add_node(user.to_string());
+        self.nodes.insert(user.to_string(), node);
+        node
+    }
+
+    pub fn node(&self, user: &str) -> Option<NodeIndex> {
+        self.nodes.get(user).copied()
+    }
+
+    pub fn username(&self, node: NodeIndex) -> &str {
+        &self.graph[node]
+    }
+
+    /// Adds an interaction, merging it into the existing edge with the same endpoints and type.
+    pub fn add(&mut self, interaction: &Interaction) -> EdgeIndex {
+        let source = self.intern(&interaction.source);
+        let target = self.intern(&interaction.target);
+        let key = (source, target, interaction.kind.clone());
+        if let Some(&edge) = self.edges.get(&key) {
+            let merged = &mut self.graph[edge];
+            merged.weight += interaction.weight;
+            merged.count += 1;
+            if let Some(timestamp) = interaction.timestamp {
+                merged.first_seen = Some(merged.first_seen.map_or(timestamp, |t| t.min(timestamp)));
+                merged.last_seen = Some(merged.last_seen.map_or(timestamp, |t| t.max(timestamp)));
+            }
+            return edge;
+        }
+        let edge = self.graph.add_edge(
+            source,
+            target,
+            Edge {
+                kind: interaction.kind.clone(),
+                weight: interaction.weight,
+                count: 1,
+                first_seen: interaction.timestamp,
+                last_seen: interaction.timestamp,
+            },
+        );
+        self.edges.insert(key, edge);
+        edge
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.graph.node_count()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.graph.edge_count()
+    }
+
+    /// Strongly connected components found with Kosaraju's algorithm, as usernames.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<&str>> {
+        kosaraju_scc(&self.graph)
+            .into_iter()
+            .map(|component| {
+                component
+                    .into_iter()
+                    .map(|node| self.username(node))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_csv_and_tsv() {
+        let csv = "source,target,type,weight,timestamp\na,b,retweet,2,10\nb,a,,,\n";
+        let interactions = from_reader(csv.as_bytes(), b',').unwrap();
+        assert_eq!(interactions.len(), 2);
+        assert_eq!(interactions[0].weight, 2.0);
+        assert_eq!(interactions[0].timestamp, Some(10));
+        assert_eq!(interactions[1].kind, "interaction");
+        assert_eq!(interactions[1].weight, 1.0);
+
+        // Only source and target are required, in any column order.
+        let tsv = "target\tsource\nb\ta\n";
+        let interactions = from_reader(tsv.as_bytes(), b'\t').unwrap();
+        assert_eq!(
+            interactions,
+            vec![Interaction::new("a", "b", "interaction")]
+        );
+    }
+
+    #[test]
+    fn reports_the_bad_line() {
+        let csv = "source,target,weight\na,b,1\na,c,-3\n";
+        let err = from_reader(csv.as_bytes(), b',').unwrap_err();
+        assert!(err.to_string().starts_with("line 3:"), "{}", err);
+        assert!(from_reader("source\na\n".as_bytes(), b',').is_err());
+    }
+
+    #[test]
+    fn parses_timestamps() {
+        assert_eq!(parse_timestamp("1475323800"), Ok(1_475_323_800));
+        assert_eq!(parse_timestamp("1970-01-01"), Ok(0));
+        assert_eq!(parse_timestamp("2016-10-01T12:10:00Z"), Ok(1_475_323_800));
+        assert_eq!(parse_timestamp("2016-10-01 12:10"), Ok(1_475_323_800));
+        assert_eq!(parse_timestamp("2000-03-01"), Ok(951_868_800));
+        assert!(parse_timestamp("2016-13-01").is_err());
+        assert!(parse_timestamp("yesterday").is_err());
+    }
+
+    #[test]
+    fn merges_duplicate_edges() {
+        let mut interactions = vec![
+            Interaction::new("a", "b", "retweet"),
+            Interaction::new("a", "b", "retweet"),
+            Interaction::new("a", "b", "mention"),
+            Interaction::new("b", "a", "retweet"),
+        ];
+        interactions[0].timestamp = Some(50);
+        interactions[1].timestamp = Some(20);
+        interactions[1].weight = 2.5;
+
+        let graph = InteractionGraph::from_interactions(&interactions);
+        assert_eq!(graph.node_count(), 2);
+        assert_eq!(graph.edge_count(), 3);
+
+        let (a, b) = (graph.node("a").unwrap(), graph.node("b").unwrap());
+        let retweets = graph
+            .graph()
+            .edges_connecting(a, b)
+            .find(|edge| edge.weight().kind == "retweet")
+            .unwrap();
+        assert_eq!(retweets.weight().weight, 3.5);
+        assert_eq!(retweets.weight().count, 2);
+        assert_eq!(retweets.weight().first_seen, Some(20));
+        assert_eq!(retweets.weight().last_seen, Some(50));
+    }
+
+    #[test]
+    fn finds_components_in_the_sample() {
+        let graph = InteractionGraph::from_interactions(&sample());
+        let components = graph.strongly_connected_components();
+        let sizes: usize = components.iter().map(Vec::len).sum();
+        assert_eq!(sizes, graph.node_count());
+
+        let journalists = components
+            .iter()
+            .find(|component| component.contains(&"journalist1"))
+            .unwrap();
+        assert!(journalists.contains(&"journalist2") && journalists.contains(&"journalist3"));
+    }
+}
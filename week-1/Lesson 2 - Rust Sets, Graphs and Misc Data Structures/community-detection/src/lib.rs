@@ -1,3 +1,32 @@
+//! Community detection over Twitter interaction graphs.
+//!
+//! Edge lists are loaded with [`interactions::load`] into an [`interactions::InteractionGraph`].
+//! Communities are found on its undirected view, [`undirected::UndirectedGraph`], with
+//! [`louvain::louvain`] or [`louvain::leiden`] and scored with [`partition::modularity`]. Faster
+//! alternatives are [`propagation::label_propagation`], [`components::weakly_connected_components`]
+//! and [`cores::core_numbers`]; [`report`] compares them all on one graph. [`coordination`] flags
+//! groups of accounts that repeatedly retweet or mention the same targets within minutes.
+//! [`export`] writes the graph and its communities for Graphviz and Gephi.
+//! [`TWITTER_USERNAMES`] is kept as a small sample dataset for trying the pipeline without a file.
+
+pub mod components;
+pub mod coordination;
+pub mod cores;
+pub mod export;
+pub mod interactions;
+pub mod louvain;
+pub mod partition;
+pub mod propagation;
+pub mod report;
+mod rng;
+pub mod undirected;
+
+pub use interactions::{Interaction, InteractionGraph};
+pub use louvain::{leiden, louvain, Communities};
+pub use partition::{modularity, Partition};
+pub use undirected::UndirectedGraph;
+
+/// Sample usernames. [`interactions::sample`] reads consecutive entries as user → mention pairs.
 pub const TWITTER_USERNAMES: [&str; 140] = [
     "blackmattersus",
     "bleepthepolice",
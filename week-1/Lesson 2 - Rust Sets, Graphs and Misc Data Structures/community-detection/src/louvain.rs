@@ -0,0 +1,508 @@
+//! Modularity-based community detection with the Louvain and Leiden algorithms.
+//!
+//! Both algorithms repeat two steps until nothing changes. First, nodes move one at a time to the
+//! neighbouring community that raises modularity the most. Then every community is collapsed into
+//! a single node and the next level works on that smaller graph.
+//!
+//! Louvain (Blondel et al., 2008) can leave a community held together only by a node that has
+//! since moved elsewhere, so its communities are not always connected. Leiden (Traag et al., 2019)
+//! adds a refinement step before collapsing: inside each community, nodes are regrouped into
+//! well-connected sub-communities, and those are collapsed instead. The communities it returns
+//! are therefore always connected. This implementation merges each node into the best
+//! sub-community greedily instead of picking one at random, so both algorithms are deterministic
+//! for a given [`Options::seed`].
+
+use std::collections::VecDeque;
+
+use crate::partition::{modularity, Partition};
+use crate::rng::Lcg;
+use crate::undirected::UndirectedGraph;
+
+/// Smallest modularity gain that counts as an improvement, so rounding noise cannot keep nodes
+/// moving back and forth forever.
+const MIN_GAIN: f64 = 1e-12;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Options {
+    /// Above 1 favours more, smaller communities; below 1 fewer, larger ones.
+    pub resolution: f64,
+    /// Seed for the order nodes are visited in.
+    pub seed: u64,
+    /// Stop after this many aggregation levels.
+    pub max_levels: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            resolution: 1.0,
+            seed: 1,
+            max_levels: 100,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Communities {
+    pub partition: Partition,
+    /// Modularity of `partition` on the input graph, at the resolution it was found with.
+    pub modularity: f64,
+    /// Aggregation levels the algorithm went through.
+    pub levels: usize,
+}
+
+/// The node-moving step on one level of the graph.
+struct Moving<'a> {
+    graph: &'a UndirectedGraph,
+    resolution: f64,
+    labels: Vec<usize>,
+    /// Summed degree of each community, indexed by label.
+    totals: Vec<f64>,
+    /// Scratch space: weight from the node being moved to each community, and which are set.
+    weights: Vec<f64>,
+    touched: Vec<usize>,
+}
+
+impl<'a> Moving<'a> {
+    /// `labels` must be numbered `0..graph.node_count()` or lower.
+    fn new(graph: &'a UndirectedGraph, labels: Vec<usize>, resolution: f64) -> Self {
+        let mut totals = vec![0.0; graph.node_count()];
+        for (node, &label) in labels.iter().enumerate() {
+            totals[label] += graph.degree(node);
+        }
+        Self {
+            graph,
+            resolution,
+            labels,
+            totals,
+            weights: vec![0.0; graph.node_count()],
+            touched: Vec::new(),
+        }
+    }
+
+    /// Moves `node` to the community with the highest modularity gain, preferring to stay on ties,
+    /// and returns its community afterwards.
+    fn move_node(&mut self, node: usize) -> usize {
+        let current = self.labels[node];
+        let degree = self.graph.degree(node);
+        self.totals[current] -= degree;
+
+        for &(neighbour, weight) in self.graph.neighbours(node) {
+            let community = self.labels[neighbour];
+            if self.weights[community] == 0.0 {
+                self.touched.push(community);
+            }
+            self.weights[community] += weight;
+        }
+
+        // Gain of joining community c, up to terms that do not depend on c.
+        let scale = self.resolution * degree / self.graph.total_weight();
+        let gain = |moving: &Self, c: usize| moving.weights[c] - scale * moving.totals[c];
+        let mut best = current;
+        let mut best_gain = gain(self, current);
+        for &community in &self.touched {
+            let candidate = gain(self, community);
+            if candidate > best_gain + MIN_GAIN {
+                best = community;
+                best_gain = candidate;
+            }
+        }
+
+        for community in self.touched.drain(..) {
+            self.weights[community] = 0.0;
+        }
+        self.totals[best] += degree;
+        self.labels[node] = best;
+        best
+    }
+
+    /// Louvain's moving step: sweeps over all nodes until a full sweep moves none. Returns whether
+    /// any node moved.
+    fn sweep(&mut self, order: &[usize]) -> bool {
+        let mut improved = false;
+        loop {
+            let mut moved = false;
+            for &node in order {
+                let before = self.labels[node];
+                if self.move_node(node) != before {
+                    moved = true;
+                }
+            }
+            if !moved {
+                return improved;
+            }
+            improved = true;
+        }
+    }
+
+    /// Leiden's fast moving step: only neighbours of a node that moved are visited again.
+    fn fast_sweep(&mut self, order: &[usize]) {
+        let mut queue: VecDeque<usize> = order.iter().copied().collect();
+        let mut queued = vec![true; self.graph.node_count()];
+        while let Some(node) = queue.pop_front() {
+            queued[node] = false;
+            let before = self.labels[node];
+            let after = self.move_node(node);
+            if after == before {
+                continue;
+            }
+            for &(neighbour, _) in self.graph.neighbours(node) {
+                if !queued[neighbour] && self.labels[neighbour] != after {
+                    queued[neighbour] = true;
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+    }
+}
+
+/// Louvain modularity optimisation on `graph`.
+pub fn louvain(graph: &UndirectedGraph, options: &Options) -> Communities {
+    let mut rng = Lcg(options.seed);
+    let mut level = graph.clone();
+    // The node of the current level that each input node has been collapsed into.
+    let mut membership: Vec<usize> = (0..graph.node_count()).collect();
+    let mut levels = 0;
+
+    while levels < options.max_levels && level.total_weight() > 0.0 {
+        let order = rng.shuffled(level.node_count());
+        let mut moving = Moving::new(
+            &level,
+            (0..level.node_count()).collect(),
+            options.resolution,
+        );
+        if !moving.sweep(&order) {
+            break;
+        }
+        let communities = Partition::from_labels(moving.labels);
+        for node in &mut membership {
+            *node = communities.community(*node);
+        }
+        level = level.aggregate(communities.labels(), communities.community_count());
+        levels += 1;
+    }
+
+    finish(graph, membership, levels, options)
+}
+
+/// Leiden modularity optimisation on `graph`. Every community it returns is connected.
+pub fn leiden(graph: &UndirectedGraph, options: &Options) -> Communities {
+    let mut rng = Lcg(options.seed);
+    let mut level = graph.clone();
+    let mut membership: Vec<usize> = (0..graph.node_count()).collect();
+    // The community of each node of the current level. Unlike Louvain, a new level starts from the
+    // communities found so far rather than from singletons.
+    let mut communities = Partition::singletons(graph.node_count());
+    let mut levels = 0;
+
+    while levels < options.max_levels && level.total_weight() > 0.0 {
+        let order = rng.shuffled(level.node_count());
+        let mut moving = Moving::new(&level, communities.labels().to_vec(), options.resolution);
+        moving.fast_sweep(&order);
+        communities = Partition::from_labels(moving.labels);
+        if communities.community_count() == level.node_count() {
+            break;
+        }
+
+        let refined = refine(&level, &communities, options.resolution, &order);
+        // If no node could join a sub-community, collapse the communities themselves so the next
+        // level is smaller and the loop makes progress.
+        let collapse = if refined.community_count() < level.node_count() {
+            refined
+        } else {
+            communities.clone()
+        };
+        let mut next_labels = vec![0; collapse.community_count()];
+        for (node, &part) in collapse.labels().iter().enumerate() {
+            next_labels[part] = communities.community(node);
+        }
+        for node in &mut membership {
+            *node = collapse.community(*node);
+        }
+        level = level.aggregate(collapse.labels(), collapse.community_count());
+        communities = Partition::from_labels(next_labels);
+        levels += 1;
+    }
+
+    let labels = membership
+        .into_iter()
+        .map(|node| communities.community(node))
+        .collect();
+    finish(graph, labels, levels, options)
+}
+
+/// Leiden's refinement: splits every community into well-connected sub-communities. Each node
+/// starts alone; a node that is still alone may join a sub-community of its own community when
+/// both are well connected to the rest of it and the move raises modularity.
+fn refine(
+    graph: &UndirectedGraph,
+    communities: &Partition,
+    resolution: f64,
+    order: &[usize],
+) -> Partition {
+    let total = graph.total_weight();
+    let n = graph.node_count();
+    let mut community_totals = vec![0.0; communities.community_count()];
+    for node in 0..n {
+        community_totals[communities.community(node)] += graph.degree(node);
+    }
+
+    let mut labels: Vec<usize> = (0..n).collect();
+    let mut sizes = vec![1usize; n];
+    let mut totals: Vec<f64> = (0..n).map(|node| graph.degree(node)).collect();
+    // Weight from each sub-community to the rest of its community.
+    let mut external: Vec<f64> = (0..n)
+        .map(|node| {
+            graph
+                .neighbours(node)
+                .iter()
+                .filter(|&&(neighbour, _)| {
+                    communities.community(neighbour) == communities.community(node)
+                })
+                .map(|&(_, weight)| weight)
+                .sum()
+        })
+        .collect();
+    let well_connected = |external: f64, total_degree: f64, community_total: f64| {
+        external >= resolution * total_degree * (community_total - total_degree) / total
+    };
+
+    let mut weights = vec![0.0; n];
+    let mut touched = Vec::new();
+    for &node in order {
+        let community = communities.community(node);
+        let degree = graph.degree(node);
+        if sizes[labels[node]] > 1
+            || !well_connected(external[node], degree, community_totals[community])
+        {
+            continue;
+        }
+
+        for &(neighbour, weight) in graph.neighbours(node) {
+            if communities.community(neighbour) != community {
+                continue;
+            }
+            let part = labels[neighbour];
+            if weights[part] == 0.0 {
+                touched.push(part);
+            }
+            weights[part] += weight;
+        }
+
+        let mut best = None;
+        let mut best_gain = MIN_GAIN;
+        for &part in &touched {
+            if !well_connected(external[part], totals[part], community_totals[community]) {
+                continue;
+            }
+            let gain = weights[part] - resolution * degree * totals[part] / total;
+            if gain > best_gain {
+                best = Some(part);
+                best_gain = gain;
+            }
+        }
+
+        if let Some(part) = best {
+            let own = labels[node];
+            sizes[own] -= 1;
+            totals[own] -= degree;
+            sizes[part] += 1;
+            totals[part] += degree;
+            // Edges between the node and the sub-community become internal; the node's other
+            // edges inside the community become external.
+            external[part] += external[node] - 2.0 * weights[part];
+            external[own] = 0.0;
+            labels[node] = part;
+        }
+        for part in touched.drain(..) {
+            weights[part] = 0.0;
+        }
+    }
+    Partition::from_labels(labels)
+}
+
+fn finish(
+    graph: &UndirectedGraph,
+    labels: Vec<usize>,
+    levels: usize,
+    options: &Options,
+) -> Communities {
+    let partition = Partition::from_labels(labels);
+    Communities {
+        modularity: modularity(graph, &partition, options.resolution),
+        partition,
+        levels,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `groups` planted communities of `size` nodes. Pairs inside a community are connected with
+    /// probability `p_in`, pairs across communities with `p_out`.
+    fn planted_partition(
+        groups: usize,
+        size: usize,
+        p_in: f64,
+        p_out: f64,
+        seed: u64,
+    ) -> (UndirectedGraph, Partition) {
+        let mut rng = Lcg(seed);
+        let n = groups * size;
+        let mut edges = Vec::new();
+        for a in 0..n {
+            for b in a + 1..n {
+                let p = if a / size == b / size { p_in } else { p_out };
+                if (rng.below(1_000_000) as f64) < p * 1_000_000.0 {
+                    edges.push((a, b, 1.0));
+                }
+            }
+        }
+        (
+            UndirectedGraph::from_edges(n, edges),
+            Partition::from_labels((0..n).map(|node| node / size)),
+        )
+    }
+
+    /// `count` cliques of `size` nodes, each joined to the next by one edge.
+    fn ring_of_cliques(count: usize, size: usize) -> (UndirectedGraph, Partition) {
+        let mut edges = Vec::new();
+        for clique in 0..count {
+            let first = clique * size;
+            for a in first..first + size {
+                for b in a + 1..first + size {
+                    edges.push((a, b, 1.0));
+                }
+            }
+            edges.push((first, ((clique + 1) % count) * size + 1, 1.0));
+        }
+        (
+            UndirectedGraph::from_edges(count * size, edges),
+            Partition::from_labels((0..count * size).map(|node| node / size)),
+        )
+    }
+
+    fn is_connected(graph: &UndirectedGraph, nodes: &[usize], partition: &Partition) -> bool {
+        let community = partition.community(nodes[0]);
+        let mut seen = vec![false; graph.node_count()];
+        let mut stack = vec![nodes[0]];
+        seen[nodes[0]] = true;
+        let mut reached = 0;
+        while let Some(node) = stack.pop() {
+            reached += 1;
+            for &(neighbour, _) in graph.neighbours(node) {
+                if !seen[neighbour] && partition.community(neighbour) == community {
+                    seen[neighbour] = true;
+                    stack.push(neighbour);
+                }
+            }
+        }
+        reached == nodes.len()
+    }
+
+    #[test]
+    fn recovers_a_ring_of_cliques() {
+        let (graph, planted) = ring_of_cliques(6, 5);
+        for found in [
+            louvain(&graph, &Options::default()),
+            leiden(&graph, &Options::default()),
+        ] {
+            assert_eq!(found.partition, planted);
+            let expected = modularity(&graph, &planted, 1.0);
+            assert!((found.modularity - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn recovers_planted_partitions() {
+        for seed in 1..=5 {
+            let (graph, planted) = planted_partition(4, 20, 0.4, 0.02, seed);
+            let planted_modularity = modularity(&graph, &planted, 1.0);
+            for found in [
+                louvain(
+                    &graph,
+                    &Options {
+                        seed,
+                        ..Options::default()
+                    },
+                ),
+                leiden(
+                    &graph,
+                    &Options {
+                        seed,
+                        ..Options::default()
+                    },
+                ),
+            ] {
+                assert_eq!(found.partition, planted, "seed {}", seed);
+                assert!(found.modularity >= planted_modularity - 1e-12);
+                assert!(found.levels >= 1);
+            }
+        }
+    }
+
+    #[test]
+    fn uses_edge_weights() {
+        // A square whose heavy sides decide the communities.
+        let graph =
+            UndirectedGraph::from_edges(4, [(0, 1, 10.0), (1, 2, 1.0), (2, 3, 10.0), (3, 0, 1.0)]);
+        let expected = Partition::from_labels([0, 0, 1, 1]);
+        assert_eq!(louvain(&graph, &Options::default()).partition, expected);
+        assert_eq!(leiden(&graph, &Options::default()).partition, expected);
+    }
+
+    #[test]
+    fn resolution_controls_community_size() {
+        let (graph, _) = planted_partition(4, 20, 0.4, 0.02, 9);
+        let coarse = Options {
+            resolution: 0.05,
+            ..Options::default()
+        };
+        let fine = Options {
+            resolution: 4.0,
+            ..Options::default()
+        };
+        for algorithm in [louvain, leiden] {
+            let default = algorithm(&graph, &Options::default())
+                .partition
+                .community_count();
+            assert!(algorithm(&graph, &coarse).partition.community_count() < default);
+            assert!(algorithm(&graph, &fine).partition.community_count() > default);
+        }
+    }
+
+    #[test]
+    fn leiden_communities_are_connected() {
+        for seed in 1..=5 {
+            let (graph, _) = planted_partition(5, 12, 0.3, 0.05, seed);
+            let found = leiden(
+                &graph,
+                &Options {
+                    seed,
+                    ..Options::default()
+                },
+            );
+            for nodes in found.partition.communities() {
+                assert!(
+                    is_connected(&graph, &nodes, &found.partition),
+                    "seed {}",
+                    seed
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn graphs_without_edges_stay_apart() {
+        let graph = UndirectedGraph::from_edges(3, []);
+        for found in [
+            louvain(&graph, &Options::default()),
+            leiden(&graph, &Options::default()),
+        ] {
+            assert_eq!(found.partition, Partition::singletons(3));
+            assert_eq!(found.modularity, 0.0);
+            assert_eq!(found.levels, 0);
+        }
+    }
+}
@@ -94,41 +94,324 @@
 //! These applications demonstrate the versatility of community detection 
 //! algorithms in providing insights into complex systems across various fields.
 //! 
-use community_detection::TWITTER_USERNAMES;
-use petgraph::algo::kosaraju_scc;
-use petgraph::prelude::*;
-use std::collections::HashMap;
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
 
-fn main() {
-    // Create a new directed Graph
-    let mut graph = DiGraph::<&str, &str>::new();
+use clap::{Args, Parser, Subcommand};
+use community_detection::coordination::{self, Similarity};
+use community_detection::export::{self, Format};
+use community_detection::louvain::Options;
+use community_detection::report::{self, Algorithm, Settings};
+use community_detection::{
+    components, cores, interactions, leiden, louvain, propagation, Communities, InteractionGraph,
+    Partition, UndirectedGraph,
+};
+use petgraph::graph::NodeIndex;
 
-    // Create a HashMap to store node indices by user name
-    let mut nodes = HashMap::new();
+#[derive(Parser)]
+#[clap(
+    version = "1.0",
+    about = "Finds communities in Twitter interaction graphs"
+)]
+struct Opts {
+    /// Edge list with source, target and optional type, weight and timestamp columns (.csv or
+    /// .tsv). Without it the bundled sample is used.
+    #[clap(short, long, global = true)]
+    edges: Option<PathBuf>,
+    /// Also write the graph, with the communities found if any, to this file
+    #[clap(short = 'x', long, global = true)]
+    export: Option<PathBuf>,
+    /// Export format: dot, graphml, gexf or json (defaults to the file extension)
+    #[clap(short, long, global = true)]
+    format: Option<Format>,
+    #[clap(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print the strongly connected components (default)
+    Scc,
+    /// Find communities with the Louvain algorithm
+    Louvain(ModularityArgs),
+    /// Find communities with the Leiden algorithm, which keeps every community connected
+    Leiden(ModularityArgs),
+    /// Find communities with asynchronous label propagation
+    LabelPropagation {
+        /// Seed for the order nodes are visited in
+        #[clap(short, long, default_value_t = 1)]
+        seed: u64,
+        /// Also write a user,community CSV to this file
+        #[clap(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Print the weakly connected components
+    Wcc {
+        /// Also write a user,community CSV to this file
+        #[clap(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Print the users grouped by core number, densest core first
+    KCore,
+    /// Run several algorithms and compare their partitions
+    Compare {
+        /// Comma-separated algorithms: scc, wcc, label-propagation, k-core, louvain, leiden
+        #[clap(
+            short,
+            long,
+            value_delimiter = ',',
+            default_value = "scc,wcc,label-propagation,k-core,louvain,leiden"
+        )]
+        algorithms: Vec<Algorithm>,
+        /// Resolution for Louvain, Leiden and the modularity column
+        #[clap(short, long, default_value_t = 1.0)]
+        resolution: f64,
+        /// Seed for Louvain, Leiden and label propagation
+        #[clap(short, long, default_value_t = 1)]
+        seed: u64,
+        /// Core whose connected components k-core reports as communities
+        #[clap(short, default_value_t = 2)]
+        k: usize,
+    },
+    /// Rank groups of users that repeatedly retweet or mention the same targets within minutes
+    Coordination {
+        /// Largest gap in seconds between two interactions of the same burst
+        #[clap(short, long, default_value_t = 600)]
+        window: i64,
+        /// Comma-separated interaction types to look at (all by default)
+        #[clap(short, long, value_delimiter = ',')]
+        kinds: Vec<String>,
+        /// Similarity between two users' bursts: jaccard or cosine
+        #[clap(short, long, default_value = "jaccard")]
+        similarity: Similarity,
+        /// Smallest similarity that links two users
+        #[clap(short, long, default_value_t = 0.3)]
+        threshold: f64,
+        /// Fewest bursts two users must share to be linked
+        #[clap(long, default_value_t = 2)]
+        min_shared: usize,
+        /// Smallest group to report
+        #[clap(long, default_value_t = 3)]
+        min_size: usize,
+    },
+}
+
+#[derive(Args)]
+struct ModularityArgs {
+    /// Above 1 favours more, smaller communities; below 1 fewer, larger ones
+    #[clap(short, long, default_value_t = 1.0)]
+    resolution: f64,
+    /// Seed for the order nodes are visited in
+    #[clap(short, long, default_value_t = 1)]
+    seed: u64,
+    /// Also write a user,community CSV to this file
+    #[clap(short, long)]
+    output: Option<PathBuf>,
+}
+
+impl ModularityArgs {
+    fn options(&self) -> Options {
+        Options {
+            resolution: self.resolution,
+            seed: self.seed,
+            ..Options::default()
+        }
+    }
+}
 
-    // Iterate over the data to populate the graph
-    for window in TWITTER_USERNAMES.windows(2) {
-        let user = window[0];
-        let mention = window[1];
+fn main() -> Result<(), Box<dyn Error>> {
+    let opts: Opts = Opts::parse();
 
-        // Add the nodes to the graph and to the HashMap
-        let user_node = *nodes.entry(user).or_insert_with(|| graph.add_node(user));
-        let mention_node = *nodes
-            .entry(mention)
-            .or_insert_with(|| graph.add_node(mention));
+    // Load the interactions and build a directed graph with one node per user
+    let interactions = match &opts.edges {
+        Some(path) => interactions::load(path)?,
+        None => interactions::sample(),
+    };
+    let graph = InteractionGraph::from_interactions(&interactions);
+    println!(
+        "{} interactions between {} users merged into {} edges",
+        interactions.len(),
+        graph.node_count(),
+        graph.edge_count()
+    );
 
-        // Add the edge to the graph
-        graph.add_edge(user_node, mention_node, "retweets");
+    // Check the export format before spending time on the analysis
+    let format = match (&opts.export, opts.format) {
+        (Some(path), None) => Some(
+            Format::from_path(path)
+                .ok_or("cannot tell the export format from the file extension, pass --format")?,
+        ),
+        (_, format) => format,
+    };
+
+    let communities = match opts.command.unwrap_or(Command::Scc) {
+        Command::Scc => {
+            // Use the Kosaraju's algorithm to detect strongly connected components
+            for usernames in graph.strongly_connected_components() {
+                println!("{} nodes in community discovered", usernames.len());
+                println!("{:?}", usernames);
+            }
+            Some(components::strongly_connected_components(&graph))
+        }
+        Command::Louvain(args) => {
+            let communities = louvain(&UndirectedGraph::from_interactions(&graph), &args.options());
+            print_communities(&graph, &communities, args.output.as_deref())?;
+            Some(communities.partition)
+        }
+        Command::Leiden(args) => {
+            let communities = leiden(&UndirectedGraph::from_interactions(&graph), &args.options());
+            print_communities(&graph, &communities, args.output.as_deref())?;
+            Some(communities.partition)
+        }
+        Command::LabelPropagation { seed, output } => {
+            let undirected = UndirectedGraph::from_interactions(&graph);
+            let partition = propagation::label_propagation(&undirected, seed);
+            print_partition(&graph, &partition, output.as_deref())?;
+            Some(partition)
+        }
+        Command::Wcc { output } => {
+            let undirected = UndirectedGraph::from_interactions(&graph);
+            let partition = components::weakly_connected_components(&undirected);
+            print_partition(&graph, &partition, output.as_deref())?;
+            Some(partition)
+        }
+        Command::KCore => {
+            let cores = cores::core_numbers(&UndirectedGraph::from_interactions(&graph));
+            for core in (0..=cores.iter().copied().max().unwrap_or(0)).rev() {
+                let usernames: Vec<&str> = (0..cores.len())
+                    .filter(|&node| cores[node] == core)
+                    .map(|node| graph.username(NodeIndex::new(node)))
+                    .collect();
+                if !usernames.is_empty() {
+                    println!("core {} ({} users): {:?}", core, usernames.len(), usernames);
+                }
+            }
+            None
+        }
+        Command::Compare {
+            algorithms,
+            resolution,
+            seed,
+            k,
+        } => {
+            let settings = Settings {
+                modularity: Options {
+                    resolution,
+                    seed,
+                    ..Options::default()
+                },
+                k,
+            };
+            print!(
+                "{}",
+                report::render(&report::compare(&graph, &algorithms, &settings))
+            );
+            None
+        }
+        Command::Coordination {
+            window,
+            kinds,
+            similarity,
+            threshold,
+            min_shared,
+            min_size,
+        } => {
+            if interactions.iter().all(|i| i.timestamp.is_none()) {
+                return Err("coordination needs timestamps, pass an edge list with -e".into());
+            }
+            let options = coordination::Options {
+                window,
+                kinds,
+                similarity,
+                threshold,
+                min_shared,
+                min_size,
+            };
+            let groups = coordination::detect(&interactions, &options);
+            if groups.is_empty() {
+                println!("no coordinated groups found");
+            }
+            for (rank, group) in groups.iter().enumerate() {
+                let targets: Vec<String> = group
+                    .targets
+                    .iter()
+                    .map(|(target, count)| format!("{} ({})", target, count))
+                    .collect();
+                println!(
+                    "#{} score {:.3}: {} users, density {:.2}, {} similarity {:.2}, {} shared bursts",
+                    rank + 1,
+                    group.score,
+                    group.users.len(),
+                    group.density,
+                    similarity,
+                    group.cohesion,
+                    group.bursts
+                );
+                println!("   users: {:?}", group.users);
+                println!("   targets: {}", targets.join(", "));
+            }
+            None
+        }
+    };
+
+    // Write the graph with the communities found for Graphviz or Gephi
+    if let (Some(path), Some(format)) = (&opts.export, format) {
+        let mut file = BufWriter::new(File::create(path)?);
+        export::write(&graph, communities.as_ref(), format, &mut file)?;
+        file.flush()?;
+        println!("wrote {} to {}", format, path.display());
     }
 
-    // Use the Kosaraju's algorithm to detect strongly connected components
-    let scc = kosaraju_scc(&graph);
-    for component in scc {
-        println!("{} nodes in community discovered", component.len());
-        let usernames: Vec<&str> = component
+    Ok(())
+}
+
+fn print_communities(
+    graph: &InteractionGraph,
+    communities: &Communities,
+    output: Option<&Path>,
+) -> Result<(), Box<dyn Error>> {
+    print_partition(graph, &communities.partition, output)?;
+    println!(
+        "modularity {:.4} after {} levels",
+        communities.modularity, communities.levels
+    );
+    Ok(())
+}
+
+/// Prints the communities, largest first, and optionally writes every user's community to a CSV.
+/// Communities keep their [`Partition`] label so the listing, the CSV and any export agree.
+fn print_partition(
+    graph: &InteractionGraph,
+    partition: &Partition,
+    output: Option<&Path>,
+) -> Result<(), Box<dyn Error>> {
+    let mut members: Vec<(usize, Vec<usize>)> =
+        partition.communities().into_iter().enumerate().collect();
+    members.sort_by_key(|(_, nodes)| std::cmp::Reverse(nodes.len()));
+    for (community, nodes) in &members {
+        let usernames: Vec<&str> = nodes
             .iter()
-            .map(|&node_index| graph[node_index])
+            .map(|&node| graph.username(NodeIndex::new(node)))
             .collect();
-        println!("{:?}", usernames);
+        println!(
+            "community {} ({} users): {:?}",
+            community,
+            nodes.len(),
+            usernames
+        );
+    }
+    println!("{} communities", members.len());
+
+    if let Some(path) = output {
+        let mut writer = csv::Writer::from_path(path)?;
+        writer.write_record(["user", "community"])?;
+        for node in 0..partition.node_count() {
+            let community = partition.community(node).to_string();
+            writer.write_record([graph.username(NodeIndex::new(node)), community.as_str()])?;
+        }
+        writer.flush()?;
     }
+    Ok(())
 }
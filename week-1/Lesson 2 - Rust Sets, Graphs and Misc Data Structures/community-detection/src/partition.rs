@@ -0,0 +1,141 @@
+//! Partitions of a graph's nodes into communities, and their modularity.
+
+use std::collections::HashMap;
+
+use crate::undirected::UndirectedGraph;
+
+/// The community of every node. Labels are renumbered `0..count` in order of first appearance, so
+/// two partitions that group the nodes the same way are equal.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Partition {
+    labels: Vec<usize>,
+    count: usize,
+}
+
+impl Partition {
+    pub fn from_labels(labels: impl IntoIterator<Item = usize>) -> Self {
+        let mut renumbered = HashMap::new();
+        let labels: Vec<usize> = labels
+            .into_iter()
+            .map(|label| {
+                let next = renumbered.len();
+                *renumbered.entry(label).or_insert(next)
+            })
+            .collect();
+        Self {
+            labels,
+            count: renumbered.len(),
+        }
+    }
+
+    /// Every node in a community of its own.
+    pub fn singletons(node_count: usize) -> Self {
+        Self::from_labels(0..node_count)
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn community_count(&self) -> usize {
+        self.count
+    }
+
+    pub fn community(&self, node: usize) -> usize {
+        self.labels[node]
+    }
+
+    pub fn labels(&self) -> &[usize] {
+        &self.labels
+    }
+
+    /// The nodes of each community, indexed by label.
+    pub fn communities(&self) -> Vec<Vec<usize>> {
+        let mut communities = vec![Vec::new(); self.count];
+        for (node, &label) in self.labels.iter().enumerate() {
+            communities[label].push(node);
+        }
+        communities
+    }
+
+    pub fn sizes(&self) -> Vec<usize> {
+        let mut sizes = vec![0; self.count];
+        for &label in &self.labels {
+            sizes[label] += 1;
+        }
+        sizes
+    }
+}
+
+/// Newman–Girvan modularity of `partition` with a `resolution` parameter:
+///
+/// `Q = Σ_c [ in_c / 2m - resolution · (tot_c / 2m)² ]`
+///
+/// where `in_c` sums the adjacency matrix over pairs of nodes inside `c` and `tot_c` is the summed
+/// degree of `c`. A resolution above 1 favours smaller communities, below 1 larger ones. A graph
+/// without edges has modularity 0.
+pub fn modularity(graph: &UndirectedGraph, partition: &Partition, resolution: f64) -> f64 {
+    let total = graph.total_weight();
+    if total == 0.0 {
+        return 0.0;
+    }
+    let mut inside = vec![0.0; partition.community_count()];
+    let mut degrees = vec![0.0; partition.community_count()];
+    for node in 0..graph.node_count() {
+        let community = partition.community(node);
+        degrees[community] += graph.degree(node);
+        inside[community] += graph.self_loop(node);
+        for &(neighbour, weight) in graph.neighbours(node) {
+            if partition.community(neighbour) == community {
+                inside[community] += weight;
+            }
+        }
+    }
+    inside
+        .iter()
+        .zip(&degrees)
+        .map(|(inside, degree)| inside / total - resolution * (degree / total).powi(2))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renumbers_labels() {
+        let partition = Partition::from_labels([7, 3, 7, 9]);
+        assert_eq!(partition.labels(), &[0, 1, 0, 2]);
+        assert_eq!(partition.community_count(), 3);
+        assert_eq!(partition.communities(), vec![vec![0, 2], vec![1], vec![3]]);
+        assert_eq!(partition.sizes(), vec![2, 1, 1]);
+        assert_eq!(partition, Partition::from_labels([1, 0, 1, 2]));
+    }
+
+    #[test]
+    fn modularity_of_two_triangles() {
+        // Two triangles joined by the edge 2-3.
+        let graph = UndirectedGraph::from_edges(
+            6,
+            [
+                (0, 1, 1.0),
+                (1, 2, 1.0),
+                (0, 2, 1.0),
+                (3, 4, 1.0),
+                (4, 5, 1.0),
+                (3, 5, 1.0),
+                (2, 3, 1.0),
+            ],
+        );
+        let split = Partition::from_labels([0, 0, 0, 1, 1, 1]);
+        // Each triangle: in = 6, tot = 7, 2m = 14.
+        let expected = 2.0 * (6.0 / 14.0 - (7.0_f64 / 14.0).powi(2));
+        assert!((modularity(&graph, &split, 1.0) - expected).abs() < 1e-12);
+
+        let together = Partition::from_labels([0; 6]);
+        assert!(modularity(&graph, &together, 1.0).abs() < 1e-12);
+        assert!(modularity(&graph, &Partition::singletons(6), 1.0) < 0.0);
+        let no_edges = UndirectedGraph::from_edges(2, []);
+        assert_eq!(modularity(&no_edges, &Partition::singletons(2), 1.0), 0.0);
+    }
+}
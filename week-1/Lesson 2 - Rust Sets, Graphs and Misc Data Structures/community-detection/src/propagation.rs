@@ -0,0 +1,100 @@
+//! Asynchronous label propagation (Raghavan, Albert and Kumara, 2007).
+//!
+//! Every node starts with a label of its own. Nodes are visited in random order and each takes
+//! the label carrying the most edge weight among its neighbours, seeing the labels already updated
+//! earlier in the same sweep. Propagation stops once a whole sweep changes nothing, which means
+//! every node holds one of its heaviest labels. Each sweep is linear in the number of edges, so it
+//! scales to graphs where modularity optimisation is too slow. The price is stability: another
+//! seed can give a different partition.
+
+use crate::partition::Partition;
+use crate::rng::Lcg;
+use crate::undirected::UndirectedGraph;
+
+/// Sweeps after which propagation stops even if labels are still changing, which can happen when
+/// ties keep flipping.
+pub const MAX_SWEEPS: usize = 100;
+
+pub fn label_propagation(graph: &UndirectedGraph, seed: u64) -> Partition {
+    let n = graph.node_count();
+    let mut rng = Lcg(seed);
+    let mut labels: Vec<usize> = (0..n).collect();
+    // Scratch space: weight of each label around the node being updated, and which are set.
+    let mut weights = vec![0.0; n];
+    let mut touched = Vec::new();
+    let mut best = Vec::new();
+
+    for _ in 0..MAX_SWEEPS {
+        let mut changed = false;
+        for node in rng.shuffled(n) {
+            for &(neighbour, weight) in graph.neighbours(node) {
+                let label = labels[neighbour];
+                if weights[label] == 0.0 {
+                    touched.push(label);
+                }
+                weights[label] += weight;
+            }
+
+            let heaviest = touched
+                .iter()
+                .map(|&label| weights[label])
+                .fold(0.0, f64::max);
+            best.extend(
+                touched
+                    .iter()
+                    .copied()
+                    .filter(|&label| weights[label] == heaviest),
+            );
+            // Keep the current label on a tie so that the stopping condition can be reached.
+            if !best.is_empty() && !best.contains(&labels[node]) {
+                best.sort_unstable();
+                labels[node] = best[rng.below(best.len())];
+                changed = true;
+            }
+
+            for label in touched.drain(..) {
+                weights[label] = 0.0;
+            }
+            best.clear();
+        }
+        if !changed {
+            break;
+        }
+    }
+    Partition::from_labels(labels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn separates_cliques_joined_by_single_edges() {
+        // Three 5-cliques in a ring.
+        let mut edges = Vec::new();
+        for clique in 0..3 {
+            let first = clique * 5;
+            for a in first..first + 5 {
+                for b in a + 1..first + 5 {
+                    edges.push((a, b, 1.0));
+                }
+            }
+            edges.push((first, (first + 6) % 15, 1.0));
+        }
+        let graph = UndirectedGraph::from_edges(15, edges);
+        let expected = Partition::from_labels((0..15).map(|node| node / 5));
+        for seed in 1..=10 {
+            assert_eq!(label_propagation(&graph, seed), expected, "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn labels_never_cross_components() {
+        let graph = UndirectedGraph::from_edges(5, [(0, 1, 1.0), (1, 2, 3.0), (3, 4, 1.0)]);
+        let partition = label_propagation(&graph, 7);
+        assert_eq!(partition, Partition::from_labels([0, 0, 0, 1, 1]));
+
+        let isolated = UndirectedGraph::from_edges(3, [(1, 1, 1.0)]);
+        assert_eq!(label_propagation(&isolated, 7), Partition::singletons(3));
+    }
+}
@@ -0,0 +1,293 @@
+//! Runs several algorithms on one graph and compares the partitions they produce.
+//!
+//! For each algorithm the report lists how many communities it found, how large they are, the
+//! modularity of the result and how long it took. A matrix of normalised mutual information then
+//! shows how far any two partitions agree, from 0 (independent) to 1 (identical up to renaming).
+
+use std::collections::HashMap;
+use std::fmt::{self, Write};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use crate::components::{strongly_connected_components, weakly_connected_components};
+use crate::cores::core_partition;
+use crate::louvain::{self, leiden, louvain};
+use crate::partition::{modularity, Partition};
+use crate::propagation::label_propagation;
+use crate::undirected::UndirectedGraph;
+use crate::InteractionGraph;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    Scc,
+    Wcc,
+    LabelPropagation,
+    KCore,
+    Louvain,
+    Leiden,
+}
+
+impl Algorithm {
+    pub const ALL: [Algorithm; 6] = [
+        Algorithm::Scc,
+        Algorithm::Wcc,
+        Algorithm::LabelPropagation,
+        Algorithm::KCore,
+        Algorithm::Louvain,
+        Algorithm::Leiden,
+    ];
+}
+
+impl FromStr for Algorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "scc" => Ok(Algorithm::Scc),
+            "wcc" => Ok(Algorithm::Wcc),
+            "label-propagation" | "lpa" => Ok(Algorithm::LabelPropagation),
+            "k-core" | "kcore" => Ok(Algorithm::KCore),
+            "louvain" => Ok(Algorithm::Louvain),
+            "leiden" => Ok(Algorithm::Leiden),
+            _ => Err(format!(
+                "unknown algorithm '{}', expected scc, wcc, label-propagation, k-core, louvain or leiden",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Algorithm::Scc => "scc",
+            Algorithm::Wcc => "wcc",
+            Algorithm::LabelPropagation => "label-propagation",
+            Algorithm::KCore => "k-core",
+            Algorithm::Louvain => "louvain",
+            Algorithm::Leiden => "leiden",
+        };
+        f.pad(name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Settings {
+    /// Resolution and seed for Louvain and Leiden. The resolution is also used to score every
+    /// partition and the seed also drives label propagation.
+    pub modularity: louvain::Options,
+    /// Core used by [`Algorithm::KCore`].
+    pub k: usize,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            modularity: louvain::Options::default(),
+            k: 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Run {
+    pub algorithm: Algorithm,
+    pub partition: Partition,
+    pub modularity: f64,
+    pub elapsed: Duration,
+}
+
+/// Runs `algorithm`. `undirected` must be the undirected view of `graph`.
+pub fn run(
+    algorithm: Algorithm,
+    graph: &InteractionGraph,
+    undirected: &UndirectedGraph,
+    settings: &Settings,
+) -> Run {
+    let started = Instant::now();
+    let partition = match algorithm {
+        Algorithm::Scc => strongly_connected_components(graph),
+        Algorithm::Wcc => weakly_connected_components(undirected),
+        Algorithm::LabelPropagation => label_propagation(undirected, settings.modularity.seed),
+        Algorithm::KCore => core_partition(undirected, settings.k),
+        Algorithm::Louvain => louvain(undirected, &settings.modularity).partition,
+        Algorithm::Leiden => leiden(undirected, &settings.modularity).partition,
+    };
+    let elapsed = started.elapsed();
+    Run {
+        algorithm,
+        modularity: modularity(undirected, &partition, settings.modularity.resolution),
+        partition,
+        elapsed,
+    }
+}
+
+/// Runs every algorithm in `algorithms` on `graph`, in order.
+pub fn compare(
+    graph: &InteractionGraph,
+    algorithms: &[Algorithm],
+    settings: &Settings,
+) -> Vec<Run> {
+    let undirected = UndirectedGraph::from_interactions(graph);
+    algorithms
+        .iter()
+        .map(|&algorithm| run(algorithm, graph, &undirected, settings))
+        .collect()
+}
+
+/// Normalised mutual information `2 I(A; B) / (H(A) + H(B))` between two partitions of the same
+/// nodes. Two partitions that both put everything in one community score 1.
+///
+/// # Panics
+///
+/// Panics if the partitions cover a different number of nodes.
+pub fn normalized_mutual_information(a: &Partition, b: &Partition) -> f64 {
+    assert_eq!(
+        a.node_count(),
+        b.node_count(),
+        "partitions cover a different number of nodes"
+    );
+    let n = a.node_count() as f64;
+    let entropy = |sizes: Vec<usize>| -> f64 {
+        sizes
+            .into_iter()
+            .map(|size| size as f64 / n)
+            .map(|p| -p * p.ln())
+            .sum()
+    };
+    let (entropy_a, entropy_b) = (entropy(a.sizes()), entropy(b.sizes()));
+    if entropy_a + entropy_b == 0.0 {
+        return 1.0;
+    }
+
+    let mut joint: HashMap<(usize, usize), usize> = HashMap::new();
+    for (&x, &y) in a.labels().iter().zip(b.labels()) {
+        *joint.entry((x, y)).or_default() += 1;
+    }
+    let (sizes_a, sizes_b) = (a.sizes(), b.sizes());
+    let mutual: f64 = joint
+        .into_iter()
+        .map(|((x, y), count)| {
+            let p = count as f64 / n;
+            p * (p * n * n / (sizes_a[x] * sizes_b[y]) as f64).ln()
+        })
+        .sum();
+    (2.0 * mutual / (entropy_a + entropy_b)).clamp(0.0, 1.0)
+}
+
+/// Renders a summary row per run followed by the pairwise normalised mutual information.
+pub fn render(runs: &[Run]) -> String {
+    let name_width = runs
+        .iter()
+        .map(|run| run.algorithm.to_string().len())
+        .max()
+        .unwrap_or(0)
+        .max("algorithm".len());
+
+    let mut report = String::new();
+    writeln!(
+        report,
+        "{:<name_width$} {:>11} {:>7} {:>10} {:>10} {:>10}",
+        "algorithm", "communities", "largest", "singletons", "modularity", "time"
+    )
+    .unwrap();
+    for run in runs {
+        let sizes = run.partition.sizes();
+        writeln!(
+            report,
+            "{:<name_width$} {:>11} {:>7} {:>10} {:>10.4} {:>10}",
+            run.algorithm,
+            sizes.len(),
+            sizes.iter().max().unwrap_or(&0),
+            sizes.iter().filter(|&&size| size == 1).count(),
+            run.modularity,
+            format!("{:.2?}", run.elapsed),
+        )
+        .unwrap();
+    }
+
+    writeln!(report, "\nnormalised mutual information").unwrap();
+    write!(report, "{:<name_width$}", "").unwrap();
+    for run in runs {
+        write!(report, " {:>5}", run.algorithm).unwrap();
+    }
+    writeln!(report).unwrap();
+    for a in runs {
+        write!(report, "{:<name_width$}", a.algorithm).unwrap();
+        for b in runs {
+            let width = b.algorithm.to_string().len().max(5);
+            let nmi = normalized_mutual_information(&a.partition, &b.partition);
+            write!(report, " {:>width$.3}", nmi).unwrap();
+        }
+        writeln!(report).unwrap();
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Interaction;
+
+    #[test]
+    fn mutual_information_ignores_label_names() {
+        let a = Partition::from_labels([0, 0, 1, 1, 2, 2]);
+        let renamed = Partition::from_labels([5, 5, 3, 3, 4, 4]);
+        assert!((normalized_mutual_information(&a, &renamed) - 1.0).abs() < 1e-12);
+
+        let halves = Partition::from_labels([0, 0, 1, 1]);
+        let crossed = Partition::from_labels([0, 1, 0, 1]);
+        assert!(normalized_mutual_information(&halves, &crossed).abs() < 1e-12);
+        let merged = Partition::from_labels([0, 0, 0, 0, 1, 1]);
+        let partial = normalized_mutual_information(&a, &merged);
+        assert!(partial > 0.0 && partial < 1.0);
+
+        let together = Partition::from_labels([0; 6]);
+        assert_eq!(normalized_mutual_information(&together, &together), 1.0);
+        assert_eq!(normalized_mutual_information(&together, &a), 0.0);
+    }
+
+    #[test]
+    fn parses_algorithm_names() {
+        for algorithm in Algorithm::ALL {
+            assert_eq!(algorithm.to_string().parse(), Ok(algorithm));
+        }
+        assert_eq!("LPA".parse(), Ok(Algorithm::LabelPropagation));
+        assert!("girvan-newman".parse::<Algorithm>().is_err());
+    }
+
+    #[test]
+    fn compares_every_algorithm() {
+        // Two groups that retweet each other in a cycle plus both diagonals, joined by one
+        // mention.
+        let mut interactions = Vec::new();
+        for group in [["a", "b", "c", "d"], ["w", "x", "y", "z"]] {
+            for i in 0..4 {
+                interactions.push(Interaction::new(group[i], group[(i + 1) % 4], "retweet"));
+            }
+            interactions.push(Interaction::new(group[0], group[2], "retweet"));
+            interactions.push(Interaction::new(group[1], group[3], "retweet"));
+        }
+        interactions.push(Interaction::new("a", "w", "mention"));
+        let graph = InteractionGraph::from_interactions(&interactions);
+
+        let runs = compare(&graph, &Algorithm::ALL, &Settings::default());
+        let counts: Vec<usize> = runs
+            .iter()
+            .map(|run| run.partition.community_count())
+            .collect();
+        // scc, wcc, label propagation, k-core, louvain, leiden. The mention joins both groups
+        // into one component and one 2-core.
+        assert_eq!(counts, vec![2, 1, 2, 1, 2, 2]);
+        let louvain = &runs[4];
+        assert!(louvain.modularity > 0.4);
+        for run in [&runs[0], &runs[2], &runs[5]] {
+            let nmi = normalized_mutual_information(&run.partition, &louvain.partition);
+            assert!((nmi - 1.0).abs() < 1e-12, "{}", run.algorithm);
+        }
+
+        let report = render(&runs);
+        assert!(report.starts_with("algorithm         communities largest singletons"));
+        assert_eq!(report.lines().count(), 1 + runs.len() + 2 + 1 + runs.len());
+    }
+}
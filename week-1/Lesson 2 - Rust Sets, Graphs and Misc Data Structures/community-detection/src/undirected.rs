@@ -0,0 +1,177 @@
+//! The undirected, weighted view of an interaction graph that the community algorithms work on.
+//!
+//! Direction and interaction type are dropped: the weight between two users is the sum of the
+//! weights of every edge between them, either way. Nodes are numbered `0..n` in the same order as
+//! the [`InteractionGraph`] node indices, so results map straight back to usernames.
+//!
+//! Weights follow the symmetric adjacency matrix `A`: an edge of weight `w` between `i` and `j`
+//! adds `w` to both `A[i][j]` and `A[j][i]`, and a self-loop adds `2w` to `A[i][i]`. A node's
+//! degree is its row sum and [`UndirectedGraph::total_weight`] is the sum of the whole matrix,
+//! `2m`.
+
+use std::collections::HashMap;
+
+use petgraph::visit::EdgeRef;
+
+use crate::InteractionGraph;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UndirectedGraph {
+    /// Neighbours of each node with the summed weight, without self-loops.
+    adjacency: Vec<Vec<(usize, f64)>>,
+    /// `A[i][i]` for each node.
+    self_loops: Vec<f64>,
+    degrees: Vec<f64>,
+    total_weight: f64,
+}
+
+impl UndirectedGraph {
+    /// Builds the graph from `(a, b, weight)` edges between nodes `0..node_count`. Repeated edges
+    /// add up.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an edge refers to a node outside `0..node_count`.
+    pub fn from_edges(
+        node_count: usize,
+        edges: impl IntoIterator<Item = (usize, usize, f64)>,
+    ) -> Self {
+        let mut rows: Vec<HashMap<usize, f64>> = vec![HashMap::new(); node_count];
+        let mut self_loops = vec![0.0; node_count];
+        for (a, b, weight) in edges {
+            assert!(
+                a < node_count && b < node_count,
+                "edge ({}, {}) is outside the {} nodes",
+                a,
+                b,
+                node_count
+            );
+            if a == b {
+                self_loops[a] += 2.0 * weight;
+            } else {
+                *rows[a].entry(b).or_default() += weight;
+                *rows[b].entry(a).or_default() += weight;
+            }
+        }
+        Self::from_rows(rows, self_loops)
+    }
+
+    fn from_rows(rows: Vec<HashMap<usize, f64>>, self_loops: Vec<f64>) -> Self {
+        let adjacency: Vec<Vec<(usize, f64)>> = rows
+            .into_iter()
+            .map(|row| {
+                let mut neighbours: Vec<(usize, f64)> = row.into_iter().collect();
+                // Sorted so that every algorithm visits neighbours in the same order on every run.
+                neighbours.sort_by_key(|&(neighbour, _)| neighbour);
+                neighbours
+            })
+            .collect();
+        let degrees: Vec<f64> = adjacency
+            .iter()
+            .zip(&self_loops)
+            .map(|(neighbours, self_loop)| {
+                self_loop + neighbours.iter().map(|&(_, weight)| weight).sum::<f64>()
+            })
+            .collect();
+        let total_weight = degrees.iter().sum();
+        Self {
+            adjacency,
+            self_loops,
+            degrees,
+            total_weight,
+        }
+    }
+
+    pub fn from_interactions(graph: &InteractionGraph) -> Self {
+        let graph = graph.graph();
+        Self::from_edges(
+            graph.node_count(),
+            graph.edge_references().map(|edge| {
+                (
+                    edge.source().index(),
+                    edge.target().index(),
+                    edge.weight().weight,
+                )
+            }),
+        )
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    /// Neighbours of `node` other than itself, with the weight between them.
+    pub fn neighbours(&self, node: usize) -> &[(usize, f64)] {
+        &self.adjacency[node]
+    }
+
+    pub fn self_loop(&self, node: usize) -> f64 {
+        self.self_loops[node]
+    }
+
+    pub fn degree(&self, node: usize) -> f64 {
+        self.degrees[node]
+    }
+
+    /// Sum of every entry of the adjacency matrix, twice the total edge weight.
+    pub fn total_weight(&self) -> f64 {
+        self.total_weight
+    }
+
+    /// Collapses every community of `labels` (numbered `0..count`) into one node. Edges inside a
+    /// community become its self-loop, so degrees and the total weight are preserved.
+    pub fn aggregate(&self, labels: &[usize], count: usize) -> Self {
+        let mut rows: Vec<HashMap<usize, f64>> = vec![HashMap::new(); count];
+        let mut self_loops = vec![0.0; count];
+        for node in 0..self.node_count() {
+            let community = labels[node];
+            self_loops[community] += self.self_loops[node];
+            for &(neighbour, weight) in &self.adjacency[node] {
+                let other = labels[neighbour];
+                // Each undirected edge is seen from both ends, which matches `A`.
+                if other == community {
+                    self_loops[community] += weight;
+                } else {
+                    *rows[community].entry(other).or_default() += weight;
+                }
+            }
+        }
+        Self::from_rows(rows, self_loops)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Interaction;
+
+    #[test]
+    fn symmetrises_directed_interactions() {
+        let interactions = [
+            Interaction::new("a", "b", "retweet"),
+            Interaction::new("b", "a", "mention"),
+            Interaction::new("b", "c", "retweet"),
+            Interaction::new("c", "c", "retweet"),
+        ];
+        let graph =
+            UndirectedGraph::from_interactions(&InteractionGraph::from_interactions(&interactions));
+        assert_eq!(graph.node_count(), 3);
+        assert_eq!(graph.neighbours(0), &[(1, 2.0)]);
+        assert_eq!(graph.neighbours(1), &[(0, 2.0), (2, 1.0)]);
+        assert_eq!(graph.self_loop(2), 2.0);
+        assert_eq!(graph.degree(2), 3.0);
+        assert_eq!(graph.total_weight(), 8.0);
+    }
+
+    #[test]
+    fn aggregation_preserves_degrees() {
+        let graph =
+            UndirectedGraph::from_edges(4, [(0, 1, 1.0), (1, 2, 2.0), (2, 3, 1.0), (3, 3, 0.5)]);
+        let aggregate = graph.aggregate(&[0, 0, 1, 1], 2);
+        assert_eq!(aggregate.neighbours(0), &[(1, 2.0)]);
+        assert_eq!(aggregate.self_loop(0), 2.0);
+        assert_eq!(aggregate.self_loop(1), 3.0);
+        assert_eq!(aggregate.degree(0), graph.degree(0) + graph.degree(1));
+        assert_eq!(aggregate.total_weight(), graph.total_weight());
+    }
+}
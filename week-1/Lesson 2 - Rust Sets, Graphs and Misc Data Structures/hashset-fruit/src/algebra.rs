@@ -0,0 +1,213 @@
+//! Set algebra over fruit baskets, for both `HashSet` and `BTreeSet`.
+//!
+//! Both set types expose the same operations through [`SetAlgebra`]. Results always come back as
+//! sorted vectors, so a `HashSet` prints the same output from one run to the next.
+
+use std::collections::{BTreeSet, HashSet};
+use std::fmt;
+use std::hash::Hash;
+use std::str::FromStr;
+
+pub trait SetAlgebra<T: Ord + Clone>: FromIterator<T> {
+    fn union_sorted(&self, other: &Self) -> Vec<T>;
+    fn intersection_sorted(&self, other: &Self) -> Vec<T>;
+    /// Items of `self` that are not in `other`.
+    fn difference_sorted(&self, other: &Self) -> Vec<T>;
+    fn symmetric_difference_sorted(&self, other: &Self) -> Vec<T>;
+    fn is_subset_of(&self, other: &Self) -> bool;
+
+    /// Size of the intersection divided by the size of the union. Two empty sets are identical,
+    /// so their similarity is 1.
+    fn jaccard(&self, other: &Self) -> f64 {
+        let union = self.union_sorted(other).len();
+        if union == 0 {
+            return 1.0;
+        }
+        self.intersection_sorted(other).len() as f64 / union as f64
+    }
+}
+
+fn sorted<'a, T: Ord + Clone + 'a>(items: impl Iterator<Item = &'a T>) -> Vec<T> {
+    let mut items: Vec<T> = items.cloned().collect();
+    items.sort();
+    items
+}
+
+impl<T: Ord + Hash + Clone> SetAlgebra<T> for HashSet<T> {
+    fn union_sorted(&self, other: &Self) -> Vec<T> {
+        sorted(self.union(other))
+    }
+
+    fn intersection_sorted(&self, other: &Self) -> Vec<T> {
+        sorted(self.intersection(other))
+    }
+
+    fn difference_sorted(&self, other: &Self) -> Vec<T> {
+        sorted(self.difference(other))
+    }
+
+    fn symmetric_difference_sorted(&self, other: &Self) -> Vec<T> {
+        sorted(self.symmetric_difference(other))
+    }
+
+    fn is_subset_of(&self, other: &Self) -> bool {
+        self.is_subset(other)
+    }
+}
+
+// A `BTreeSet` already iterates in order, so `sorted` has nothing left to do.
+impl<T: Ord + Clone> SetAlgebra<T> for BTreeSet<T> {
+    fn union_sorted(&self, other: &Self) -> Vec<T> {
+        self.union(other).cloned().collect()
+    }
+
+    fn intersection_sorted(&self, other: &Self) -> Vec<T> {
+        self.intersection(other).cloned().collect()
+    }
+
+    fn difference_sorted(&self, other: &Self) -> Vec<T> {
+        self.difference(other).cloned().collect()
+    }
+
+    fn symmetric_difference_sorted(&self, other: &Self) -> Vec<T> {
+        self.symmetric_difference(other).cloned().collect()
+    }
+
+    fn is_subset_of(&self, other: &Self) -> bool {
+        self.is_subset(other)
+    }
+}
+
+/// Which set type the operations run on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    Hash,
+    BTree,
+}
+
+impl FromStr for Backend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "hash" | "hashset" => Ok(Backend::Hash),
+            "btree" | "btreeset" => Ok(Backend::BTree),
+            other => Err(format!(
+                "unknown backend '{}', expected hash or btree",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Backend::Hash => write!(f, "HashSet"),
+            Backend::BTree => write!(f, "BTreeSet"),
+        }
+    }
+}
+
+/// Every operation applied to a pair of baskets `a` and `b`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub union: Vec<String>,
+    pub intersection: Vec<String>,
+    pub a_minus_b: Vec<String>,
+    pub b_minus_a: Vec<String>,
+    pub symmetric_difference: Vec<String>,
+    pub jaccard: f64,
+    pub a_subset_of_b: bool,
+    pub b_subset_of_a: bool,
+}
+
+impl Comparison {
+    pub fn of<S: SetAlgebra<String>>(a: &S, b: &S) -> Self {
+        Self {
+            union: a.union_sorted(b),
+            intersection: a.intersection_sorted(b),
+            a_minus_b: a.difference_sorted(b),
+            b_minus_a: b.difference_sorted(a),
+            symmetric_difference: a.symmetric_difference_sorted(b),
+            jaccard: a.jaccard(b),
+            a_subset_of_b: a.is_subset_of(b),
+            b_subset_of_a: b.is_subset_of(a),
+        }
+    }
+}
+
+impl Backend {
+    /// Builds both baskets as this backend's set type and compares them. Duplicates in the input
+    /// are collapsed.
+    pub fn compare(self, a: &[String], b: &[String]) -> Comparison {
+        match self {
+            Backend::Hash => Comparison::of(
+                &a.iter().cloned().collect::<HashSet<_>>(),
+                &b.iter().cloned().collect::<HashSet<_>>(),
+            ),
+            Backend::BTree => Comparison::of(
+                &a.iter().cloned().collect::<BTreeSet<_>>(),
+                &b.iter().cloned().collect::<BTreeSet<_>>(),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn basket(fruits: &[&str]) -> Vec<String> {
+        fruits.iter().map(|fruit| fruit.to_string()).collect()
+    }
+
+    #[test]
+    fn compares_two_baskets() {
+        let a = basket(&["Pear", "Apple", "Fig", "Apple"]);
+        let b = basket(&["Fig", "Cherry", "Apple"]);
+        let comparison = Backend::Hash.compare(&a, &b);
+
+        assert_eq!(
+            comparison.union,
+            basket(&["Apple", "Cherry", "Fig", "Pear"])
+        );
+        assert_eq!(comparison.intersection, basket(&["Apple", "Fig"]));
+        assert_eq!(comparison.a_minus_b, basket(&["Pear"]));
+        assert_eq!(comparison.b_minus_a, basket(&["Cherry"]));
+        assert_eq!(comparison.symmetric_difference, basket(&["Cherry", "Pear"]));
+        assert_eq!(comparison.jaccard, 0.5);
+        assert!(!comparison.a_subset_of_b && !comparison.b_subset_of_a);
+    }
+
+    #[test]
+    fn backends_agree() {
+        let a = basket(&["Plum", "Date", "Grape", "Banana", "Orange"]);
+        let b = basket(&["Orange", "Banana", "Loquat"]);
+        assert_eq!(
+            Backend::Hash.compare(&a, &b),
+            Backend::BTree.compare(&a, &b)
+        );
+    }
+
+    #[test]
+    fn subsets_and_empty_baskets() {
+        let small = basket(&["Fig"]);
+        let large = basket(&["Fig", "Date"]);
+        let comparison = Backend::BTree.compare(&small, &large);
+        assert!(comparison.a_subset_of_b);
+        assert!(!comparison.b_subset_of_a);
+
+        let empty = Backend::Hash.compare(&[], &[]);
+        assert_eq!(empty.jaccard, 1.0);
+        assert!(empty.a_subset_of_b && empty.b_subset_of_a);
+        assert_eq!(Backend::Hash.compare(&small, &[]).jaccard, 0.0);
+    }
+
+    #[test]
+    fn parses_backends() {
+        assert_eq!("hash".parse(), Ok(Backend::Hash));
+        assert_eq!("BTreeSet".parse(), Ok(Backend::BTree));
+        assert!("vec".parse::<Backend>().is_err());
+    }
+}
@@ -0,0 +1,146 @@
+//! Named fruit baskets kept as plain text files in a directory.
+//!
+//! A basket file lists one fruit per line; commas also separate fruits, and blank lines and lines
+//! starting with `#` are skipped. The basket `summer` lives in `<dir>/summer.txt`.
+
+use std::error::Error;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+const EXTENSION: &str = "txt";
+
+/// Splits the contents of a basket file into fruit names, in the order they appear.
+pub fn parse(contents: &str) -> Vec<String> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .flat_map(|line| line.split(','))
+        .map(str::trim)
+        .filter(|fruit| !fruit.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[derive(Debug, Clone)]
+pub struct BasketStore {
+    dir: PathBuf,
+}
+
+impl BasketStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Names of the stored baskets, sorted. A missing directory holds no baskets.
+    pub fn names(&self) -> Result<Vec<String>, Box<dyn Error>> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err.into()),
+        };
+        let mut names = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(EXTENSION) {
+                continue;
+            }
+            if let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) {
+                names.push(name.to_string());
+            }
+        }
+        names.sort();
+        Ok(names)
+    }
+
+    pub fn load(&self, name: &str) -> Result<Vec<String>, Box<dyn Error>> {
+        let path = self.path(name)?;
+        let contents = fs::read_to_string(&path).map_err(|err| match err.kind() {
+            ErrorKind::NotFound => format!("no basket named '{}' in {}", name, self.dir.display()),
+            _ => format!("could not read {}: {}", path.display(), err),
+        })?;
+        Ok(parse(&contents))
+    }
+
+    /// Writes the basket sorted and without duplicates, replacing any basket with the same name.
+    pub fn save(&self, name: &str, fruits: &[String]) -> Result<PathBuf, Box<dyn Error>> {
+        let path = self.path(name)?;
+        let mut fruits = fruits.to_vec();
+        fruits.sort();
+        fruits.dedup();
+
+        fs::create_dir_all(&self.dir)?;
+        let mut contents = fruits.join("\n");
+        contents.push('\n');
+        fs::write(&path, contents)?;
+        Ok(path)
+    }
+
+    pub fn delete(&self, name: &str) -> Result<(), Box<dyn Error>> {
+        let path = self.path(name)?;
+        fs::remove_file(&path).map_err(|err| match err.kind() {
+            ErrorKind::NotFound => format!("no basket named '{}' in {}", name, self.dir.display()),
+            _ => format!("could not delete {}: {}", path.display(), err),
+        })?;
+        Ok(())
+    }
+
+    fn path(&self, name: &str) -> Result<PathBuf, Box<dyn Error>> {
+        let valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err(format!(
+                "invalid basket name '{}': use letters, digits, '-' and '_'",
+                name
+            )
+            .into());
+        }
+        Ok(self.dir.join(format!("{}.{}", name, EXTENSION)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_lines_commas_and_comments() {
+        let contents = "# summer basket\nApple, Fig\n\n  Strawberry Tree Berry  \nPear,\n";
+        assert_eq!(
+            parse(contents),
+            vec!["Apple", "Fig", "Strawberry Tree Berry", "Pear"]
+        );
+    }
+
+    #[test]
+    fn saves_loads_and_deletes_baskets() {
+        let dir = std::env::temp_dir().join(format!("hashset-fruit-{}", std::process::id()));
+        let store = BasketStore::new(&dir);
+        assert!(store.names().unwrap().is_empty());
+
+        let fruits: Vec<String> = ["Pear", "Apple", "Pear"].map(String::from).to_vec();
+        store.save("winter", &fruits).unwrap();
+        store.save("autumn", &fruits[..1]).unwrap();
+        assert_eq!(store.names().unwrap(), vec!["autumn", "winter"]);
+        assert_eq!(store.load("winter").unwrap(), vec!["Apple", "Pear"]);
+
+        store.delete("autumn").unwrap();
+        assert!(store.load("autumn").is_err());
+        assert!(store.delete("autumn").is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn rejects_names_that_escape_the_directory() {
+        let store = BasketStore::new("baskets");
+        assert!(store.load("../secrets").is_err());
+        assert!(store.save("", &[]).is_err());
+    }
+}
@@ -0,0 +1,10 @@
+//! Named fruit baskets and the set algebra used to compare them.
+//!
+//! Baskets are stored as text files by [`basket::BasketStore`] and compared with the operations in
+//! [`algebra`], running on either a `HashSet` or a `BTreeSet`.
+
+pub mod algebra;
+pub mod basket;
+
+pub use algebra::{Backend, Comparison, SetAlgebra};
+pub use basket::BasketStore;
@@ -70,33 +70,160 @@
 //! and potential insertion costs compared to the `HashSet`.
 //!
 
-use rand::seq::SliceRandom;
-use rand::thread_rng;
+use std::error::Error;
+use std::io::{self, BufRead};
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand};
+use fruit_catalogue::FRUITS;
+use hashset_fruit::{basket, Backend, BasketStore, Comparison};
+use rand::{thread_rng, Rng};
 use std::collections::{HashMap, HashSet};
 
-fn generate_fruit() -> &'static str {
-    let fruits = [
-        "Apple",
-        "Banana",
-        "Cherry",
-        "Date",
-        "Elderberry",
-        "Fig",
-        "Grape",
-        "Honeydew",
-    ];
-    let mut rng = thread_rng();
-    fruits.choose(&mut rng).unwrap()
+#[derive(Parser)]
+#[clap(
+    version = "1.0",
+    about = "Counts unique random fruits and compares named fruit baskets"
+)]
+struct Opts {
+    /// Directory holding the named baskets, one `<name>.txt` file each
+    #[clap(short, long, global = true, default_value = "baskets")]
+    dir: PathBuf,
+    /// Set type the operations run on: hash or btree
+    #[clap(short, long, global = true, default_value = "hash")]
+    backend: Backend,
+    #[clap(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Generate random fruits and count the unique ones (default)
+    Random {
+        /// Number of fruits to generate; asked for on stdin when missing
+        #[clap(short, long)]
+        count: Option<usize>,
+    },
+    /// Store a basket under a name, replacing any basket with that name
+    Save {
+        name: String,
+        /// Fruits to store, separated by spaces or commas
+        #[clap(required_unless_present = "file")]
+        fruits: Vec<String>,
+        /// Read the fruits from a basket file instead
+        #[clap(short, long, conflicts_with = "fruits")]
+        file: Option<PathBuf>,
+    },
+    /// List the stored baskets
+    List,
+    /// Print the fruits in a basket
+    Show { name: String },
+    /// Delete a stored basket
+    Delete { name: String },
+    /// Fruits in either basket
+    Union(Pair),
+    /// Fruits in both baskets
+    Intersection(Pair),
+    /// Fruits in the first basket but not in the second
+    Difference(Pair),
+    /// Fruits in exactly one of the baskets
+    SymmetricDifference(Pair),
+    /// Shared fruits divided by all fruits, from 0 to 1
+    Jaccard(Pair),
+    /// Whether the first basket is a subset of the second
+    Subset(Pair),
+    /// Every operation at once
+    Compare(Pair),
+}
+
+#[derive(Args)]
+struct Pair {
+    a: String,
+    b: String,
+}
+
+fn generate_fruit<R: Rng + ?Sized>(rng: &mut R) -> &'static str {
+    fruit_catalogue::choose(&FRUITS, rng)
+        .copied()
+        .expect("the catalogue is not empty")
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let opts: Opts = Opts::parse();
+    let store = BasketStore::new(&opts.dir);
+    let compare = |pair: &Pair| -> Result<Comparison, Box<dyn Error>> {
+        Ok(opts
+            .backend
+            .compare(&store.load(&pair.a)?, &store.load(&pair.b)?))
+    };
+
+    match opts.command.unwrap_or(Command::Random { count: None }) {
+        Command::Random { count } => {
+            let count = match count {
+                Some(count) => count,
+                None => read_user_input()?,
+            };
+            count_random_fruits(count);
+        }
+        Command::Save { name, fruits, file } => {
+            let fruits = match file {
+                Some(file) => basket::parse(&std::fs::read_to_string(file)?),
+                None => basket::parse(&fruits.join(",")),
+            };
+            let path = store.save(&name, &fruits)?;
+            println!("Saved basket '{}' to {}", name, path.display());
+        }
+        Command::List => {
+            for name in store.names()? {
+                println!("{}", name);
+            }
+        }
+        Command::Show { name } => print_fruits(&store.load(&name)?),
+        Command::Delete { name } => {
+            store.delete(&name)?;
+            println!("Deleted basket '{}'", name);
+        }
+        Command::Union(pair) => print_fruits(&compare(&pair)?.union),
+        Command::Intersection(pair) => print_fruits(&compare(&pair)?.intersection),
+        Command::Difference(pair) => print_fruits(&compare(&pair)?.a_minus_b),
+        Command::SymmetricDifference(pair) => print_fruits(&compare(&pair)?.symmetric_difference),
+        Command::Jaccard(pair) => println!("{:.4}", compare(&pair)?.jaccard),
+        Command::Subset(pair) => println!("{}", compare(&pair)?.a_subset_of_b),
+        Command::Compare(pair) => {
+            let comparison = compare(&pair)?;
+            let (a, b) = (&pair.a, &pair.b);
+            println!("Comparing '{}' and '{}' with {}", a, b, opts.backend);
+            println!("union: {:?}", comparison.union);
+            println!("intersection: {:?}", comparison.intersection);
+            println!("{} - {}: {:?}", a, b, comparison.a_minus_b);
+            println!("{} - {}: {:?}", b, a, comparison.b_minus_a);
+            println!(
+                "symmetric difference: {:?}",
+                comparison.symmetric_difference
+            );
+            println!("jaccard similarity: {:.4}", comparison.jaccard);
+            println!("{} is a subset of {}: {}", a, b, comparison.a_subset_of_b);
+            println!("{} is a subset of {}: {}", b, a, comparison.b_subset_of_a);
+        }
+    }
+
+    Ok(())
 }
 
-fn main() {
+fn print_fruits(fruits: &[String]) {
+    for fruit in fruits {
+        println!("{}", fruit);
+    }
+}
+
+fn count_random_fruits(number_of_fruits: usize) {
     let mut fruit_set = HashSet::new();
     let mut fruit_counter = HashMap::new();
-    let number_of_fruits = read_user_input(); // Challenge(1): Read the number of fruits from the user
+    let mut rng = thread_rng();
 
     println!("Generating {} random fruits...", number_of_fruits);
     for _ in 0..number_of_fruits {
-        let fruit = generate_fruit();
+        let fruit = generate_fruit(&mut rng);
         fruit_set.insert(fruit);
         fruit_counter
             .entry(fruit)
@@ -111,19 +238,20 @@ fn main() {
     ); // Challenge(3): Print the number of times each fruit was generated
 }
 
-// This function read a number of random fruits from the user (cmd line) and return this number
-fn read_user_input() -> usize {
+// Challenge(1): Read the number of fruits from the user (cmd line). Invalid input asks again
+// instead of quietly generating nothing; only the end of the input gives up.
+fn read_user_input() -> Result<usize, Box<dyn Error>> {
+    let stdin = io::stdin();
     let mut input = String::new();
-    println!("Enter the number of fruits you want to generate: ");
-    std::io::stdin()
-        .read_line(&mut input)
-        .expect("Failed to read line");
-    let input: usize = match input.trim().parse() {
-        Ok(num) => num,
-        Err(_) => {
-            println!("Error: Invalid input. Please enter a number.");
-            0
+    loop {
+        println!("Enter the number of fruits you want to generate: ");
+        input.clear();
+        if stdin.lock().read_line(&mut input)? == 0 {
+            return Err("no number of fruits was entered".into());
         }
-    };
-    input
+        match input.trim().parse() {
+            Ok(num) => return Ok(num),
+            Err(_) => println!("Error: Invalid input. Please enter a number."),
+        }
+    }
 }
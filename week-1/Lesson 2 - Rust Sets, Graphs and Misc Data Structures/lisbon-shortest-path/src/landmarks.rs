@@ -0,0 +1,127 @@
+//! Lisbon landmarks, their coordinates and the roads between them.
+//!
+//! Road lengths are in kilometres and never shorter than the great-circle distance between their
+//! ends, so [`Landmark::distance_km`] to the destination never overestimates the remaining
+//! distance and can guide A*.
+
+use petgraph::prelude::*;
+
+/// Mean Earth radius in kilometres.
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Landmark {
+    pub name: &'static str,
+    /// Degrees north.
+    pub latitude: f64,
+    /// Degrees east; Lisbon's are negative.
+    pub longitude: f64,
+}
+
+impl Landmark {
+    pub const fn new(name: &'static str, latitude: f64, longitude: f64) -> Self {
+        Self {
+            name,
+            latitude,
+            longitude,
+        }
+    }
+
+    /// Great-circle distance to `other` with the haversine formula.
+    pub fn distance_km(&self, other: &Landmark) -> f64 {
+        let (lat1, lat2) = (self.latitude.to_radians(), other.latitude.to_radians());
+        let half_dlat = (lat2 - lat1) / 2.0;
+        let half_dlon = (other.longitude - self.longitude).to_radians() / 2.0;
+        let a = half_dlat.sin().powi(2) + lat1.cos() * lat2.cos() * half_dlon.sin().powi(2);
+        2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+    }
+}
+
+pub type CityMap = Graph<Landmark, f64, Undirected>;
+
+pub const LANDMARKS: [Landmark; 10] = [
+    Landmark::new("Belem Tower", 38.6916, -9.2160),
+    Landmark::new("Jerónimos Monastery", 38.6979, -9.2068),
+    Landmark::new("LX Factory", 38.7034, -9.1787),
+    Landmark::new("Time Out Market", 38.7069, -9.1459),
+    Landmark::new("Commerce Square", 38.7076, -9.1365),
+    Landmark::new("Santa Justa Lift", 38.7121, -9.1394),
+    Landmark::new("Rossio Square", 38.7139, -9.1394),
+    Landmark::new("Lisbon Cathedral", 38.7099, -9.1334),
+    Landmark::new("São Jorge Castle", 38.7139, -9.1335),
+    Landmark::new("National Pantheon", 38.7150, -9.1249),
+];
+
+/// Roads as indexes into [`LANDMARKS`] and their length in kilometres.
+const ROADS: [(usize, usize, f64); 16] = [
+    (0, 1, 1.2), // Belem Tower - Jerónimos Monastery
+    (0, 2, 4.0), // Belem Tower - LX Factory
+    (0, 4, 7.5), // Belem Tower - Commerce Square
+    (1, 2, 3.0), // Jerónimos Monastery - LX Factory
+    (1, 4, 6.6), // Jerónimos Monastery - Commerce Square
+    (2, 4, 4.4), // LX Factory - Commerce Square
+    (2, 3, 3.2), // LX Factory - Time Out Market
+    (3, 4, 1.0), // Time Out Market - Commerce Square
+    (3, 6, 1.4), // Time Out Market - Rossio Square
+    (4, 7, 0.6), // Commerce Square - Lisbon Cathedral
+    (4, 5, 0.7), // Commerce Square - Santa Justa Lift
+    (5, 6, 0.3), // Santa Justa Lift - Rossio Square
+    (6, 8, 0.9), // Rossio Square - São Jorge Castle
+    (7, 8, 0.7), // Lisbon Cathedral - São Jorge Castle
+    (7, 9, 1.2), // Lisbon Cathedral - National Pantheon
+    (8, 9, 1.1), // São Jorge Castle - National Pantheon
+];
+
+/// The landmarks and roads as a graph whose node `i` is `LANDMARKS[i]`.
+pub fn lisbon() -> CityMap {
+    let mut graph = CityMap::with_capacity(LANDMARKS.len(), ROADS.len());
+    for landmark in LANDMARKS {
+        graph.add_node(landmark);
+    }
+    for &(a, b, km) in &ROADS {
+        graph.add_edge(NodeIndex::new(a), NodeIndex::new(b), km);
+    }
+    graph
+}
+
+/// The node of the landmark called `name`, ignoring case.
+pub fn find(graph: &CityMap, name: &str) -> Option<NodeIndex> {
+    graph
+        .node_indices()
+        .find(|&node| graph[node].name.to_lowercase() == name.to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measures_great_circle_distances() {
+        let tower = LANDMARKS[0];
+        assert_eq!(tower.distance_km(&tower), 0.0);
+        // One degree of latitude is about 111.2 km.
+        let north = Landmark::new("north", tower.latitude + 1.0, tower.longitude);
+        assert!((tower.distance_km(&north) - 111.19).abs() < 0.01);
+        let cathedral = LANDMARKS[7];
+        let km = tower.distance_km(&cathedral);
+        assert!((km - cathedral.distance_km(&tower)).abs() < 1e-12);
+        assert!(km > 7.0 && km < 7.5);
+    }
+
+    #[test]
+    fn roads_are_never_shorter_than_the_crow_flies() {
+        let graph = lisbon();
+        assert_eq!(graph.node_count(), LANDMARKS.len());
+        for edge in graph.edge_references() {
+            let (a, b) = (&graph[edge.source()], &graph[edge.target()]);
+            assert!(
+                *edge.weight() >= a.distance_km(b),
+                "{} - {}",
+                a.name,
+                b.name
+            );
+        }
+        assert_eq!(find(&graph, "lx factory"), Some(NodeIndex::new(2)));
+        assert_eq!(find(&graph, "Eiffel Tower"), None);
+    }
+}
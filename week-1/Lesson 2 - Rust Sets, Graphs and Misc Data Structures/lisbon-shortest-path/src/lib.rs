@@ -0,0 +1,8 @@
+//! Routes between Lisbon landmarks.
+//!
+//! [`landmarks`] holds the landmarks, their coordinates and the roads between them, and
+//! [`routing`] finds the shortest route, with A* guided by the straight-line distance, and
+//! alternative routes with Yen's algorithm.
+
+pub mod landmarks;
+pub mod routing;
@@ -118,36 +118,70 @@
 //! These applications demonstrate the broad utility of Dijkstra's algorithm in
 //! solving various shortest path problems across multiple domains.
 //! 
-use petgraph::algo::dijkstra;
-use petgraph::prelude::*;
+use std::error::Error;
 
-fn main() {
-    let mut graph = Graph::<&str, u32, Undirected>::new_undirected();
+use clap::Parser;
+use lisbon_shortest_path::landmarks::{self, CityMap};
+use lisbon_shortest_path::routing::{self, Route};
+use petgraph::graph::NodeIndex;
 
-    let belem_tower = graph.add_node("Belem Tower");
-    let monastery = graph.add_node("Jerónimos Monastery");
-    let lx_factory = graph.add_node("LX Factory");
-    let commerce_square = graph.add_node("Commerce Square");
-    let lisbon_cathedral = graph.add_node("Lisbon Cathedral");
+#[derive(Parser)]
+#[clap(
+    version = "1.0",
+    about = "Finds the shortest route and alternatives between Lisbon landmarks"
+)]
+struct Opts {
+    /// Landmark to start from
+    #[clap(default_value = "Belem Tower")]
+    from: String,
+    /// Landmark to go to
+    #[clap(default_value = "Lisbon Cathedral")]
+    to: String,
+    /// Number of alternative routes to list after the shortest one
+    #[clap(short, long, default_value_t = 2)]
+    alternatives: usize,
+}
 
-    graph.extend_with_edges([
-        (belem_tower, monastery, 1), // The distance from Belem Tower to Jerónimos Monastery is 1 km
-        (belem_tower, lx_factory, 3), // The distance from Belem Tower to LX Factory is 3 km
-        (belem_tower, commerce_square, 7), // The distance from Belem Tower to Commerce Square is 7 km
-        (monastery, lx_factory, 3), // The distance from Jerónimos Monastery to LX Factory is 3 km
-        (monastery, commerce_square, 6), // The distance from Jerónimos Monastery to Commerce Square is 6 km
-        (lx_factory, commerce_square, 5), // The distance from LX Factory to Commerce Square is 5 km
-        (commerce_square, lisbon_cathedral, 1), // The distance from Commerce Square to Lisbon Cathedral is 1 km
-    ]);
+fn main() -> Result<(), Box<dyn Error>> {
+    let opts: Opts = Opts::parse();
+    let graph = landmarks::lisbon();
+    let find = |name: &str| {
+        landmarks::find(&graph, name).ok_or_else(|| {
+            let known: Vec<&str> = graph.node_weights().map(|landmark| landmark.name).collect();
+            format!("unknown landmark '{}', try one of: {}", name, known.join(", "))
+        })
+    };
+    let (from, to) = (find(&opts.from)?, find(&opts.to)?);
+    let (start, goal) = (graph[from].name, graph[to].name);
 
-    let node_map = dijkstra(&graph, belem_tower, Some(lisbon_cathedral), |e| *e.weight());
+    // A* guided by the straight-line distance to the destination
+    let crow_flies = |node: NodeIndex| graph[node].distance_km(&graph[to]);
+    let Some(shortest) = routing::astar(&graph, from, to, crow_flies) else {
+        println!("No route found from {} to {}.", start, goal);
+        return Ok(());
+    };
 
-    if let Some(distance) = node_map.get(&lisbon_cathedral) {
+    // Yen's algorithm branches the alternatives off the A* route, which stays first
+    let routes = routing::alternatives(&graph, shortest, opts.alternatives + 1);
+    for (i, route) in routes.iter().enumerate() {
+        if i == 0 {
+            println!(
+                "The shortest route from {} to {} is {:.1} km:",
+                start, goal, route.distance
+            );
+        } else {
+            println!("Alternative {}: {:.1} km", i, route.distance);
+        }
+        print_legs(&graph, route);
+    }
+    Ok(())
+}
+
+fn print_legs(graph: &CityMap, route: &Route) {
+    for leg in &route.legs {
         println!(
-            "The shortest distance from Belem Tower to Lisbon Cathedral is {} km",
-            distance
+            "  {} -> {}: {:.1} km",
+            graph[leg.from].name, graph[leg.to].name, leg.distance
         );
-    } else {
-        println!("No route found from Belem Tower to Lisbon Cathedral.");
     }
-}
\ No newline at end of file
+}
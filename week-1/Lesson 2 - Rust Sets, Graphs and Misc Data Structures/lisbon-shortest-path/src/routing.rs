@@ -0,0 +1,364 @@
+//! Shortest routes with the full itinerary, not just the distance.
+//!
+//! petgraph's `dijkstra` returns the distance to every node but not the path that achieves it.
+//! The searches here record the edge each node was reached through and walk those back from the
+//! destination, so a [`Route`] lists every landmark passed and the length of every leg.
+//!
+//! [`astar`] is Dijkstra guided by a heuristic estimate of the remaining distance; with an
+//! estimate that never overshoots, such as the straight-line distance, it returns the same
+//! shortest route while settling fewer nodes. [`k_shortest_paths`] implements Yen's algorithm:
+//! each further route branches off one of the routes already found at a spur node, with the
+//! roads those routes took from there closed, so every route is loopless and no route repeats.
+//! [`alternatives`] runs it from a shortest route found beforehand.
+//!
+//! Edge weights must be non-negative.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashSet};
+
+use petgraph::prelude::*;
+use petgraph::EdgeType;
+
+/// One edge of a [`Route`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Leg {
+    pub from: NodeIndex,
+    pub to: NodeIndex,
+    pub edge: EdgeIndex,
+    pub distance: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Route {
+    /// Every node visited, from the start to the destination.
+    pub nodes: Vec<NodeIndex>,
+    /// The legs between consecutive nodes, one fewer than `nodes`.
+    pub legs: Vec<Leg>,
+    /// Summed length of the legs.
+    pub distance: f64,
+}
+
+impl Route {
+    fn from_legs(start: NodeIndex, legs: Vec<Leg>) -> Self {
+        let mut nodes = vec![start];
+        nodes.extend(legs.iter().map(|leg| leg.to));
+        let distance = legs.iter().map(|leg| leg.distance).sum();
+        Self {
+            nodes,
+            legs,
+            distance,
+        }
+    }
+
+    fn edges(&self) -> impl Iterator<Item = EdgeIndex> + '_ {
+        self.legs.iter().map(|leg| leg.edge)
+    }
+}
+
+/// The shortest route from `start` to `goal` found with Dijkstra's algorithm, or `None` if
+/// `goal` cannot be reached.
+pub fn dijkstra<N, Ty: EdgeType>(
+    graph: &Graph<N, f64, Ty>,
+    start: NodeIndex,
+    goal: NodeIndex,
+) -> Option<Route> {
+    search(graph, start, goal, |_| 0.0, &Closed::default())
+}
+
+/// The shortest route from `start` to `goal` found with A*. `estimate(node)` guesses the
+/// remaining distance from `node` to `goal`; the route is only guaranteed to be shortest if the
+/// guess never exceeds the true distance.
+pub fn astar<N, Ty: EdgeType>(
+    graph: &Graph<N, f64, Ty>,
+    start: NodeIndex,
+    goal: NodeIndex,
+    estimate: impl FnMut(NodeIndex) -> f64,
+) -> Option<Route> {
+    search(graph, start, goal, estimate, &Closed::default())
+}
+
+/// Up to `k` loopless routes from `start` to `goal`, shortest first, with Yen's algorithm.
+pub fn k_shortest_paths<N, Ty: EdgeType>(
+    graph: &Graph<N, f64, Ty>,
+    start: NodeIndex,
+    goal: NodeIndex,
+    k: usize,
+) -> Vec<Route> {
+    match dijkstra(graph, start, goal) {
+        Some(shortest) if k > 0 => alternatives(graph, shortest, k),
+        _ => Vec::new(),
+    }
+}
+
+/// Like [`k_shortest_paths`], starting from an already found `shortest` route, e.g. one found with
+/// [`astar`]. The result starts with `shortest` unless `k` is 0.
+pub fn alternatives<N, Ty: EdgeType>(
+    graph: &Graph<N, f64, Ty>,
+    shortest: Route,
+    k: usize,
+) -> Vec<Route> {
+    if k == 0 {
+        return Vec::new();
+    }
+    let (start, goal) = (
+        shortest.nodes[0],
+        *shortest.nodes.last().expect("a route has nodes"),
+    );
+    let mut routes = vec![shortest];
+    let mut candidates: Vec<Route> = Vec::new();
+    while routes.len() < k {
+        let last = routes.last().expect("at least one route");
+        for spur in 0..last.legs.len() {
+            let root = &last.legs[..spur];
+            let mut closed = Closed::default();
+            // Routes sharing this root may not leave the spur node the way they did before...
+            for route in &routes {
+                if route.legs.len() > spur
+                    && route.edges().take(spur).eq(root.iter().map(|leg| leg.edge))
+                {
+                    closed.edges.insert(route.legs[spur].edge);
+                }
+            }
+            // ...nor return to a node of the root, which would make a loop.
+            closed.nodes.extend(&last.nodes[..spur]);
+
+            let Some(detour) = search(graph, last.nodes[spur], goal, |_| 0.0, &closed) else {
+                continue;
+            };
+            let legs: Vec<Leg> = root.iter().chain(&detour.legs).copied().collect();
+            let candidate = Route::from_legs(start, legs);
+            let known = |route: &Route| route.edges().eq(candidate.edges());
+            if !routes.iter().any(known) && !candidates.iter().any(known) {
+                candidates.push(candidate);
+            }
+        }
+
+        let Some(best) = candidates
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| {
+                a.distance
+                    .total_cmp(&b.distance)
+                    .then(a.legs.len().cmp(&b.legs.len()))
+            })
+            .map(|(i, _)| i)
+        else {
+            break;
+        };
+        routes.push(candidates.swap_remove(best));
+    }
+    routes
+}
+
+/// Nodes and edges a search may not use.
+#[derive(Default)]
+struct Closed {
+    nodes: HashSet<NodeIndex>,
+    edges: HashSet<EdgeIndex>,
+}
+
+/// An entry of the search frontier, ordered so the max-heap pops the smallest estimate first.
+struct Frontier {
+    estimate: f64,
+    distance: f64,
+    node: NodeIndex,
+}
+
+impl PartialEq for Frontier {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Frontier {}
+
+impl PartialOrd for Frontier {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Frontier {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.estimate.total_cmp(&self.estimate)
+    }
+}
+
+fn search<N, Ty: EdgeType>(
+    graph: &Graph<N, f64, Ty>,
+    start: NodeIndex,
+    goal: NodeIndex,
+    mut estimate: impl FnMut(NodeIndex) -> f64,
+    closed: &Closed,
+) -> Option<Route> {
+    let mut distances = vec![f64::INFINITY; graph.node_count()];
+    let mut reached_by: Vec<Option<Leg>> = vec![None; graph.node_count()];
+    let mut frontier = BinaryHeap::new();
+    distances[start.index()] = 0.0;
+    frontier.push(Frontier {
+        estimate: estimate(start),
+        distance: 0.0,
+        node: start,
+    });
+    while let Some(Frontier { distance, node, .. }) = frontier.pop() {
+        if node == goal {
+            let mut legs = Vec::new();
+            let mut at = goal;
+            while let Some(leg) = reached_by[at.index()] {
+                legs.push(leg);
+                at = leg.from;
+            }
+            legs.reverse();
+            return Some(Route::from_legs(start, legs));
+        }
+        // A shorter way to this node was found after this entry was queued.
+        if distance > distances[node.index()] {
+            continue;
+        }
+        for edge in graph.edges(node) {
+            let next = edge.target();
+            if closed.edges.contains(&edge.id()) || closed.nodes.contains(&next) {
+                continue;
+            }
+            let through = distance + edge.weight();
+            if through < distances[next.index()] {
+                distances[next.index()] = through;
+                reached_by[next.index()] = Some(Leg {
+                    from: node,
+                    to: next,
+                    edge: edge.id(),
+                    distance: *edge.weight(),
+                });
+                frontier.push(Frontier {
+                    estimate: through + estimate(next),
+                    distance: through,
+                    node: next,
+                });
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::landmarks::{self, CityMap};
+
+    fn names(graph: &CityMap, route: &Route) -> Vec<&'static str> {
+        route.nodes.iter().map(|&node| graph[node].name).collect()
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn reconstructs_the_shortest_route() {
+        let graph = landmarks::lisbon();
+        let tower = landmarks::find(&graph, "Belem Tower").unwrap();
+        let cathedral = landmarks::find(&graph, "Lisbon Cathedral").unwrap();
+        let route = dijkstra(&graph, tower, cathedral).unwrap();
+        assert_eq!(
+            names(&graph, &route),
+            ["Belem Tower", "Commerce Square", "Lisbon Cathedral"]
+        );
+        let legs: Vec<f64> = route.legs.iter().map(|leg| leg.distance).collect();
+        assert_eq!(legs, [7.5, 0.6]);
+        assert_close(route.distance, 8.1);
+
+        // Roads are undirected, so the way back is the same route reversed.
+        let back = dijkstra(&graph, cathedral, tower).unwrap();
+        assert_eq!(
+            back.nodes,
+            route.nodes.iter().rev().copied().collect::<Vec<_>>()
+        );
+
+        let here = dijkstra(&graph, tower, tower).unwrap();
+        assert_eq!(
+            (here.nodes, here.legs, here.distance),
+            (vec![tower], vec![], 0.0)
+        );
+    }
+
+    #[test]
+    fn astar_agrees_with_dijkstra() {
+        let graph = landmarks::lisbon();
+        for start in graph.node_indices() {
+            for goal in graph.node_indices() {
+                let crow_flies = |node: NodeIndex| graph[node].distance_km(&graph[goal]);
+                let guided = astar(&graph, start, goal, crow_flies).unwrap();
+                let plain = dijkstra(&graph, start, goal).unwrap();
+                assert_close(guided.distance, plain.distance);
+                assert_eq!(guided.nodes.first(), Some(&start));
+                assert_eq!(guided.nodes.last(), Some(&goal));
+            }
+        }
+    }
+
+    #[test]
+    fn unreachable_goals_have_no_route() {
+        let mut graph = Graph::<&str, f64>::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        graph.add_edge(a, b, 1.0);
+        assert!(dijkstra(&graph, b, a).is_none());
+        assert!(astar(&graph, b, a, |_| 0.0).is_none());
+        assert!(k_shortest_paths(&graph, b, a, 3).is_empty());
+        assert_eq!(k_shortest_paths(&graph, a, b, 3).len(), 1);
+    }
+
+    #[test]
+    fn lists_alternative_loopless_routes_in_order() {
+        let graph = landmarks::lisbon();
+        let tower = landmarks::find(&graph, "Belem Tower").unwrap();
+        let cathedral = landmarks::find(&graph, "Lisbon Cathedral").unwrap();
+        let routes = k_shortest_paths(&graph, tower, cathedral, 4);
+        assert_eq!(routes.len(), 4);
+        assert_eq!(routes[0], dijkstra(&graph, tower, cathedral).unwrap());
+        assert_eq!(
+            names(&graph, &routes[1]),
+            [
+                "Belem Tower",
+                "Jerónimos Monastery",
+                "Commerce Square",
+                "Lisbon Cathedral"
+            ]
+        );
+        assert_close(routes[1].distance, 8.4);
+        for pair in routes.windows(2) {
+            assert!(pair[0].distance <= pair[1].distance);
+            assert_ne!(pair[0].nodes, pair[1].nodes);
+        }
+        for route in &routes {
+            let unique: HashSet<_> = route.nodes.iter().collect();
+            assert_eq!(unique.len(), route.nodes.len(), "{:?}", route.nodes);
+        }
+        assert!(k_shortest_paths(&graph, tower, cathedral, 0).is_empty());
+
+        // Starting from the A* route gives the same list without searching for it again.
+        let crow_flies = |node: NodeIndex| graph[node].distance_km(&graph[cathedral]);
+        let guided = astar(&graph, tower, cathedral, crow_flies).unwrap();
+        assert_eq!(alternatives(&graph, guided, 4), routes);
+    }
+
+    #[test]
+    fn keeps_parallel_roads_apart() {
+        // Two roads join a and b; Yen's algorithm must offer both.
+        let mut graph = Graph::<&str, f64, Undirected>::new_undirected();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        graph.extend_with_edges([(a, b, 1.0), (a, b, 2.0), (b, c, 1.0), (a, c, 5.0)]);
+        let distances: Vec<f64> = k_shortest_paths(&graph, a, c, 5)
+            .iter()
+            .map(|route| route.distance)
+            .collect();
+        assert_eq!(distances, [2.0, 3.0, 5.0]);
+    }
+}
@@ -0,0 +1,50 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use pagerank::{Csr, PageRank};
+
+/// 1M pages with 10 out-links each on average.
+const NODES: usize = 1_000_000;
+const EDGES: usize = 10_000_000;
+
+/// A graph whose in-degrees follow a power law, like links on the web: sources are uniform, and
+/// targets are skewed towards low node numbers, so a few pages collect most of the links. Some
+/// pages end up without out-links, which exercises the dangling-node handling.
+fn power_law_graph() -> Csr {
+    // Cheap LCG so generating the graph does not dominate the benchmark setup.
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut next = move || {
+        state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (state >> 11) as f64 / (1u64 << 53) as f64
+    };
+    let edges: Vec<(usize, usize)> = (0..EDGES)
+        .map(|_| {
+            let source = (next() * NODES as f64) as usize;
+            // Cubing a uniform draw gives P(target < x) = (x / n)^(1/3).
+            let target = (next().powi(3) * NODES as f64) as usize;
+            (source, target)
+        })
+        .collect();
+    Csr::from_edges(NODES, edges)
+}
+
+fn ranking_benchmarks(c: &mut Criterion) {
+    let graph = power_law_graph();
+    let settings = PageRank::new(0.85, 1e-8, 100);
+    let mut group = c.benchmark_group("pagerank");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(graph.edge_count() as u64));
+
+    group.bench_with_input(
+        BenchmarkId::new("push_sequential", NODES),
+        &graph,
+        |b, g| b.iter(|| settings.rank(g)),
+    );
+    group.bench_with_input(BenchmarkId::new("pull_parallel", NODES), &graph, |b, g| {
+        b.iter(|| settings.par_rank(g))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, ranking_benchmarks);
+criterion_main!(benches);
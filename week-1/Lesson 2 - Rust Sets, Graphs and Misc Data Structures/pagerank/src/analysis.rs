@@ -0,0 +1,202 @@
+//! One entry point for every ranking of a graph of named pages.
+//!
+//! [`LinkAnalysis`] keeps the page names next to the graph, so callers can ask for PageRank,
+//! PageRank personalised towards chosen seed pages, or HITS scores, and read the results back by
+//! name. [`Builder`] numbers the pages while their links are read.
+
+use std::collections::HashMap;
+
+use crate::csr::Csr;
+use crate::hits::{Hits, HitsScores};
+use crate::rank::{PageRank, Ranking};
+
+#[derive(Debug, Clone, Default)]
+pub struct LinkAnalysis {
+    graph: Csr,
+    names: Vec<String>,
+    index: HashMap<String, usize>,
+}
+
+impl LinkAnalysis {
+    /// Names the nodes of `graph`, node `i` getting `names[i]`.
+    pub fn new(graph: Csr, names: Vec<String>) -> Result<Self, String> {
+        if names.len() != graph.node_count() {
+            return Err(format!(
+                "{} names for {} nodes",
+                names.len(),
+                graph.node_count()
+            ));
+        }
+        let mut index = HashMap::new();
+        for (node, name) in names.iter().enumerate() {
+            if index.insert(name.clone(), node).is_some() {
+                return Err(format!("page '{}' is named twice", name));
+            }
+        }
+        Ok(Self {
+            graph,
+            names,
+            index,
+        })
+    }
+
+    /// Builds a weighted graph from `(source, target, weight)` edges between named pages. Pages
+    /// are numbered in order of first appearance.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a weight is negative or not finite.
+    pub fn from_named_edges<'a>(edges: impl IntoIterator<Item = (&'a str, &'a str, f64)>) -> Self {
+        let mut builder = Builder::default();
+        for (source, target, weight) in edges {
+            builder.link(source, target, weight);
+        }
+        builder.build()
+    }
+
+    pub fn graph(&self) -> &Csr {
+        &self.graph
+    }
+
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    pub fn name(&self, node: usize) -> &str {
+        &self.names[node]
+    }
+
+    pub fn node(&self, name: &str) -> Option<usize> {
+        self.index.get(name).copied()
+    }
+
+    pub fn pagerank(&self, settings: &PageRank) -> Ranking {
+        settings.rank(&self.graph)
+    }
+
+    /// PageRank with every random jump landing on one of `seeds`, chosen uniformly, so pages rank
+    /// by their importance as seen from the seeds.
+    pub fn personalized(&self, settings: &PageRank, seeds: &[&str]) -> Result<Ranking, String> {
+        if seeds.is_empty() {
+            return Err("personalized PageRank needs at least one seed page".to_string());
+        }
+        let mut teleport = vec![0.0; self.names.len()];
+        for seed in seeds {
+            let node = self
+                .node(seed)
+                .ok_or_else(|| format!("unknown page '{}'", seed))?;
+            teleport[node] = 1.0;
+        }
+        Ok(settings.personalized(&self.graph, &teleport))
+    }
+
+    pub fn hits(&self, settings: &Hits) -> HitsScores {
+        settings.scores(&self.graph)
+    }
+
+    /// Pairs every page with its score, highest first, ties broken by name.
+    pub fn ranked(&self, scores: &[f64]) -> Vec<(&str, f64)> {
+        let mut ranked: Vec<(&str, f64)> = self
+            .names
+            .iter()
+            .map(String::as_str)
+            .zip(scores.iter().copied())
+            .collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(b.0)));
+        ranked
+    }
+}
+
+/// Collects named pages and the links between them for a [`LinkAnalysis`]. Pages are numbered in
+/// order of first appearance.
+#[derive(Debug, Clone, Default)]
+pub struct Builder {
+    names: Vec<String>,
+    index: HashMap<String, usize>,
+    edges: Vec<(usize, usize, f64)>,
+}
+
+impl Builder {
+    /// Adds the page called `name` if it is new, and returns its node.
+    pub fn page(&mut self, name: &str) -> usize {
+        if let Some(&node) = self.index.get(name) {
+            return node;
+        }
+        self.names.push(name.to_string());
+        self.index.insert(name.to_string(), self.names.len() - 1);
+        self.names.len() - 1
+    }
+
+    /// Adds a link of the given weight, and any page it names that is new.
+    pub fn link(&mut self, source: &str, target: &str, weight: f64) {
+        let edge = (self.page(source), self.page(target), weight);
+        self.edges.push(edge);
+    }
+
+    /// # Panics
+    ///
+    /// Panics if a weight is negative or not finite.
+    pub fn build(self) -> LinkAnalysis {
+        LinkAnalysis {
+            graph: Csr::from_weighted_edges(self.names.len(), self.edges),
+            names: self.names,
+            index: self.index,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sports() -> LinkAnalysis {
+        LinkAnalysis::from_named_edges([
+            ("ESPN", "NFL", 1.0),
+            ("ESPN", "NBA", 1.0),
+            ("NFL", "ESPN", 1.0),
+            ("NBA", "ESPN", 1.0),
+            ("NBA", "UFC", 1.0),
+            ("UFC", "ESPN", 1.0),
+            ("MLB", "ESPN", 1.0),
+            ("MLB", "NFL", 1.0),
+        ])
+    }
+
+    #[test]
+    fn ranks_named_pages() {
+        let sites = sports();
+        assert_eq!(sites.names(), &["ESPN", "NFL", "NBA", "UFC", "MLB"]);
+        assert_eq!(sites.node("UFC"), Some(3));
+        let ranking = sites.pagerank(&PageRank::default());
+        let ranked = sites.ranked(&ranking.ranks);
+        assert_eq!(ranked[0].0, "ESPN");
+        assert_eq!(ranked[4].0, "MLB");
+
+        let hits = sites.hits(&Hits::default());
+        assert_eq!(sites.ranked(&hits.authorities)[0].0, "ESPN");
+        assert_eq!(sites.ranked(&hits.hubs)[0].0, "MLB");
+    }
+
+    #[test]
+    fn personalizes_towards_seed_pages() {
+        let sites = sports();
+        let settings = PageRank::default();
+        let global = sites.pagerank(&settings).ranks;
+        let from_ufc = sites.personalized(&settings, &["UFC"]).unwrap().ranks;
+        assert!(from_ufc[3] > global[3]);
+        assert_eq!(
+            sites.personalized(&settings, &["XFL"]).unwrap_err(),
+            "unknown page 'XFL'"
+        );
+        assert!(sites.personalized(&settings, &[]).is_err());
+    }
+
+    #[test]
+    fn names_must_match_the_graph() {
+        let graph = Csr::from_edges(2, [(0, 1)]);
+        assert!(LinkAnalysis::new(graph.clone(), vec!["a".to_string()]).is_err());
+        assert!(LinkAnalysis::new(graph.clone(), vec!["a".to_string(), "a".to_string()]).is_err());
+        let named = LinkAnalysis::new(graph, vec!["a".to_string(), "b".to_string()]).unwrap();
+        assert_eq!(named.name(1), "b");
+    }
+}
@@ -0,0 +1,214 @@
+//! Compressed sparse row storage for directed graphs.
+//!
+//! The out-links of node `i` are `targets[offsets[i]..offsets[i + 1]]`. Two flat vectors replace
+//! one `Vec` per node, so a graph with millions of edges takes two allocations and iterating over
+//! it reads memory in order. Weighted graphs add a third vector, `weights`, parallel to
+//! `targets`.
+
+/// A directed graph over nodes `0..node_count`, optionally weighted. Parallel edges and self-loops
+/// are kept.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Csr {
+    offsets: Vec<usize>,
+    targets: Vec<usize>,
+    weights: Option<Vec<f64>>,
+}
+
+impl Csr {
+    /// Builds the graph from `(source, target)` pairs with a counting sort, in `O(n + m)`. Each
+    /// node's out-links keep the order they were given in.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an edge refers to a node outside `0..node_count`.
+    pub fn from_edges(node_count: usize, edges: impl IntoIterator<Item = (usize, usize)>) -> Self {
+        let edges: Vec<(usize, usize)> = edges.into_iter().collect();
+        Self::build(node_count, &edges, None)
+    }
+
+    /// Like [`Csr::from_edges`] with a weight per edge.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an edge refers to a node outside `0..node_count` or a weight is negative or not
+    /// finite.
+    pub fn from_weighted_edges(
+        node_count: usize,
+        edges: impl IntoIterator<Item = (usize, usize, f64)>,
+    ) -> Self {
+        let (edges, weights): (Vec<(usize, usize)>, Vec<f64>) = edges
+            .into_iter()
+            .map(|(source, target, weight)| {
+                assert!(
+                    weight.is_finite() && weight >= 0.0,
+                    "edge ({}, {}) has weight {}, expected a finite weight of at least 0",
+                    source,
+                    target,
+                    weight
+                );
+                ((source, target), weight)
+            })
+            .unzip();
+        Self::build(node_count, &edges, Some(weights))
+    }
+
+    fn build(node_count: usize, edges: &[(usize, usize)], weights: Option<Vec<f64>>) -> Self {
+        let mut offsets = vec![0; node_count + 1];
+        for &(source, target) in edges {
+            assert!(
+                source < node_count && target < node_count,
+                "edge ({}, {}) is outside the {} nodes",
+                source,
+                target,
+                node_count
+            );
+            offsets[source + 1] += 1;
+        }
+        for i in 0..node_count {
+            offsets[i + 1] += offsets[i];
+        }
+        let mut next = offsets.clone();
+        let mut targets = vec![0; edges.len()];
+        let mut sorted_weights = weights.as_ref().map(|weights| vec![0.0; weights.len()]);
+        for (edge, &(source, target)) in edges.iter().enumerate() {
+            targets[next[source]] = target;
+            if let (Some(sorted), Some(weights)) = (&mut sorted_weights, &weights) {
+                sorted[next[source]] = weights[edge];
+            }
+            next[source] += 1;
+        }
+        Self {
+            offsets,
+            targets,
+            weights: sorted_weights,
+        }
+    }
+
+    /// Builds the graph from one list of out-links per node.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a link refers to a node outside `0..adjacency.len()`.
+    pub fn from_adjacency(adjacency: &[Vec<usize>]) -> Self {
+        let edges = adjacency
+            .iter()
+            .enumerate()
+            .flat_map(|(source, targets)| targets.iter().map(move |&target| (source, target)));
+        Self::from_edges(adjacency.len(), edges)
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.offsets.len().saturating_sub(1)
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.targets.len()
+    }
+
+    pub fn out_links(&self, node: usize) -> &[usize] {
+        &self.targets[self.offsets[node]..self.offsets[node + 1]]
+    }
+
+    pub fn out_degree(&self, node: usize) -> usize {
+        self.offsets[node + 1] - self.offsets[node]
+    }
+
+    /// Number of links into each node.
+    pub fn in_degrees(&self) -> Vec<usize> {
+        let mut degrees = vec![0; self.node_count()];
+        for &target in &self.targets {
+            degrees[target] += 1;
+        }
+        degrees
+    }
+
+    pub fn is_weighted(&self) -> bool {
+        self.weights.is_some()
+    }
+
+    /// Weights of the out-links of `node`, in the same order as [`Csr::out_links`], or `None` if
+    /// the graph is unweighted.
+    pub fn out_weights(&self, node: usize) -> Option<&[f64]> {
+        self.weights
+            .as_ref()
+            .map(|weights| &weights[self.offsets[node]..self.offsets[node + 1]])
+    }
+
+    /// Summed weight of the out-links of `node`, its out-degree if the graph is unweighted.
+    pub fn out_weight(&self, node: usize) -> f64 {
+        match self.out_weights(node) {
+            Some(weights) => weights.iter().sum(),
+            None => self.out_degree(node) as f64,
+        }
+    }
+
+    /// Every edge reversed, so that `out_links` of the result lists a node's in-links. Weights
+    /// stay with their edges.
+    pub fn transpose(&self) -> Self {
+        let mut edges = Vec::with_capacity(self.edge_count());
+        for source in 0..self.node_count() {
+            edges.extend(
+                self.out_links(source)
+                    .iter()
+                    .map(|&target| (target, source)),
+            );
+        }
+        Self::build(self.node_count(), &edges, self.weights.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_out_links_by_source() {
+        let graph = Csr::from_edges(4, [(2, 0), (0, 1), (2, 3), (0, 2), (2, 0)]);
+        assert_eq!(graph.node_count(), 4);
+        assert_eq!(graph.edge_count(), 5);
+        assert_eq!(graph.out_links(0), &[1, 2]);
+        assert_eq!(graph.out_links(1), &[] as &[usize]);
+        assert_eq!(graph.out_links(2), &[0, 3, 0]);
+        assert_eq!(graph.out_degree(2), 3);
+        assert_eq!(graph.in_degrees(), vec![2, 1, 1, 1]);
+        assert_eq!(
+            graph,
+            Csr::from_adjacency(&[vec![1, 2], vec![], vec![0, 3, 0], vec![]])
+        );
+        assert_eq!(Csr::default().node_count(), 0);
+    }
+
+    #[test]
+    fn transposes_edges() {
+        let graph = Csr::from_edges(3, [(0, 1), (0, 2), (1, 2)]);
+        let reversed = graph.transpose();
+        assert_eq!(reversed.out_links(2), &[0, 1]);
+        assert_eq!(reversed.out_links(0), &[] as &[usize]);
+        assert_eq!(reversed.transpose(), graph);
+    }
+
+    #[test]
+    fn keeps_weights_with_their_edges() {
+        let graph = Csr::from_weighted_edges(3, [(1, 2, 0.5), (0, 1, 2.0), (1, 0, 1.5)]);
+        assert!(graph.is_weighted());
+        assert_eq!(graph.out_links(1), &[2, 0]);
+        assert_eq!(graph.out_weights(1), Some(&[0.5, 1.5][..]));
+        assert_eq!(graph.out_weight(1), 2.0);
+        assert_eq!(graph.out_weight(2), 0.0);
+
+        let reversed = graph.transpose();
+        assert_eq!(reversed.out_links(0), &[1]);
+        assert_eq!(reversed.out_weights(0), Some(&[1.5][..]));
+        assert_eq!(reversed.out_weights(1), Some(&[2.0][..]));
+
+        let unweighted = Csr::from_edges(2, [(0, 1), (0, 1)]);
+        assert_eq!(unweighted.out_weights(0), None);
+        assert_eq!(unweighted.out_weight(0), 2.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected a finite weight")]
+    fn rejects_negative_weights() {
+        Csr::from_weighted_edges(2, [(0, 1, -1.0)]);
+    }
+}
@@ -0,0 +1,105 @@
+//! Reading graphs of named pages from text.
+//!
+//! One link per line, written `source -> target`, optionally followed by a weight in brackets:
+//! `ESPN -> NFL [2.5]`. A line holding only a name adds a page without links. Blank lines and
+//! lines starting with `#` are skipped. Names may contain spaces; pages are numbered in order of
+//! first appearance.
+
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use crate::analysis::{Builder, LinkAnalysis};
+
+/// Links between the sports sites of the original example.
+pub const SAMPLE: &str = "\
+# Sports sites and the sites they link to
+ESPN -> NFL
+ESPN -> NBA
+NFL -> ESPN
+NBA -> ESPN
+NBA -> UFC
+UFC -> ESPN
+MLB -> ESPN
+MLB -> NFL
+";
+
+pub fn parse(text: &str) -> Result<LinkAnalysis, String> {
+    let mut builder = Builder::default();
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let error = |message: &str| format!("line {}: {}", number + 1, message);
+        let Some((source, rest)) = line.split_once("->") else {
+            builder.page(line);
+            continue;
+        };
+        let (target, weight) = split_weight(rest.trim()).map_err(|message| error(&message))?;
+        let source = source.trim();
+        if source.is_empty() || target.is_empty() {
+            return Err(error("expected `source -> target`"));
+        }
+        builder.link(source, target, weight);
+    }
+    Ok(builder.build())
+}
+
+pub fn load(path: &Path) -> Result<LinkAnalysis, Box<dyn Error>> {
+    let text = fs::read_to_string(path)
+        .map_err(|err| format!("could not read {}: {}", path.display(), err))?;
+    parse(&text).map_err(|err| format!("{}: {}", path.display(), err).into())
+}
+
+/// Splits `target [weight]` into the target and its weight, 1 if none is given.
+fn split_weight(text: &str) -> Result<(&str, f64), String> {
+    let Some(open) = text.strip_suffix(']').and_then(|rest| rest.rfind('[')) else {
+        return Ok((text, 1.0));
+    };
+    let weight = &text[open + 1..text.len() - 1];
+    match weight.trim().parse::<f64>() {
+        Ok(value) if value.is_finite() && value >= 0.0 => Ok((text[..open].trim(), value)),
+        _ => Err(format!("'{}' is not a finite, non-negative weight", weight)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_links_weights_and_lone_pages() {
+        let text = "\
+            # comment\n\
+            Fox Sports -> NFL [2.5]\n\
+            \n\
+            NFL -> Fox Sports\n\
+            Archive\n\
+            NFL -> NBA[ 0 ]\n";
+        let sites = parse(text).unwrap();
+        assert_eq!(sites.names(), &["Fox Sports", "NFL", "Archive", "NBA"]);
+        let graph = sites.graph();
+        assert_eq!(graph.edge_count(), 3);
+        assert_eq!(graph.out_links(0), &[1]);
+        assert_eq!(graph.out_weights(0), Some(&[2.5][..]));
+        assert_eq!(graph.out_links(1), &[0, 3]);
+        assert_eq!(graph.out_weights(1), Some(&[1.0, 0.0][..]));
+        assert_eq!(graph.out_degree(2), 0);
+
+        assert_eq!(parse(SAMPLE).unwrap().names().len(), 5);
+    }
+
+    #[test]
+    fn reports_the_offending_line() {
+        assert_eq!(
+            parse("a -> b\n -> b").unwrap_err(),
+            "line 2: expected `source -> target`"
+        );
+        assert_eq!(
+            parse("a -> b [-1]").unwrap_err(),
+            "line 1: '-1' is not a finite, non-negative weight"
+        );
+        assert!(parse("a -> b [heavy]").is_err());
+    }
+}
@@ -0,0 +1,169 @@
+//! HITS hub and authority scores (Kleinberg, 1999).
+//!
+//! A good authority is linked to by good hubs, and a good hub links to good authorities. Starting
+//! from uniform hub scores, each iteration sets every authority score to the summed hub scores of
+//! its in-links, then every hub score to the summed authority scores of its out-links, and scales
+//! both vectors to sum to 1. Edge weights scale each link's contribution. Iteration stops once the
+//! combined L1 change of both vectors falls below the tolerance.
+
+use crate::csr::Csr;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hits {
+    /// Stop once the L1 change of hubs and authorities together is below this.
+    pub tolerance: f64,
+    /// Stop after this many iterations even if the scores have not converged.
+    pub max_iterations: usize,
+}
+
+impl Default for Hits {
+    fn default() -> Self {
+        Self {
+            tolerance: 1e-10,
+            max_iterations: 1000,
+        }
+    }
+}
+
+/// The result of [`Hits::scores`]. Both vectors sum to 1.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HitsScores {
+    pub hubs: Vec<f64>,
+    pub authorities: Vec<f64>,
+    pub iterations: usize,
+    /// L1 change made by the last iteration.
+    pub residual: f64,
+    pub converged: bool,
+}
+
+impl Hits {
+    pub fn new(tolerance: f64, max_iterations: usize) -> Self {
+        Self {
+            tolerance,
+            max_iterations,
+        }
+    }
+
+    pub fn scores(&self, graph: &Csr) -> HitsScores {
+        let n = graph.node_count();
+        let mut hubs = vec![1.0 / n as f64; n];
+        let mut authorities = vec![1.0 / n as f64; n];
+        let mut next = vec![0.0; n];
+        let mut iterations = 0;
+        let mut residual = if n == 0 { 0.0 } else { f64::INFINITY };
+        while iterations < self.max_iterations && residual >= self.tolerance {
+            // Authorities collect the hub scores of their in-links.
+            next.fill(0.0);
+            for (node, &hub) in hubs.iter().enumerate() {
+                for (target, weight) in links(graph, node) {
+                    next[target] += weight * hub;
+                }
+            }
+            normalise(&mut next);
+            residual = l1_distance(&next, &authorities);
+            std::mem::swap(&mut authorities, &mut next);
+
+            // Hubs collect the authority scores of their out-links.
+            for (node, hub) in next.iter_mut().enumerate() {
+                *hub = links(graph, node)
+                    .map(|(target, weight)| weight * authorities[target])
+                    .sum();
+            }
+            normalise(&mut next);
+            residual += l1_distance(&next, &hubs);
+            std::mem::swap(&mut hubs, &mut next);
+            iterations += 1;
+        }
+
+        HitsScores {
+            hubs,
+            authorities,
+            iterations,
+            residual,
+            converged: residual < self.tolerance,
+        }
+    }
+}
+
+/// The out-links of `node` with their weights, 1 if the graph is unweighted.
+fn links(graph: &Csr, node: usize) -> impl Iterator<Item = (usize, f64)> + '_ {
+    let weights = graph.out_weights(node);
+    graph
+        .out_links(node)
+        .iter()
+        .enumerate()
+        .map(move |(i, &target)| (target, weights.map_or(1.0, |weights| weights[i])))
+}
+
+/// Scales `scores` to sum to 1. Scores that are all 0, as in a graph without edges, become
+/// uniform.
+fn normalise(scores: &mut [f64]) {
+    let total: f64 = scores.iter().sum();
+    if total > 0.0 {
+        scores.iter_mut().for_each(|score| *score /= total);
+    } else {
+        let uniform = 1.0 / scores.len() as f64;
+        scores.iter_mut().for_each(|score| *score = uniform);
+    }
+}
+
+fn l1_distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(a, b)| (a - b).abs()).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn hubs_point_at_authorities() {
+        // 0 links to both 2 and 3, 1 only to 2.
+        let graph = Csr::from_edges(4, [(0, 2), (0, 3), (1, 2)]);
+        let scores = Hits::default().scores(&graph);
+        assert!(scores.converged);
+        let (hubs, authorities) = (&scores.hubs, &scores.authorities);
+        assert!(hubs[0] > hubs[1] && hubs[1] > 0.0);
+        assert_eq!((hubs[2], hubs[3]), (0.0, 0.0));
+        assert!(authorities[2] > authorities[3] && authorities[3] > 0.0);
+        assert_eq!((authorities[0], authorities[1]), (0.0, 0.0));
+    }
+
+    #[test]
+    fn star_has_one_authority() {
+        let graph = Csr::from_edges(4, [(1, 0), (2, 0), (3, 0)]);
+        let scores = Hits::default().scores(&graph);
+        assert_eq!(scores.authorities, vec![1.0, 0.0, 0.0, 0.0]);
+        for &hub in &scores.hubs[1..] {
+            assert!((hub - 1.0 / 3.0).abs() < 1e-12);
+        }
+
+        let weighted = Csr::from_weighted_edges(4, [(1, 0, 2.0), (2, 0, 1.0), (3, 0, 1.0)]);
+        let scores = Hits::default().scores(&weighted);
+        assert!((scores.hubs[1] - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn graphs_without_edges_score_uniformly() {
+        let scores = Hits::default().scores(&Csr::from_edges(2, []));
+        assert_eq!(scores.hubs, vec![0.5, 0.5]);
+        assert_eq!(scores.authorities, vec![0.5, 0.5]);
+        let empty = Hits::default().scores(&Csr::default());
+        assert!(empty.converged && empty.hubs.is_empty());
+    }
+
+    proptest! {
+        #[test]
+        fn scores_form_distributions(
+            n in 1..30usize,
+            edges in prop::collection::vec((0..30usize, 0..30usize, 0.0..5.0f64), 0..100),
+        ) {
+            let edges = edges.into_iter().map(|(a, b, w)| (a % n, b % n, w));
+            let scores = Hits::new(1e-9, 100).scores(&Csr::from_weighted_edges(n, edges));
+            for scores in [&scores.hubs, &scores.authorities] {
+                prop_assert!((scores.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+                prop_assert!(scores.iter().all(|&score| score >= 0.0));
+            }
+        }
+    }
+}
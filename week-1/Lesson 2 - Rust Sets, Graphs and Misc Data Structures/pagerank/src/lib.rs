@@ -0,0 +1,20 @@
+//! PageRank and HITS over sparse directed graphs.
+//!
+//! Graphs are stored as [`csr::Csr`], optionally weighted, and ranked with [`rank::PageRank`],
+//! which handles pages without out-links, supports personalised teleport vectors and stops once
+//! the ranks converge. [`parallel`] adds multi-threaded variants. [`hits::Hits`] computes hub and
+//! authority scores. [`analysis::LinkAnalysis`] puts all of them behind one API over named pages,
+//! which [`edge_list`] reads from text and [`report`] prints as a table or JSON.
+
+pub mod analysis;
+pub mod csr;
+pub mod edge_list;
+pub mod hits;
+pub mod parallel;
+pub mod rank;
+pub mod report;
+
+pub use analysis::LinkAnalysis;
+pub use csr::Csr;
+pub use hits::{Hits, HitsScores};
+pub use rank::{PageRank, Ranking};
@@ -84,93 +84,101 @@
 //! the algorithm to stabilize, while too many may result in unnecessary
 //! computation once convergence is achieved.
 
-// Importing the fill function from the textwrap crate to wrap text at 78 characters per line.
-use textwrap::fill;
-
-// The PageRank struct holds the damping factor and the number of iterations to run the algorithm.
-struct PageRank {
+use std::error::Error;
+use std::path::PathBuf;
+
+use clap::Parser;
+use pagerank::edge_list;
+use pagerank::report::Report;
+use pagerank::{Hits, PageRank};
+
+#[derive(Parser)]
+#[clap(
+    version = "1.0",
+    about = "Ranks the pages of a link graph with PageRank",
+    long_about = "Ranks the pages of a link graph with PageRank, a link analysis algorithm \
+    that uses the link structure of the web to estimate how important each page is from the \
+    number and quality of the links pointing to it."
+)]
+struct Opts {
+    /// Edge list with one `source -> target` link per line, optionally followed by a
+    /// `[weight]`. Without it the bundled sports sites are used.
+    edges: Option<PathBuf>,
+    /// Probability of following a link rather than jumping to a random page
+    #[clap(short, long, default_value_t = 0.85)]
     damping: f64,
-    iterations: usize,
+    /// Stop once the ranks change by less than this between two iterations
+    #[clap(short, long, default_value_t = 1e-10)]
+    tolerance: f64,
+    /// Stop after this many iterations even if the ranks have not converged
+    #[clap(short = 'i', long, default_value_t = 1000)]
+    max_iterations: usize,
+    /// Only show this many of the highest-ranked pages
+    #[clap(short = 'n', long)]
+    top: Option<usize>,
+    /// Rank pages as seen from this page: random jumps land only on seed pages. Repeat for
+    /// several seeds.
+    #[clap(short, long = "seed", value_name = "PAGE")]
+    seeds: Vec<String>,
+    /// Report HITS authority and hub scores instead of PageRank
+    #[clap(long, conflicts_with_all = ["seeds", "damping"])]
+    hits: bool,
+    /// Print the report as JSON instead of a table
+    #[clap(short, long)]
+    json: bool,
 }
 
-impl PageRank {
-    // The new function creates a new instance of the PageRank struct.
-    fn new(damping: f64, iterations: usize) -> Self {
-        Self { damping, iterations }
+fn main() -> Result<(), Box<dyn Error>> {
+    let opts: Opts = Opts::parse();
+    if !(0.0..=1.0).contains(&opts.damping) {
+        return Err(format!("damping factor {} is outside 0..=1", opts.damping).into());
     }
 
-    // The rank function calculates and returns the PageRank for each node in the graph.
-    fn rank(&self, graph: &Vec<Vec<usize>>) -> Vec<f64> {
-        // The number of nodes in the graph.
-        let n = graph.len();
-
-        // The initial PageRank value for each node.
-        let mut ranks = vec![1.0 / (n as f64); n];
-
-        // Iterates the specified number of times.
-        for _ in 0..self.iterations {
-            // A new vector to hold the updated PageRank values.
-            let mut new_ranks = vec![0.0; n];
-
-            // Iterates over each node and its edges in the graph.
-            for (node, edges) in graph.iter().enumerate() {
-                // The amount of PageRank value this node contributes to its linked nodes.
-                let contribution = ranks[node] / (edges.len() as f64);
-
-                // Distributes the PageRank value to the linked nodes.
-                for &edge in edges {
-                    new_ranks[edge] += contribution;
-                }
-            }
-
-            // Updates the PageRank values using the damping factor.
-            for rank in &mut new_ranks {
-                *rank = *rank * self.damping + (1.0 - self.damping) / (n as f64);
-            }
-
-            // Replaces the old PageRank values with the new ones.
-            ranks = new_ranks;
+    // Load the links and map each page name to a node of the graph
+    let sites = match &opts.edges {
+        Some(path) => edge_list::load(path)?,
+        None => edge_list::parse(edge_list::SAMPLE)?,
+    };
+
+    let reports = if opts.hits {
+        let hits = sites.hits(&Hits::new(opts.tolerance, opts.max_iterations));
+        vec![
+            Report::new(
+                &sites,
+                "HITS authority",
+                &hits.authorities,
+                (&hits).into(),
+                opts.top,
+            ),
+            Report::new(&sites, "HITS hub", &hits.hubs, (&hits).into(), opts.top),
+        ]
+    } else {
+        let pagerank = PageRank::new(opts.damping, opts.tolerance, opts.max_iterations);
+        let (measure, ranking) = if opts.seeds.is_empty() {
+            ("PageRank".to_string(), sites.pagerank(&pagerank))
+        } else {
+            let seeds: Vec<&str> = opts.seeds.iter().map(String::as_str).collect();
+            let measure = format!("PageRank personalized to {}", seeds.join(", "));
+            (measure, sites.personalized(&pagerank, &seeds)?)
+        };
+        vec![Report::new(
+            &sites,
+            &measure,
+            &ranking.ranks,
+            (&ranking).into(),
+            opts.top,
+        )]
+    };
+
+    if opts.json {
+        // One report prints as an object, the two HITS reports as an array
+        match reports.as_slice() {
+            [report] => println!("{}", report.to_json()?),
+            reports => println!("{}", serde_json::to_string_pretty(reports)?),
         }
-
-        // Returns the final PageRank values.
-        ranks
+    } else {
+        let tables: Vec<String> = reports.iter().map(Report::render).collect();
+        print!("{}", tables.join("\n"));
     }
-}
-
-fn main() {
-    // The graph represents links between sports websites. Each index represents a website, 
-    // and the values in the vectors are the indexes of the websites they link to.
-    let graph = vec![
-        vec![1, 2],  // ESPN links to NFL, NBA
-        vec![0],     // NFL links to ESPN
-        vec![0, 3],  // NBA links to ESPN, UFC
-        vec![0],     // UFC links to ESPN
-        vec![0, 1],  // MLB links to ESPN, NFL
-    ];
-    
-    // The names corresponding to the indexes of the websites.
-    let names = vec!["ESPN", "NFL", "NBA", "UFC", "MLB"];
-
-    // Initializes the PageRank struct.
-    let pagerank = PageRank::new(0.85, 100);
-
-    // Calculates the PageRank values.
-    let ranks = pagerank.rank(&graph);  
-
-    // Prints the PageRank values.
-    println!("The PageRank values are:");
-    for (i, rank) in ranks.iter().enumerate() {
-        println!("{}: {}", names[i], rank);
-    }
-    
-    // Prints the PageRank values.
-    for (i, rank) in ranks.iter().enumerate() {
-        println!("The PageRank of {} is {}", names[i], rank);
-    }
-
-    // Explanation of how PageRank works.
-    let explanation = "PageRank is a link analysis algorithm used by Google that uses the hyperlink structure of the web to determine a quality ranking for each web page. It works by counting the number and quality of links to a page to determine a rough estimate of how important the website is.";
-    
-    // Prints the explanation wrapped at 78 characters per line.
-    println!("{}", fill(explanation, 78));
+    Ok(())
 }
@@ -0,0 +1,329 @@
+//! Power-iteration PageRank over a [`Csr`] graph.
+//!
+//! Each iteration moves every node's rank along its out-links. A dangling node, one without
+//! out-links, has nowhere to send its rank, so its rank is spread evenly over all nodes as if it
+//! linked to every page. Without that the total rank leaks away each iteration. Iteration stops
+//! once the L1 distance between two successive rank vectors falls below the tolerance.
+//!
+//! On a weighted graph a node splits its rank between its out-links in proportion to their
+//! weights. [`PageRank::personalized`] replaces the uniform random jump with a teleport vector, so
+//! the ranks measure importance as seen from the pages it favours (topic-sensitive PageRank).
+//! Dangling rank then follows the teleport vector too.
+
+use crate::csr::Csr;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PageRank {
+    /// Probability of following a link rather than jumping to a random page, in `0..=1`.
+    pub damping: f64,
+    /// Stop once the L1 change between two iterations is below this.
+    pub tolerance: f64,
+    /// Stop after this many iterations even if the ranks have not converged.
+    pub max_iterations: usize,
+}
+
+impl Default for PageRank {
+    fn default() -> Self {
+        Self {
+            damping: 0.85,
+            tolerance: 1e-10,
+            max_iterations: 1000,
+        }
+    }
+}
+
+/// The result of [`PageRank::rank`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ranking {
+    /// Rank of every node. The ranks sum to 1.
+    pub ranks: Vec<f64>,
+    pub iterations: usize,
+    /// L1 change made by the last iteration.
+    pub residual: f64,
+    /// Whether `residual` fell below the tolerance before `max_iterations` ran out.
+    pub converged: bool,
+}
+
+impl PageRank {
+    pub fn new(damping: f64, tolerance: f64, max_iterations: usize) -> Self {
+        Self {
+            damping,
+            tolerance,
+            max_iterations,
+        }
+    }
+
+    /// Ranks every node of `graph`, starting from the uniform distribution.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the damping factor is outside `0..=1`.
+    pub fn rank(&self, graph: &Csr) -> Ranking {
+        self.iterate(graph, None)
+    }
+
+    /// Ranks every node of `graph` with random jumps landing on node `i` with probability
+    /// proportional to `teleport[i]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the damping factor is outside `0..=1`, `teleport` does not have one entry per
+    /// node, or its entries are not finite, non-negative and positive in sum.
+    pub fn personalized(&self, graph: &Csr, teleport: &[f64]) -> Ranking {
+        let teleport = teleport_distribution(graph, teleport);
+        self.iterate(graph, Some(&teleport))
+    }
+
+    pub(crate) fn check_damping(&self) {
+        assert!(
+            (0.0..=1.0).contains(&self.damping),
+            "damping factor {} is outside 0..=1",
+            self.damping
+        );
+    }
+
+    fn iterate(&self, graph: &Csr, teleport: Option<&[f64]>) -> Ranking {
+        self.check_damping();
+        let n = graph.node_count();
+        if n == 0 {
+            return Ranking::empty();
+        }
+
+        let uniform = 1.0 / n as f64;
+        let out_weights: Vec<f64> = (0..n).map(|node| graph.out_weight(node)).collect();
+        let mut ranks = vec![uniform; n];
+        let mut next = vec![0.0; n];
+        let mut iterations = 0;
+        let mut residual = f64::INFINITY;
+        while iterations < self.max_iterations && residual >= self.tolerance {
+            // Push each node's rank along its out-links, and pool the rank of dangling nodes.
+            next.fill(0.0);
+            let mut dangling = 0.0;
+            for (node, &rank) in ranks.iter().enumerate() {
+                if out_weights[node] == 0.0 {
+                    dangling += rank;
+                    continue;
+                }
+                let links = graph.out_links(node);
+                match graph.out_weights(node) {
+                    Some(weights) => {
+                        let scale = rank / out_weights[node];
+                        for (&target, &weight) in links.iter().zip(weights) {
+                            next[target] += scale * weight;
+                        }
+                    }
+                    None => {
+                        let share = rank / links.len() as f64;
+                        for &target in links {
+                            next[target] += share;
+                        }
+                    }
+                }
+            }
+
+            // Damp the followed links and add the random jumps, including the dangling rank.
+            let jumping = 1.0 - self.damping + self.damping * dangling;
+            residual = 0.0;
+            for (node, (rank, old)) in next.iter_mut().zip(&ranks).enumerate() {
+                let jump = teleport.map_or(uniform, |teleport| teleport[node]);
+                *rank = self.damping * *rank + jumping * jump;
+                residual += (*rank - old).abs();
+            }
+            std::mem::swap(&mut ranks, &mut next);
+            iterations += 1;
+        }
+
+        Ranking {
+            ranks,
+            iterations,
+            residual,
+            converged: residual < self.tolerance,
+        }
+    }
+}
+
+impl Ranking {
+    pub(crate) fn empty() -> Self {
+        Self {
+            ranks: Vec::new(),
+            iterations: 0,
+            residual: 0.0,
+            converged: true,
+        }
+    }
+}
+
+/// Checks a teleport vector and scales it to sum to 1.
+pub(crate) fn teleport_distribution(graph: &Csr, teleport: &[f64]) -> Vec<f64> {
+    assert_eq!(
+        teleport.len(),
+        graph.node_count(),
+        "the teleport vector needs one entry per node"
+    );
+    assert!(
+        teleport.iter().all(|&p| p.is_finite() && p >= 0.0),
+        "teleport entries must be finite and at least 0"
+    );
+    let total: f64 = teleport.iter().sum();
+    assert!(total > 0.0, "the teleport vector sums to 0");
+    teleport.iter().map(|p| p / total).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn assert_close(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-9, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn redistributes_dangling_rank() {
+        // 0 -> 1 and 1 links nowhere. Solving r0 = (1 - d) / 2 + d * r1 / 2 with r0 + r1 = 1
+        // gives r0 = 1 / (2 + d).
+        let graph = Csr::from_edges(2, [(0, 1)]);
+        let ranking = PageRank::default().rank(&graph);
+        assert!(ranking.converged);
+        let r0 = 1.0 / 2.85;
+        assert_close(&ranking.ranks, &[r0, 1.0 - r0]);
+    }
+
+    #[test]
+    fn symmetric_graphs_rank_evenly() {
+        let cycle = Csr::from_edges(3, [(0, 1), (1, 2), (2, 0)]);
+        let ranking = PageRank::default().rank(&cycle);
+        assert_close(&ranking.ranks, &[1.0 / 3.0; 3]);
+        assert!(ranking.iterations <= 2);
+
+        let no_links = Csr::from_edges(4, []);
+        assert_close(&PageRank::default().rank(&no_links).ranks, &[0.25; 4]);
+        assert!(PageRank::default().rank(&Csr::default()).ranks.is_empty());
+    }
+
+    #[test]
+    fn stops_at_the_tolerance_or_the_iteration_limit() {
+        // The sports sites: ESPN, NFL, NBA, UFC, MLB.
+        let graph = Csr::from_adjacency(&[vec![1, 2], vec![0], vec![0, 3], vec![0], vec![0, 1]]);
+        let ranking = PageRank::new(0.85, 1e-12, 1000).rank(&graph);
+        assert!(ranking.converged);
+        assert!(ranking.residual < 1e-12);
+        assert!(ranking.iterations > 10 && ranking.iterations < 1000);
+        let espn = ranking.ranks[0];
+        assert!(ranking.ranks[1..].iter().all(|&rank| rank < espn));
+        // MLB has no in-links, so it only gets the random jumps.
+        assert!((ranking.ranks[4] - 0.15 / 5.0).abs() < 1e-12);
+
+        let capped = PageRank::new(0.85, 1e-12, 3).rank(&graph);
+        assert_eq!(capped.iterations, 3);
+        assert!(!capped.converged);
+        assert!(capped.residual >= 1e-12);
+    }
+
+    #[test]
+    fn handles_millions_of_edges() {
+        let n = 200_000;
+        let mut state = 42u64;
+        let edges = (0..2_000_000).map(|_| {
+            let mut next = || {
+                state = state
+                    .wrapping_mul(6_364_136_223_846_793_005)
+                    .wrapping_add(1);
+                (state >> 33) as usize
+            };
+            (next() % n, next() % n)
+        });
+        let graph = Csr::from_edges(n, edges);
+        let ranking = PageRank::new(0.85, 1e-8, 100).rank(&graph);
+        assert!(ranking.converged);
+        assert!((ranking.ranks.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn splits_rank_by_edge_weight() {
+        // 0 sends three times as much rank to 1 as to 2; 1 and 2 link back to 0.
+        let graph =
+            Csr::from_weighted_edges(3, [(0, 1, 3.0), (0, 2, 1.0), (1, 0, 1.0), (2, 0, 1.0)]);
+        let ranks = PageRank::default().rank(&graph).ranks;
+        let d = 0.85;
+        // With a = (1 - d) / 3: r0 = a + d * (r1 + r2), r1 = a + d * 0.75 * r0 and
+        // r2 = a + d * 0.25 * r0.
+        let r0 = (1.0 - d) / 3.0 * (1.0 + 2.0 * d) / (1.0 - d * d);
+        let r1 = (1.0 - d) / 3.0 + d * 0.75 * r0;
+        assert_close(&ranks, &[r0, r1, 1.0 - r0 - r1]);
+
+        // Equal weights rank like the unweighted graph.
+        let even =
+            Csr::from_weighted_edges(3, [(0, 1, 2.0), (0, 2, 2.0), (1, 0, 5.0), (2, 0, 5.0)]);
+        let plain = Csr::from_edges(3, [(0, 1), (0, 2), (1, 0), (2, 0)]);
+        assert_close(
+            &PageRank::default().rank(&even).ranks,
+            &PageRank::default().rank(&plain).ranks,
+        );
+    }
+
+    #[test]
+    fn personalization_favours_the_seed() {
+        let graph = Csr::from_adjacency(&[vec![1, 2], vec![0], vec![0, 3], vec![0], vec![0, 1]]);
+        let global = PageRank::default().rank(&graph).ranks;
+        let from_mlb = PageRank::default()
+            .personalized(&graph, &[0.0, 0.0, 0.0, 0.0, 2.0])
+            .ranks;
+        assert!((from_mlb.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!(from_mlb[4] > global[4]);
+        assert!(from_mlb[1] > global[1]);
+        // UFC is only reachable through NBA, far from MLB.
+        assert!(from_mlb[3] < global[3]);
+
+        let uniform = PageRank::default().personalized(&graph, &[1.0; 5]).ranks;
+        assert_close(&uniform, &global);
+    }
+
+    #[test]
+    #[should_panic(expected = "sums to 0")]
+    fn teleport_vector_must_have_mass() {
+        PageRank::default().personalized(&Csr::from_edges(2, [(0, 1)]), &[0.0, 0.0]);
+    }
+
+    fn graphs() -> impl Strategy<Value = Csr> {
+        (1..40usize).prop_flat_map(|n| {
+            prop::collection::vec((0..n, 0..n), 0..200)
+                .prop_map(move |edges| Csr::from_edges(n, edges))
+        })
+    }
+
+    fn weighted_graphs() -> impl Strategy<Value = Csr> {
+        (1..40usize).prop_flat_map(|n| {
+            prop::collection::vec((0..n, 0..n, 0.0..10.0f64), 0..200)
+                .prop_map(move |edges| Csr::from_weighted_edges(n, edges))
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn personalized_weighted_ranks_form_a_distribution(
+            graph in weighted_graphs(),
+            damping in 0.0..=1.0f64,
+            seed in any::<prop::sample::Index>(),
+        ) {
+            let mut teleport = vec![0.0; graph.node_count()];
+            teleport[seed.index(graph.node_count())] = 1.0;
+            let ranking = PageRank::new(damping, 1e-12, 200).personalized(&graph, &teleport);
+            prop_assert!((ranking.ranks.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+            prop_assert!(ranking.ranks.iter().all(|&rank| rank >= 0.0));
+        }
+
+        #[test]
+        fn ranks_form_a_distribution(graph in graphs(), damping in 0.0..=1.0f64) {
+            let n = graph.node_count() as f64;
+            let ranking = PageRank::new(damping, 1e-12, 200).rank(&graph);
+            prop_assert!((ranking.ranks.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+            // Every node gets at least its share of the random jumps.
+            let floor = (1.0 - damping) / n;
+            prop_assert!(ranking.ranks.iter().all(|&rank| rank >= floor - 1e-12));
+        }
+    }
+}
@@ -0,0 +1,198 @@
+//! Ranked reports of a [`LinkAnalysis`], as a plain-text table or JSON.
+//!
+//! A [`Report`] takes plain per-page scores, so PageRank, personalized PageRank and both HITS
+//! vectors are reported the same way.
+
+use serde::Serialize;
+
+use crate::analysis::LinkAnalysis;
+use crate::hits::HitsScores;
+use crate::rank::Ranking;
+
+/// One page of a [`Report`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Row {
+    /// Position in the report, from 1.
+    pub rank: usize,
+    pub page: String,
+    pub score: f64,
+    pub in_degree: usize,
+    pub out_degree: usize,
+}
+
+/// How the iteration that produced the scores ended.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Convergence {
+    pub converged: bool,
+    pub iterations: usize,
+    pub residual: f64,
+}
+
+impl From<&Ranking> for Convergence {
+    fn from(ranking: &Ranking) -> Self {
+        Self {
+            converged: ranking.converged,
+            iterations: ranking.iterations,
+            residual: ranking.residual,
+        }
+    }
+}
+
+impl From<&HitsScores> for Convergence {
+    fn from(scores: &HitsScores) -> Self {
+        Self {
+            converged: scores.converged,
+            iterations: scores.iterations,
+            residual: scores.residual,
+        }
+    }
+}
+
+/// The pages ordered by one score, highest first, with how the scores converged.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Report {
+    /// What the scores measure, e.g. `PageRank` or `HITS authority`.
+    pub measure: String,
+    #[serde(flatten)]
+    pub convergence: Convergence,
+    /// Number of pages ranked, including any cut by `top`.
+    pub page_count: usize,
+    pub pages: Vec<Row>,
+}
+
+impl Report {
+    /// Reports the `top` pages with the highest `scores`, or all of them if `top` is `None`.
+    /// Ties are broken by name.
+    pub fn new(
+        analysis: &LinkAnalysis,
+        measure: &str,
+        scores: &[f64],
+        convergence: Convergence,
+        top: Option<usize>,
+    ) -> Self {
+        let graph = analysis.graph();
+        let in_degrees = graph.in_degrees();
+        let ranked = analysis.ranked(scores);
+        let pages = ranked
+            .iter()
+            .take(top.unwrap_or(ranked.len()))
+            .enumerate()
+            .map(|(position, &(page, score))| {
+                let node = analysis.node(page).expect("ranked pages are in the graph");
+                Row {
+                    rank: position + 1,
+                    page: page.to_string(),
+                    score,
+                    in_degree: in_degrees[node],
+                    out_degree: graph.out_degree(node),
+                }
+            })
+            .collect();
+        Self {
+            measure: measure.to_string(),
+            convergence,
+            page_count: ranked.len(),
+            pages,
+        }
+    }
+
+    /// A table with one line per page under a summary of the run.
+    pub fn render(&self) -> String {
+        let width = self
+            .pages
+            .iter()
+            .map(|row| row.page.chars().count())
+            .chain(["Page".len()])
+            .max()
+            .unwrap_or_default();
+        let convergence = &self.convergence;
+        let mut out = format!(
+            "{}: {} after {} iterations (residual {:e}), showing {} of {} pages\n",
+            self.measure,
+            if convergence.converged {
+                "converged"
+            } else {
+                "did not converge"
+            },
+            convergence.iterations,
+            convergence.residual,
+            self.pages.len(),
+            self.page_count
+        );
+        out += &format!(
+            "{:>4}  {:<width$}  {:>10}  {:>6}  {:>6}\n",
+            "Rank",
+            "Page",
+            "Score",
+            "In",
+            "Out",
+            width = width
+        );
+        for row in &self.pages {
+            out += &format!(
+                "{:>4}  {:<width$}  {:>10.6}  {:>6}  {:>6}\n",
+                row.rank,
+                row.page,
+                row.score,
+                row.in_degree,
+                row.out_degree,
+                width = width
+            );
+        }
+        out
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edge_list;
+    use crate::hits::Hits;
+    use crate::rank::PageRank;
+
+    #[test]
+    fn lists_the_top_pages_with_their_degrees() {
+        let sites = edge_list::parse(edge_list::SAMPLE).unwrap();
+        let ranking = PageRank::default().rank(sites.graph());
+        let report = Report::new(
+            &sites,
+            "PageRank",
+            &ranking.ranks,
+            (&ranking).into(),
+            Some(2),
+        );
+        assert_eq!(report.page_count, 5);
+        assert_eq!(report.pages.len(), 2);
+        let espn = &report.pages[0];
+        assert_eq!((espn.rank, espn.page.as_str()), (1, "ESPN"));
+        assert_eq!((espn.in_degree, espn.out_degree), (4, 2));
+        assert!(report.pages[0].score > report.pages[1].score);
+
+        let table = report.render();
+        assert!(table.starts_with("PageRank: converged after "));
+        assert!(table.contains("showing 2 of 5 pages"));
+        assert!(table.lines().nth(2).unwrap().starts_with("   1  ESPN  "));
+
+        let json: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+        assert_eq!(json["measure"], "PageRank");
+        assert_eq!(json["pages"][0]["page"], "ESPN");
+        assert_eq!(json["pages"][0]["in_degree"], 4);
+        assert_eq!(json["converged"], true);
+        let all = Report::new(&sites, "PageRank", &ranking.ranks, (&ranking).into(), None);
+        assert_eq!(all.pages.last().unwrap().page, "MLB");
+    }
+
+    #[test]
+    fn reports_hits_scores() {
+        let sites = edge_list::parse(edge_list::SAMPLE).unwrap();
+        let hits = sites.hits(&Hits::default());
+        let hubs = Report::new(&sites, "HITS hub", &hits.hubs, (&hits).into(), Some(1));
+        assert_eq!(hubs.pages[0].page, "MLB");
+        assert_eq!(hubs.convergence.iterations, hits.iterations);
+        assert!(hubs.render().starts_with("HITS hub: converged after "));
+    }
+}
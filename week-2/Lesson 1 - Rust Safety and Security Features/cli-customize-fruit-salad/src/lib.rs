@@ -2,15 +2,15 @@
 This code defines a function called create_fruit_salad 
 that takes a mutable vector of strings as input and returns 
 a new vector of strings that contains the same elements as the input vector, 
-but in a random order.
+but in a random order. The random number generator is passed in, so a
+seeded one gives the same salad every time.
 */
 
 use rand::seq::SliceRandom;
-use rand::thread_rng;
+use rand::Rng;
 
-pub fn create_fruit_salad(mut fruits: Vec<String>) -> Vec<String> {
-    let mut rng = thread_rng();
-    fruits.shuffle(&mut rng);
+pub fn create_fruit_salad<R: Rng + ?Sized>(mut fruits: Vec<String>, rng: &mut R) -> Vec<String> {
+    fruits.shuffle(rng);
 
     fruits
 }
\ No newline at end of file
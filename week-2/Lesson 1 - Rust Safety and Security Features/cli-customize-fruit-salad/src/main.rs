@@ -4,6 +4,8 @@ Usage:
 cargo run -- fruits.csv
 or
 cargo run -- --fruits "apple, pear"
+or, for a salad of the whole fruit catalogue
+cargo run
 
  */
 //! Reflection Questions:
@@ -43,6 +45,7 @@ cargo run -- --fruits "apple, pear"
 //! configuration provided in the `Opts` struct.
 
 use clap::Parser;
+use fruit_catalogue::FRUITS;
 use fruit_salad_maker::create_fruit_salad;
 use std::io::Write;
 
@@ -82,16 +85,18 @@ fn main() {
                 .expect("Could not read file");
             csv_to_vec(&fruits)
         },
-        None => {
-            opts.fruits.unwrap_or_default()
+        None => match opts.fruits {
+            Some(fruits) => fruits
                 .split(',')
                 .map(|s| s.trim().to_string())
-                .collect()
+                .collect(),
+            // Without any input, use every fruit in the catalogue
+            None => FRUITS.iter().map(|fruit| fruit.to_string()).collect(),
         },
     };
 
     // display fruit salad
-    let fruit_salad = create_fruit_salad(fruit_list);
+    let fruit_salad = create_fruit_salad(fruit_list, &mut rand::thread_rng());
     display_fruit_salad(&fruit_salad);
 
     // Challenge(2): Write the fruit salad to a file
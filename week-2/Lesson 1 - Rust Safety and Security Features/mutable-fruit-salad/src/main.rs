@@ -95,26 +95,26 @@
 //! Without the `mut` keyword, these operations would result in a compile-time 
 //! error, as Rust enforces strict controls over mutability to uphold safety 
 //! guarantees.
+use fruit_catalogue::FRUITS;
 use rand::thread_rng;
-use rand::prelude::SliceRandom;
 
 fn main() {
     // Create a vector of fruits.
-    let fruit_salad = vec!["apple", "banana", "cherry", "dates", "elderberries"];
+    let fruit_salad = FRUITS[..5].to_vec();
     println!("Original fruit salad: {:?}, length: {}\n", fruit_salad, fruit_salad.len()); // Challenge(3): Print the length of the fruit salad
 
     // Uncommenting the following line will cause a compilation error because fruit_salad is immutable.
-    // fruit_salad.push("figs");
+    // fruit_salad.push("Fig");
 
     // To mutate the vector, we need to declare it as mutable:
-    let mut fruit_salad = vec!["apple", "banana", "cherry", "dates", "elderberries"];
+    let mut fruit_salad = FRUITS[..5].to_vec();
     fruit_salad.sort(); // Challenge(2): Sort the fruit salad
-    fruit_salad.push("figs");
+    fruit_salad.push("Fig");
     println!("Modified fruit salad: {:?}, length: {}\n", fruit_salad, fruit_salad.len());
 
     // Challenge(1): Remove a fruit from the salad 
     let mut rng = thread_rng();
-    let fruit_to_remove = *fruit_salad.choose(&mut rng).expect("Should have chosen a fruit");
+    let fruit_to_remove = *fruit_catalogue::choose(&fruit_salad, &mut rng).expect("Should have chosen a fruit");
     println!("Fruit to remove: {}", fruit_to_remove);
 
     remove_fruit(&mut fruit_salad, fruit_to_remove);